@@ -1,6 +1,10 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{cell::RefCell, env, process, rc::Rc, time::Duration, time::Instant};
 extern crate rust;
 use rust::linked_lists::sllwt_rc_refcell::{LinkedList, Node};
+use rust::queues::binary_heap::{self, IndexedHeap, MinFirst};
+use rust::queues::fibonacci_heap::{self, FibonacciHeap};
+use rust::queues::pairing_heap::{self, PairingHeap};
+use rust::stacks::expression::{self, Environment, Instruction, Spanned, Value};
 
 // fn main() {
 //     let data_vec: Vec<i64> = vec![2];
@@ -20,6 +24,233 @@ use rust::linked_lists::sllwt_rc_refcell::{LinkedList, Node};
 // }
 
 fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match args.first().map(String::as_str) {
+        Some("expr") => expression_command(&args[1..]),
+        Some("bench") => heap_bench(&args[1..]),
+        _ => list_demo(),
+    }
+}
+
+/// `linked_list expr "<expression>" [name=value ...]` prints the postfix form
+/// and the value, with the standard functions and the given variables.
+fn expression_command(args: &[String]) {
+    let input: &str = match args.first() {
+        Some(input) => input,
+        None => {
+            eprintln!("usage: linked_list expr \"<expression>\" [name=value ...]");
+            process::exit(2);
+        }
+    };
+
+    let mut environment: Environment = Environment::standard();
+    for assignment in &args[1..] {
+        let parsed: Option<(&str, Value)> = assignment.split_once('=').and_then(|(name, value)| match value {
+            "true" => Some((name, Value::Boolean(true))),
+            "false" => Some((name, Value::Boolean(false))),
+            _ => value.parse::<f64>().ok().map(|number| (name, Value::Number(number))),
+        });
+        match parsed {
+            Some((name, value)) => environment.set_variable(name, value),
+            None => {
+                eprintln!("bad variable '{}', expected name=value", assignment);
+                process::exit(2);
+            }
+        }
+    }
+
+    let program: Result<Vec<Instruction>, expression::ExpressionError> = expression::check_brackets(input)
+        .and_then(|_| expression::tokenize(input))
+        .and_then(|tokens: Vec<Spanned>| expression::to_postfix(&tokens));
+    let result: Result<Value, expression::ExpressionError> = program.and_then(|program| {
+        println!("postfix: {}", expression::postfix_string(&program));
+        expression::evaluate(&program, &environment)
+    });
+
+    match result {
+        Ok(value) => println!("value: {}", value),
+        Err(error) => {
+            eprintln!("{}", input);
+            eprintln!("{}^", " ".repeat(error_position(&error)));
+            eprintln!("error: {}", error);
+            process::exit(1);
+        }
+    }
+}
+
+fn error_position(error: &expression::ExpressionError) -> usize {
+    use expression::ExpressionError::*;
+
+    match error {
+        UnexpectedCharacter { position, .. }
+        | UnclosedBracket { position, .. }
+        | UnopenedBracket { position, .. }
+        | UnexpectedToken { position }
+        | UnknownVariable { position, .. }
+        | UnknownFunction { position, .. }
+        | WrongArgumentCount { position, .. }
+        | TypeMismatch { position }
+        | DivisionByZero { position } => *position,
+        MismatchedBracket { close, .. } => *close,
+    }
+}
+
+/// `linked_list bench [vertices] [rounds]` times Dijkstra's algorithm on a
+/// complete graph with random weights, once per heap. Every vertex is
+/// inserted up front and improved with decrease-key, which is where the
+/// pairing and Fibonacci heaps should pull ahead of the binary heap.
+fn heap_bench(args: &[String]) {
+    let parse = |index: usize, default: usize| -> usize {
+        match args.get(index).map(|arg| arg.parse::<usize>()) {
+            None => default,
+            Some(Ok(value)) if value > 0 => value,
+            Some(_) => {
+                eprintln!("usage: linked_list bench [vertices] [rounds]");
+                process::exit(2);
+            }
+        }
+    };
+    let (vertices, rounds): (usize, usize) = (parse(0, 2000), parse(1, 3));
+
+    let weights: Vec<Vec<u64>> = random_weights(vertices, 0x2545_f491_4f6c_dd1d);
+    println!("dijkstra on a complete graph: {} vertices, {} edges, best of {}", vertices, vertices * (vertices - 1), rounds);
+
+    let (binary, binary_time): (Vec<u64>, Duration) = time_rounds(rounds, || dijkstra(IndexedHeap::new(MinFirst), &weights));
+    let (pairing, pairing_time): (Vec<u64>, Duration) = time_rounds(rounds, || dijkstra(PairingHeap::new(), &weights));
+    let (fibonacci, fibonacci_time): (Vec<u64>, Duration) = time_rounds(rounds, || dijkstra(FibonacciHeap::new(), &weights));
+
+    if binary != pairing || binary != fibonacci {
+        eprintln!("error: the heaps disagree on the shortest distances");
+        process::exit(1);
+    }
+
+    for (name, time) in [("binary", binary_time), ("pairing", pairing_time), ("fibonacci", fibonacci_time)] {
+        println!("{:>10}: {:>9.2} ms", name, time.as_secs_f64() * 1000.0);
+    }
+}
+
+fn time_rounds<F: FnMut() -> Vec<u64>>(rounds: usize, mut run: F) -> (Vec<u64>, Duration) {
+    let mut best: Duration = Duration::MAX;
+    let mut result: Vec<u64> = Vec::new();
+
+    for _ in 0..rounds {
+        let start: Instant = Instant::now();
+        result = run();
+        best = best.min(start.elapsed());
+    }
+
+    (result, best)
+}
+
+/// An adjacency matrix of weights in `1..=1000` from a xorshift generator,
+/// so every run of the benchmark sees the same graph.
+fn random_weights(vertices: usize, mut state: u64) -> Vec<Vec<u64>> {
+    let mut next = || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state % 1000 + 1
+    };
+
+    (0..vertices).map(|_| (0..vertices).map(|_| next()).collect()).collect()
+}
+
+/// The operations Dijkstra's algorithm needs from a heap of
+/// `(distance, vertex)` pairs.
+trait DecreaseKeyHeap {
+    type Handle;
+
+    fn insert(&mut self, entry: (u64, usize)) -> Self::Handle;
+
+    fn delete_min(&mut self) -> Option<(u64, usize)>;
+
+    /// The entry behind `handle` while it is still in the heap.
+    fn get(&self, handle: &Self::Handle) -> Option<(u64, usize)>;
+
+    fn decrease_key(&mut self, handle: &Self::Handle, entry: (u64, usize));
+}
+
+impl DecreaseKeyHeap for IndexedHeap<(u64, usize), MinFirst> {
+    type Handle = binary_heap::Handle;
+
+    fn insert(&mut self, entry: (u64, usize)) -> Self::Handle {
+        self.push(entry)
+    }
+
+    fn delete_min(&mut self) -> Option<(u64, usize)> {
+        self.pop().map(|(_, entry)| entry)
+    }
+
+    fn get(&self, handle: &Self::Handle) -> Option<(u64, usize)> {
+        IndexedHeap::get(self, *handle).copied()
+    }
+
+    fn decrease_key(&mut self, handle: &Self::Handle, entry: (u64, usize)) {
+        let _ = IndexedHeap::decrease_key(self, *handle, entry);
+    }
+}
+
+impl DecreaseKeyHeap for PairingHeap<(u64, usize)> {
+    type Handle = pairing_heap::Handle<(u64, usize)>;
+
+    fn insert(&mut self, entry: (u64, usize)) -> Self::Handle {
+        PairingHeap::insert(self, entry)
+    }
+
+    fn delete_min(&mut self) -> Option<(u64, usize)> {
+        PairingHeap::delete_min(self)
+    }
+
+    fn get(&self, handle: &Self::Handle) -> Option<(u64, usize)> {
+        PairingHeap::get(self, handle)
+    }
+
+    fn decrease_key(&mut self, handle: &Self::Handle, entry: (u64, usize)) {
+        let _ = PairingHeap::decrease_key(self, handle, entry);
+    }
+}
+
+impl DecreaseKeyHeap for FibonacciHeap<(u64, usize)> {
+    type Handle = fibonacci_heap::Handle<(u64, usize)>;
+
+    fn insert(&mut self, entry: (u64, usize)) -> Self::Handle {
+        FibonacciHeap::insert(self, entry)
+    }
+
+    fn delete_min(&mut self) -> Option<(u64, usize)> {
+        FibonacciHeap::delete_min(self)
+    }
+
+    fn get(&self, handle: &Self::Handle) -> Option<(u64, usize)> {
+        FibonacciHeap::get(self, handle)
+    }
+
+    fn decrease_key(&mut self, handle: &Self::Handle, entry: (u64, usize)) {
+        let _ = FibonacciHeap::decrease_key(self, handle, entry);
+    }
+}
+
+/// Shortest distances from vertex 0.
+fn dijkstra<H: DecreaseKeyHeap>(mut heap: H, weights: &[Vec<u64>]) -> Vec<u64> {
+    let handles: Vec<H::Handle> = (0..weights.len()).map(|vertex| heap.insert((if vertex == 0 { 0 } else { u64::MAX }, vertex))).collect();
+    let mut distances: Vec<u64> = vec![u64::MAX; weights.len()];
+
+    while let Some((distance, vertex)) = heap.delete_min() {
+        distances[vertex] = distance;
+        for (next, weight) in weights[vertex].iter().enumerate() {
+            if let Some((known, _)) = heap.get(&handles[next]) {
+                if distance + weight < known {
+                    heap.decrease_key(&handles[next], (distance + weight, next));
+                }
+            }
+        }
+    }
+
+    distances
+}
+
+fn list_demo() {
     let (index, value): (usize, i64) = (8, 2);
     let (index_1, value_1): (usize, i64) = (2, 37);
 
@@ -0,0 +1,421 @@
+use std::fmt::{Debug, Display, Error, Formatter};
+use std::mem::{ManuallyDrop, MaybeUninit};
+use std::ops::{Deref, DerefMut};
+use std::ptr;
+use std::slice;
+
+/// Returned when an element does not fit in a fixed-capacity array. It hands
+/// the element back so the caller doesn't lose it.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct CapacityError<T>(pub T);
+
+impl<T> CapacityError<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Display for CapacityError<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        write!(f, "the array is full")
+    }
+}
+
+/// Why an insert failed. Both variants hand the element back.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum InsertError<T> {
+    /// The array was already full.
+    Full(T),
+    /// The index was past the length.
+    OutOfBounds(T),
+}
+
+impl<T> InsertError<T> {
+    pub fn into_inner(self) -> T {
+        match self {
+            InsertError::Full(data) | InsertError::OutOfBounds(data) => data,
+        }
+    }
+}
+
+impl<T> Display for InsertError<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match self {
+            InsertError::Full(_) => write!(f, "the array is full"),
+            InsertError::OutOfBounds(_) => write!(f, "the index is past the length"),
+        }
+    }
+}
+
+/// A vector whose `N` slots live inline, so it never allocates. Only
+/// `elements[..length]` are initialised.
+pub struct ArrayList<T, const N: usize> {
+    elements: [MaybeUninit<T>; N],
+    length: usize,
+}
+
+impl<T, const N: usize> ArrayList<T, N> {
+    pub const fn new() -> Self {
+        ArrayList { elements: [const { MaybeUninit::uninit() }; N], length: 0 }
+    }
+
+    pub fn from(data_vec: Vec<T>) -> Result<Self, CapacityError<Vec<T>>> {
+        if data_vec.len() > N {
+            return Err(CapacityError(data_vec));
+        }
+
+        let mut list: ArrayList<T, N> = ArrayList::new();
+        for data in data_vec {
+            let _ = list.push(data);
+        }
+
+        return Ok(list);
+    }
+
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    pub fn len(&self) -> usize {
+        return self.length;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.length == 0;
+    }
+
+    pub fn is_full(&self) -> bool {
+        return self.length == N;
+    }
+
+    pub fn push(&mut self, data: T) -> Result<(), CapacityError<T>> {
+        if self.length == N {
+            return Err(CapacityError(data));
+        }
+
+        self.elements[self.length].write(data);
+        self.length += 1;
+        return Ok(());
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        if self.length == 0 {
+            return None;
+        }
+
+        self.length -= 1;
+        // SAFETY: the slot was initialised and is now outside `..length`.
+        return Some(unsafe { self.elements[self.length].assume_init_read() });
+    }
+
+    /// Inserts `data` at `index`, shifting later elements up.
+    pub fn insert(&mut self, index: usize, data: T) -> Result<(), InsertError<T>> {
+        if index > self.length {
+            return Err(InsertError::OutOfBounds(data));
+        }
+        if self.length == N {
+            return Err(InsertError::Full(data));
+        }
+
+        // SAFETY: there is a spare slot, so `index..length` can move up one.
+        unsafe {
+            let slot: *mut T = self.as_mut_ptr().add(index);
+            ptr::copy(slot, slot.add(1), self.length - index);
+            slot.write(data);
+        }
+        self.length += 1;
+        return Ok(());
+    }
+
+    pub fn remove(&mut self, index: usize) -> Result<T, Error> {
+        if index >= self.length {
+            return Err(Error);
+        }
+
+        // SAFETY: `index` is initialised; the tail is shifted over it.
+        let data: T = unsafe {
+            let slot: *mut T = self.as_mut_ptr().add(index);
+            let data: T = slot.read();
+            ptr::copy(slot.add(1), slot, self.length - index - 1);
+            data
+        };
+        self.length -= 1;
+        return Ok(data);
+    }
+
+    pub fn truncate(&mut self, length: usize) {
+        if length >= self.length {
+            return;
+        }
+
+        let dropped: *mut [T] = &mut self.as_mut_slice()[length..];
+        // Shorten first so a panicking drop can't cause a double drop.
+        self.length = length;
+        // SAFETY: the dropped elements were initialised and are now unreachable.
+        unsafe { ptr::drop_in_place(dropped) };
+    }
+
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    fn as_mut_ptr(&mut self) -> *mut T {
+        self.elements.as_mut_ptr() as *mut T
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        // SAFETY: `..length` is initialised.
+        unsafe { slice::from_raw_parts(self.elements.as_ptr() as *const T, self.length) }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        // SAFETY: `..length` is initialised and `&mut self` is unique.
+        unsafe { slice::from_raw_parts_mut(self.as_mut_ptr(), self.length) }
+    }
+}
+
+impl<T, const N: usize> Drop for ArrayList<T, N> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T, const N: usize> Deref for ArrayList<T, N> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, const N: usize> DerefMut for ArrayList<T, N> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl<T: Clone, const N: usize> Clone for ArrayList<T, N> {
+    fn clone(&self) -> Self {
+        let mut list: ArrayList<T, N> = ArrayList::new();
+
+        for data in self.iter() {
+            let _ = list.push(data.clone());
+        }
+
+        return list;
+    }
+}
+
+impl<T: Debug, const N: usize> Debug for ArrayList<T, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq, const N: usize> PartialEq for ArrayList<T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        return self.as_slice() == other.as_slice();
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a ArrayList<T, N> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> slice::Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a mut ArrayList<T, N> {
+    type Item = &'a mut T;
+    type IntoIter = slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> slice::IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+impl<T, const N: usize> IntoIterator for ArrayList<T, N> {
+    type Item = T;
+    type IntoIter = IntoIter<T, N>;
+
+    fn into_iter(self) -> IntoIter<T, N> {
+        let list: ManuallyDrop<ArrayList<T, N>> = ManuallyDrop::new(self);
+        // SAFETY: `list` is never dropped, so the elements are moved out
+        // exactly once, into the iterator.
+        let elements: [MaybeUninit<T>; N] = unsafe { ptr::read(&list.elements) };
+        IntoIter { elements, start: 0, end: list.length }
+    }
+}
+
+pub struct IntoIter<T, const N: usize> {
+    elements: [MaybeUninit<T>; N],
+    start: usize,
+    end: usize,
+}
+
+impl<T, const N: usize> Iterator for IntoIter<T, N> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.start == self.end {
+            return None;
+        }
+
+        self.start += 1;
+        // SAFETY: `start..end` are initialised and each is read once.
+        return Some(unsafe { self.elements[self.start - 1].assume_init_read() });
+    }
+}
+
+impl<T, const N: usize> Drop for IntoIter<T, N> {
+    fn drop(&mut self) {
+        for slot in &mut self.elements[self.start..self.end] {
+            // SAFETY: the elements that were not taken are still initialised.
+            unsafe { slot.assume_init_drop() };
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    struct DropCounter(Rc<Cell<usize>>);
+
+    impl Drop for DropCounter {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    #[test]
+    fn test_array_list_new() {
+        let list: ArrayList<i64, 4> = ArrayList::new();
+        assert!(list.is_empty());
+        assert_eq!(list.capacity(), 4);
+        assert_eq!(list.first(), None);
+    }
+
+    #[test]
+    fn test_array_list_from() {
+        let list: ArrayList<i64, 4> = ArrayList::from(vec![1, 2, 3]).unwrap();
+        assert_eq!(list.as_slice(), &[1, 2, 3]);
+        assert_eq!(ArrayList::<i64, 2>::from(vec![1, 2, 3]), Err(CapacityError(vec![1, 2, 3])));
+    }
+
+    #[test]
+    fn test_push_until_full() {
+        let mut list: ArrayList<i64, 2> = ArrayList::new();
+        assert_eq!(list.push(1), Ok(()));
+        assert_eq!(list.push(2), Ok(()));
+        assert!(list.is_full());
+        assert_eq!(list.push(3), Err(CapacityError(3)));
+        assert_eq!(list.push(3).unwrap_err().into_inner(), 3);
+        assert_eq!(list.as_slice(), &[1, 2]);
+    }
+
+    #[test]
+    fn test_pop() {
+        let mut list: ArrayList<i64, 3> = ArrayList::from(vec![1, 2]).unwrap();
+        assert_eq!(list.pop(), Some(2));
+        assert_eq!(list.pop(), Some(1));
+        assert_eq!(list.pop(), None);
+    }
+
+    #[test]
+    fn test_insert_and_remove() {
+        let mut list: ArrayList<i64, 4> = ArrayList::from(vec![1, 3]).unwrap();
+        assert_eq!(list.insert(1, 2), Ok(()));
+        assert_eq!(list.insert(0, 0), Ok(()));
+        assert_eq!(list.insert(2, 9), Err(InsertError::Full(9)));
+        assert_eq!(list.as_slice(), &[0, 1, 2, 3]);
+
+        assert_eq!(list.remove(1), Ok(1));
+        assert!(list.remove(3).is_err());
+        assert_eq!(list.as_slice(), &[0, 2, 3]);
+    }
+
+    #[test]
+    fn test_insert_past_length() {
+        let mut list: ArrayList<i64, 4> = ArrayList::new();
+        assert_eq!(list.insert(1, 1), Err(InsertError::OutOfBounds(1)));
+        assert_eq!(list.insert(1, 1).map_err(InsertError::into_inner), Err(1));
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn test_slice_deref() {
+        let mut list: ArrayList<i64, 8> = ArrayList::from(vec![3, 1, 2]).unwrap();
+        list.sort();
+        assert_eq!(list[0], 1);
+        assert_eq!(list.len(), 3);
+        assert!(list.contains(&3));
+        list[2] = 30;
+        assert_eq!(list.iter().sum::<i64>(), 33);
+    }
+
+    #[test]
+    fn test_zero_capacity() {
+        let mut list: ArrayList<i64, 0> = ArrayList::new();
+        assert_eq!(list.push(1), Err(CapacityError(1)));
+        assert!(list.is_full());
+    }
+
+    #[test]
+    fn test_drop_only_initialised_prefix() {
+        let drops: Rc<Cell<usize>> = Rc::new(Cell::new(0));
+        let mut list: ArrayList<DropCounter, 8> = ArrayList::new();
+        for _ in 0..3 {
+            let _ = list.push(DropCounter(drops.clone()));
+        }
+
+        drop(list);
+        assert_eq!(drops.get(), 3);
+    }
+
+    #[test]
+    fn test_truncate_and_remove_drop_once() {
+        let drops: Rc<Cell<usize>> = Rc::new(Cell::new(0));
+        let mut list: ArrayList<DropCounter, 8> = ArrayList::new();
+        for _ in 0..6 {
+            let _ = list.push(DropCounter(drops.clone()));
+        }
+
+        drop(list.remove(0));
+        list.truncate(3);
+        assert_eq!(drops.get(), 3);
+
+        let rejected: CapacityError<DropCounter> = ArrayList::<DropCounter, 0>::new()
+            .push(DropCounter(drops.clone()))
+            .unwrap_err();
+        drop(rejected);
+        assert_eq!(drops.get(), 4);
+
+        drop(list);
+        assert_eq!(drops.get(), 7);
+    }
+
+    #[test]
+    fn test_into_iter() {
+        let drops: Rc<Cell<usize>> = Rc::new(Cell::new(0));
+        let mut list: ArrayList<DropCounter, 4> = ArrayList::new();
+        for _ in 0..4 {
+            let _ = list.push(DropCounter(drops.clone()));
+        }
+
+        let mut iter: IntoIter<DropCounter, 4> = list.into_iter();
+        drop(iter.next());
+        assert_eq!(drops.get(), 1);
+        drop(iter);
+        assert_eq!(drops.get(), 4);
+
+        let values: ArrayList<i64, 4> = ArrayList::from(vec![1, 2, 3]).unwrap();
+        assert_eq!(values.into_iter().collect::<Vec<i64>>(), vec![1, 2, 3]);
+    }
+}
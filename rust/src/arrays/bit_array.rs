@@ -0,0 +1,415 @@
+use std::fmt::{Debug, Error, Formatter};
+use std::ops::Range;
+use std::sync::OnceLock;
+
+const WORD_BITS: usize = 64;
+
+/// How many words share one rank sample: one `usize` per 512 bits.
+const SAMPLE_WORDS: usize = 8;
+
+/// A fixed-length array of bits packed 64 to a `u64` word.
+///
+/// Bits past `length` in the last word are always zero, so whole-word
+/// operations like `count_ones` never need masking.
+///
+/// `rank` and `select` use a sampled index holding the number of set bits
+/// before every `SAMPLE_WORDS`th word. It is built on the first query and
+/// thrown away by any write. The cache is a `OnceLock`, so a `BitArray`
+/// can still be queried from several threads at once.
+pub struct BitArray {
+    words: Vec<u64>,
+    length: usize,
+    rank_samples: OnceLock<Vec<usize>>,
+}
+
+impl BitArray {
+    pub fn new(length: usize) -> Self {
+        BitArray { words: vec![0; length.div_ceil(WORD_BITS)], length, rank_samples: OnceLock::new() }
+    }
+
+    pub fn from(bits: Vec<bool>) -> Self {
+        let mut array: BitArray = BitArray::new(bits.len());
+
+        for (index, bit) in bits.into_iter().enumerate() {
+            if bit {
+                array.words[index / WORD_BITS] |= 1 << (index % WORD_BITS);
+            }
+        }
+
+        return array;
+    }
+
+    pub fn len(&self) -> usize {
+        return self.length;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.length == 0;
+    }
+
+    pub fn get(&self, index: usize) -> Option<bool> {
+        if index >= self.length {
+            return None;
+        }
+        Some(self.words[index / WORD_BITS] & (1 << (index % WORD_BITS)) != 0)
+    }
+
+    fn changed(&mut self) {
+        self.rank_samples.take();
+    }
+
+    pub fn set(&mut self, index: usize, value: bool) -> Result<(), Error> {
+        if index >= self.length {
+            return Err(Error);
+        }
+
+        let mask: u64 = 1 << (index % WORD_BITS);
+        match value {
+            true => self.words[index / WORD_BITS] |= mask,
+            false => self.words[index / WORD_BITS] &= !mask,
+        }
+        self.changed();
+        return Ok(());
+    }
+
+    pub fn flip(&mut self, index: usize) -> Result<(), Error> {
+        if index >= self.length {
+            return Err(Error);
+        }
+
+        self.words[index / WORD_BITS] ^= 1 << (index % WORD_BITS);
+        self.changed();
+        return Ok(());
+    }
+
+    /// Applies `update(word, mask)` to each word the range touches, where
+    /// `mask` covers the bits of that word inside the range.
+    fn update_range(&mut self, range: Range<usize>, update: fn(&mut u64, u64)) -> Result<(), Error> {
+        if range.start > range.end || range.end > self.length {
+            return Err(Error);
+        }
+        if range.is_empty() {
+            return Ok(());
+        }
+
+        let first: usize = range.start / WORD_BITS;
+        let last: usize = (range.end - 1) / WORD_BITS;
+        for (offset, word) in self.words[first..=last].iter_mut().enumerate() {
+            let word_start: usize = (first + offset) * WORD_BITS;
+            let low: usize = range.start.max(word_start) - word_start;
+            let high: usize = range.end.min(word_start + WORD_BITS) - word_start;
+            let mask: u64 = (u64::MAX >> (WORD_BITS - (high - low))) << low;
+            update(word, mask);
+        }
+
+        self.changed();
+        return Ok(());
+    }
+
+    pub fn set_range(&mut self, range: Range<usize>) -> Result<(), Error> {
+        self.update_range(range, |word, mask| *word |= mask)
+    }
+
+    pub fn clear_range(&mut self, range: Range<usize>) -> Result<(), Error> {
+        self.update_range(range, |word, mask| *word &= !mask)
+    }
+
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+        self.changed();
+    }
+
+    pub fn count_ones(&self) -> usize {
+        return self.words.iter().map(|word| word.count_ones() as usize).sum();
+    }
+
+    pub fn count_zeros(&self) -> usize {
+        return self.length - self.count_ones();
+    }
+
+    /// The indexes of the set bits, in increasing order.
+    pub fn iter_ones(&self) -> Ones<'_> {
+        Ones { words: &self.words, word_index: 0, current: self.words.first().copied().unwrap_or(0) }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+        (0..self.length).map(|index| self.words[index / WORD_BITS] & (1 << (index % WORD_BITS)) != 0)
+    }
+
+    fn combine(&mut self, other: &BitArray, operation: fn(u64, u64) -> u64) -> Result<(), Error> {
+        if self.length != other.length {
+            return Err(Error);
+        }
+
+        for (word, other_word) in self.words.iter_mut().zip(other.words.iter()) {
+            *word = operation(*word, *other_word);
+        }
+        self.changed();
+        return Ok(());
+    }
+
+    /// Both arrays must have the same length, as for the other set operations.
+    pub fn union_with(&mut self, other: &BitArray) -> Result<(), Error> {
+        self.combine(other, |a, b| a | b)
+    }
+
+    pub fn intersect_with(&mut self, other: &BitArray) -> Result<(), Error> {
+        self.combine(other, |a, b| a & b)
+    }
+
+    pub fn difference_with(&mut self, other: &BitArray) -> Result<(), Error> {
+        self.combine(other, |a, b| a & !b)
+    }
+
+    pub fn symmetric_difference_with(&mut self, other: &BitArray) -> Result<(), Error> {
+        self.combine(other, |a, b| a ^ b)
+    }
+
+    fn with_samples<R>(&self, query: impl FnOnce(&[usize]) -> R) -> R {
+        let samples: &Vec<usize> = self.rank_samples.get_or_init(|| {
+            let mut total: usize = 0;
+            self.words
+                .chunks(SAMPLE_WORDS)
+                .map(|block| {
+                    let before: usize = total;
+                    total += block.iter().map(|word| word.count_ones() as usize).sum::<usize>();
+                    before
+                })
+                .collect()
+        });
+        query(samples)
+    }
+
+    /// The number of set bits before `index`.
+    pub fn rank(&self, index: usize) -> Result<usize, Error> {
+        if index > self.length {
+            return Err(Error);
+        }
+
+        let word: usize = index / WORD_BITS;
+        let sample: usize = word / SAMPLE_WORDS;
+        let mut rank: usize = match sample * SAMPLE_WORDS < self.words.len() {
+            true => self.with_samples(|samples| samples[sample]),
+            false => self.count_ones(),
+        };
+
+        for full_word in &self.words[sample * SAMPLE_WORDS..word] {
+            rank += full_word.count_ones() as usize;
+        }
+        if !index.is_multiple_of(WORD_BITS) {
+            rank += (self.words[word] & (u64::MAX >> (WORD_BITS - index % WORD_BITS))).count_ones() as usize;
+        }
+
+        return Ok(rank);
+    }
+
+    /// The index of the set bit with rank `nth`, counting from zero.
+    pub fn select(&self, nth: usize) -> Option<usize> {
+        let (sample, before): (usize, usize) = self.with_samples(|samples| {
+            let sample: usize = samples.partition_point(|before| *before <= nth).checked_sub(1)?;
+            Some((sample, samples[sample]))
+        })?;
+
+        let mut remaining: usize = nth - before;
+        for (offset, word) in self.words[sample * SAMPLE_WORDS..].iter().enumerate() {
+            let ones: usize = word.count_ones() as usize;
+            if remaining < ones {
+                let mut word: u64 = *word;
+                for _ in 0..remaining {
+                    word &= word - 1;
+                }
+                return Some((sample * SAMPLE_WORDS + offset) * WORD_BITS + word.trailing_zeros() as usize);
+            }
+            remaining -= ones;
+        }
+
+        return None;
+    }
+}
+
+impl Clone for BitArray {
+    fn clone(&self) -> Self {
+        BitArray { words: self.words.clone(), length: self.length, rank_samples: OnceLock::new() }
+    }
+}
+
+impl PartialEq for BitArray {
+    fn eq(&self, other: &Self) -> bool {
+        return self.length == other.length && self.words == other.words;
+    }
+}
+
+impl Debug for BitArray {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        for bit in self.iter() {
+            write!(f, "{}", if bit { '1' } else { '0' })?;
+        }
+        return Ok(());
+    }
+}
+
+pub struct Ones<'a> {
+    words: &'a [u64],
+    word_index: usize,
+    current: u64,
+}
+
+impl Iterator for Ones<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        while self.current == 0 {
+            self.word_index += 1;
+            self.current = *self.words.get(self.word_index)?;
+        }
+
+        let bit: usize = self.current.trailing_zeros() as usize;
+        self.current &= self.current - 1;
+        return Some(self.word_index * WORD_BITS + bit);
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn from_indexes(length: usize, indexes: &[usize]) -> BitArray {
+        let mut array: BitArray = BitArray::new(length);
+        for index in indexes {
+            let _ = array.set(*index, true);
+        }
+        return array;
+    }
+
+    #[test]
+    fn test_bit_array_new() {
+        let array: BitArray = BitArray::new(100);
+        assert_eq!(array.len(), 100);
+        assert_eq!(array.count_ones(), 0);
+        assert_eq!(array.get(99), Some(false));
+        assert_eq!(array.get(100), None);
+        assert!(BitArray::new(0).is_empty());
+    }
+
+    #[test]
+    fn test_get_set_flip() {
+        let mut array: BitArray = BitArray::from(vec![true, false, true]);
+        assert_eq!(format!("{:?}", array), "101");
+        assert_eq!(array.set(1, true), Ok(()));
+        assert_eq!(array.flip(0), Ok(()));
+        assert_eq!(format!("{:?}", array), "011");
+        assert!(array.set(3, true).is_err());
+        assert!(array.flip(3).is_err());
+        assert_eq!(array.count_zeros(), 1);
+    }
+
+    #[test]
+    fn test_ranges() {
+        let mut array: BitArray = BitArray::new(200);
+        assert_eq!(array.set_range(10..150), Ok(()));
+        assert_eq!(array.count_ones(), 140);
+        assert_eq!(array.get(9), Some(false));
+        assert_eq!(array.get(10), Some(true));
+        assert_eq!(array.get(149), Some(true));
+        assert_eq!(array.get(150), Some(false));
+
+        assert_eq!(array.clear_range(64..128), Ok(()));
+        assert_eq!(array.count_ones(), 76);
+        assert_eq!(array.set_range(0..200), Ok(()));
+        assert_eq!(array.count_ones(), 200);
+        assert_eq!(array.clear_range(5..5), Ok(()));
+        assert!(array.set_range(190..201).is_err());
+
+        array.clear();
+        assert_eq!(array.count_ones(), 0);
+    }
+
+    #[test]
+    fn test_iter_ones() {
+        let indexes: Vec<usize> = vec![0, 3, 63, 64, 65, 200, 299];
+        let array: BitArray = from_indexes(300, &indexes);
+        assert_eq!(array.iter_ones().collect::<Vec<usize>>(), indexes);
+        assert_eq!(BitArray::new(300).iter_ones().next(), None);
+        assert_eq!(BitArray::new(0).iter_ones().next(), None);
+    }
+
+    #[test]
+    fn test_set_algebra() {
+        let a: BitArray = from_indexes(130, &[1, 2, 70, 129]);
+        let b: BitArray = from_indexes(130, &[2, 3, 129]);
+
+        let mut union: BitArray = a.clone();
+        assert_eq!(union.union_with(&b), Ok(()));
+        assert_eq!(union.iter_ones().collect::<Vec<usize>>(), vec![1, 2, 3, 70, 129]);
+
+        let mut intersection: BitArray = a.clone();
+        assert_eq!(intersection.intersect_with(&b), Ok(()));
+        assert_eq!(intersection.iter_ones().collect::<Vec<usize>>(), vec![2, 129]);
+
+        let mut difference: BitArray = a.clone();
+        assert_eq!(difference.difference_with(&b), Ok(()));
+        assert_eq!(difference.iter_ones().collect::<Vec<usize>>(), vec![1, 70]);
+
+        let mut xor: BitArray = a.clone();
+        assert_eq!(xor.symmetric_difference_with(&b), Ok(()));
+        assert_eq!(xor.iter_ones().collect::<Vec<usize>>(), vec![1, 3, 70]);
+
+        assert!(xor.union_with(&BitArray::new(10)).is_err());
+    }
+
+    #[test]
+    fn test_rank_and_select() {
+        let length: usize = 5000;
+        let indexes: Vec<usize> = (0..length).filter(|index| index % 7 == 0 || index % 11 == 3).collect();
+        let array: BitArray = from_indexes(length, &indexes);
+
+        for (nth, index) in indexes.iter().enumerate() {
+            assert_eq!(array.select(nth), Some(*index));
+            assert_eq!(array.rank(*index), Ok(nth));
+            assert_eq!(array.rank(*index + 1), Ok(nth + 1));
+        }
+        assert_eq!(array.select(indexes.len()), None);
+        assert_eq!(array.rank(length), Ok(indexes.len()));
+        assert!(array.rank(length + 1).is_err());
+    }
+
+    #[test]
+    fn test_rank_after_write() {
+        let mut array: BitArray = from_indexes(1024, &[5, 600]);
+        assert_eq!(array.rank(1024), Ok(2));
+        assert_eq!(array.select(1), Some(600));
+
+        let _ = array.set(700, true);
+        let _ = array.set(5, false);
+        assert_eq!(array.rank(1024), Ok(2));
+        assert_eq!(array.rank(650), Ok(1));
+        assert_eq!(array.select(1), Some(700));
+    }
+
+    #[test]
+    fn test_shared_queries_across_threads() {
+        let array: BitArray = from_indexes(1 << 20, &(0..1 << 20).step_by(3).collect::<Vec<usize>>());
+        std::thread::scope(|scope| {
+            for thread in 0..4 {
+                let array: &BitArray = &array;
+                scope.spawn(move || {
+                    for nth in (thread..1000).step_by(4) {
+                        assert_eq!(array.select(nth), Some(3 * nth));
+                        assert_eq!(array.rank(3 * nth), Ok(nth));
+                    }
+                });
+            }
+        });
+    }
+
+    #[test]
+    fn test_rank_on_word_boundary() {
+        let array: BitArray = from_indexes(512, &[511]);
+        assert_eq!(array.rank(512), Ok(1));
+        assert_eq!(array.rank(511), Ok(0));
+        assert_eq!(BitArray::new(0).rank(0), Ok(0));
+        assert_eq!(BitArray::new(0).select(0), None);
+    }
+}
@@ -0,0 +1,663 @@
+use std::alloc::{self, Layout};
+use std::fmt::{Debug, Error, Formatter};
+use std::mem::{self, size_of};
+use std::ptr::{self, NonNull};
+use std::slice;
+
+/// When and how much a `DynamicArray` grows and shrinks.
+///
+/// A full array grows to `capacity * growth_factor` (at least one more slot
+/// and at least `min_capacity`). After a pop or remove leaves fewer than
+/// `capacity * shrink_threshold` elements, the capacity is cut down to
+/// `length * growth_factor`, so the next push doesn't have to grow again
+/// straight away. A `shrink_threshold` of zero never shrinks.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct GrowthPolicy {
+    pub growth_factor: f64,
+    pub shrink_threshold: f64,
+    pub min_capacity: usize,
+}
+
+impl GrowthPolicy {
+    pub fn new() -> Self {
+        GrowthPolicy { growth_factor: 2.0, shrink_threshold: 0.25, min_capacity: 4 }
+    }
+
+    pub fn grown_capacity(&self, capacity: usize) -> usize {
+        let grown: usize = (capacity as f64 * self.growth_factor).ceil() as usize;
+        return grown.max(capacity + 1).max(self.min_capacity);
+    }
+
+    pub fn should_shrink(&self, length: usize, capacity: usize) -> bool {
+        return capacity > self.min_capacity && (length as f64) < capacity as f64 * self.shrink_threshold;
+    }
+
+    /// Never less than `length`, whatever the policy says, so shrinking
+    /// can't cut off live elements.
+    pub fn shrunk_capacity(&self, length: usize) -> usize {
+        let shrunk: usize = (length as f64 * self.growth_factor).ceil() as usize;
+        return shrunk.max(self.min_capacity).max(length);
+    }
+
+    /// The growth factor must be above one and the shrink threshold in
+    /// `0.0..1.0`. Their product must also stay below one: a shrink sizes
+    /// the buffer to `length * growth_factor` with `length` just under
+    /// `capacity * shrink_threshold`, so a larger product would "shrink" to
+    /// a bigger buffer.
+    pub fn is_valid(&self) -> bool {
+        return self.growth_factor > 1.0
+            && (0.0..1.0).contains(&self.shrink_threshold)
+            && self.shrink_threshold * self.growth_factor < 1.0;
+    }
+}
+
+/// The allocation behind a `DynamicArray`. It knows nothing about which
+/// slots are initialised; it only makes sure the memory is given back, even
+/// when dropping an element panics.
+struct RawBuffer<T> {
+    pointer: NonNull<T>,
+    capacity: usize,
+}
+
+impl<T> RawBuffer<T> {
+    fn new() -> Self {
+        let capacity: usize = if size_of::<T>() == 0 { usize::MAX } else { 0 };
+        RawBuffer { pointer: NonNull::dangling(), capacity }
+    }
+
+    fn layout(capacity: usize) -> Layout {
+        Layout::array::<T>(capacity).expect("capacity overflow")
+    }
+
+    /// Moves the buffer to an allocation of exactly `capacity` slots. The
+    /// caller makes sure every initialised slot fits in the new capacity.
+    fn resize(&mut self, capacity: usize) {
+        if size_of::<T>() == 0 || capacity == self.capacity {
+            return;
+        }
+
+        if capacity == 0 {
+            // SAFETY: the old allocation was made with this layout.
+            unsafe { alloc::dealloc(self.pointer.as_ptr() as *mut u8, RawBuffer::<T>::layout(self.capacity)) };
+            self.pointer = NonNull::dangling();
+            self.capacity = 0;
+            return;
+        }
+
+        let new_layout: Layout = RawBuffer::<T>::layout(capacity);
+        // SAFETY: `new_layout` has a non-zero size. When there is an old
+        // allocation it was made with the old layout and the same alignment.
+        let new_pointer: *mut u8 = unsafe {
+            if self.capacity == 0 {
+                alloc::alloc(new_layout)
+            } else {
+                let old_layout: Layout = RawBuffer::<T>::layout(self.capacity);
+                alloc::realloc(self.pointer.as_ptr() as *mut u8, old_layout, new_layout.size())
+            }
+        };
+
+        self.pointer = match NonNull::new(new_pointer as *mut T) {
+            Some(pointer) => pointer,
+            None => alloc::handle_alloc_error(new_layout),
+        };
+        self.capacity = capacity;
+    }
+}
+
+// SAFETY: the buffer owns its elements like a `Box<[T]>` would.
+unsafe impl<T: Send> Send for RawBuffer<T> {}
+unsafe impl<T: Sync> Sync for RawBuffer<T> {}
+
+impl<T> Drop for RawBuffer<T> {
+    fn drop(&mut self) {
+        self.resize(0);
+    }
+}
+
+/// A growable array that manages its own heap allocation through
+/// `std::alloc`, with the growth and shrink rules set by a `GrowthPolicy`.
+///
+/// Elements live in `buffer[..length]`; every slot past that is
+/// uninitialised memory.
+pub struct DynamicArray<T> {
+    buffer: RawBuffer<T>,
+    length: usize,
+    policy: GrowthPolicy,
+}
+
+impl<T> DynamicArray<T> {
+    pub fn new() -> Self {
+        DynamicArray::with_policy(GrowthPolicy::new())
+    }
+
+    pub fn with_policy(policy: GrowthPolicy) -> Self {
+        assert!(policy.is_valid(), "the growth factor must be above one and the shrink threshold below its inverse");
+        DynamicArray { buffer: RawBuffer::new(), length: 0, policy }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut array: DynamicArray<T> = DynamicArray::new();
+        array.reserve(capacity);
+        return array;
+    }
+
+    pub fn from(data_vec: Vec<T>) -> Self {
+        let mut array: DynamicArray<T> = DynamicArray::with_capacity(data_vec.len());
+
+        for data in data_vec {
+            array.push(data);
+        }
+
+        return array;
+    }
+
+    pub fn len(&self) -> usize {
+        return self.length;
+    }
+
+    pub fn capacity(&self) -> usize {
+        return self.buffer.capacity;
+    }
+
+    pub fn policy(&self) -> GrowthPolicy {
+        return self.policy;
+    }
+
+    /// Swaps in a new policy, which applies from the next push or pop. An
+    /// invalid policy is rejected and the old one kept.
+    pub fn set_policy(&mut self, policy: GrowthPolicy) -> Result<(), Error> {
+        if !policy.is_valid() {
+            return Err(Error);
+        }
+
+        self.policy = policy;
+        return Ok(());
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.length == 0;
+    }
+
+    fn pointer(&self) -> *mut T {
+        self.buffer.pointer.as_ptr()
+    }
+
+    /// Makes sure at least `additional` more elements fit without growing.
+    pub fn reserve(&mut self, additional: usize) {
+        let needed: usize = self.length.checked_add(additional).expect("capacity overflow");
+        if needed > self.capacity() {
+            self.buffer.resize(needed);
+        }
+    }
+
+    pub fn shrink_to_fit(&mut self) {
+        self.buffer.resize(self.length);
+    }
+
+    fn grow_if_full(&mut self) {
+        if self.length == self.capacity() {
+            let capacity: usize = self.policy.grown_capacity(self.capacity());
+            self.buffer.resize(capacity);
+        }
+    }
+
+    fn shrink_if_sparse(&mut self) {
+        if size_of::<T>() != 0 && self.policy.should_shrink(self.length, self.capacity()) {
+            let capacity: usize = self.policy.shrunk_capacity(self.length);
+            self.buffer.resize(capacity);
+        }
+    }
+
+    pub fn push(&mut self, data: T) {
+        self.grow_if_full();
+        // SAFETY: there is spare capacity at `length`.
+        unsafe { self.pointer().add(self.length).write(data) };
+        self.length += 1;
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        if self.length == 0 {
+            return None;
+        }
+
+        self.length -= 1;
+        // SAFETY: the slot at the old last index is initialised, and it is
+        // outside `..length` now, so it will not be read or dropped again.
+        let data: T = unsafe { self.pointer().add(self.length).read() };
+        self.shrink_if_sparse();
+        return Some(data);
+    }
+
+    pub fn insert(&mut self, index: usize, data: T) -> Result<(), Error> {
+        if index > self.length {
+            return Err(Error);
+        }
+
+        self.grow_if_full();
+        // SAFETY: there is room for one more element, so shifting
+        // `index..length` up by one stays in bounds.
+        unsafe {
+            let slot: *mut T = self.pointer().add(index);
+            ptr::copy(slot, slot.add(1), self.length - index);
+            slot.write(data);
+        }
+        self.length += 1;
+        return Ok(());
+    }
+
+    pub fn remove(&mut self, index: usize) -> Result<T, Error> {
+        if index >= self.length {
+            return Err(Error);
+        }
+
+        // SAFETY: `index` is initialised; the elements after it are shifted
+        // down over it once it has been read out.
+        let data: T = unsafe {
+            let slot: *mut T = self.pointer().add(index);
+            let data: T = slot.read();
+            ptr::copy(slot.add(1), slot, self.length - index - 1);
+            data
+        };
+        self.length -= 1;
+        self.shrink_if_sparse();
+        return Ok(data);
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.as_slice().get(index)
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.as_mut_slice().get_mut(index)
+    }
+
+    /// Replaces the element at `index`, returning the old one.
+    pub fn set(&mut self, index: usize, data: T) -> Result<T, Error> {
+        match self.get_mut(index) {
+            Some(slot) => Ok(mem::replace(slot, data)),
+            None => Err(Error),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        while self.pop().is_some() {}
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        // SAFETY: `..length` is initialised and the pointer is non-null and
+        // aligned even when nothing has been allocated.
+        unsafe { slice::from_raw_parts(self.pointer(), self.length) }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        // SAFETY: as in `as_slice`, with `&mut self` giving unique access.
+        unsafe { slice::from_raw_parts_mut(self.pointer(), self.length) }
+    }
+
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.as_slice().iter()
+    }
+
+    pub fn iter_mut(&mut self) -> slice::IterMut<'_, T> {
+        self.as_mut_slice().iter_mut()
+    }
+}
+
+impl<T> Drop for DynamicArray<T> {
+    fn drop(&mut self) {
+        let elements: *mut [T] = self.as_mut_slice();
+        // Forget the elements first so a panicking drop can't lead to a
+        // second drop; `buffer` frees the memory either way.
+        self.length = 0;
+        // SAFETY: the elements were initialised and are now unreachable.
+        unsafe { ptr::drop_in_place(elements) };
+    }
+}
+
+impl<T: Clone> Clone for DynamicArray<T> {
+    fn clone(&self) -> Self {
+        let mut array: DynamicArray<T> = DynamicArray::with_policy(self.policy);
+        array.reserve(self.length);
+
+        for data in self.iter() {
+            array.push(data.clone());
+        }
+
+        return array;
+    }
+}
+
+impl<T: Debug> Debug for DynamicArray<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for DynamicArray<T> {
+    fn eq(&self, other: &Self) -> bool {
+        return self.as_slice() == other.as_slice();
+    }
+}
+
+impl<'a, T> IntoIterator for &'a DynamicArray<T> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> slice::Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<T> IntoIterator for DynamicArray<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        let mut array: mem::ManuallyDrop<DynamicArray<T>> = mem::ManuallyDrop::new(self);
+        let buffer: RawBuffer<T> = mem::replace(&mut array.buffer, RawBuffer::new());
+        IntoIter { buffer, start: 0, end: array.length }
+    }
+}
+
+/// Moves the elements out of a `DynamicArray`, dropping any that were not
+/// taken along with the allocation.
+pub struct IntoIter<T> {
+    buffer: RawBuffer<T>,
+    start: usize,
+    end: usize,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.start == self.end {
+            return None;
+        }
+
+        self.start += 1;
+        // SAFETY: slots `start..end` are initialised and each is read once.
+        return Some(unsafe { self.buffer.pointer.as_ptr().add(self.start - 1).read() });
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.end - self.start, Some(self.end - self.start))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<T> {
+        if self.start == self.end {
+            return None;
+        }
+
+        self.end -= 1;
+        // SAFETY: as in `next`, from the other end.
+        return Some(unsafe { self.buffer.pointer.as_ptr().add(self.end).read() });
+    }
+}
+
+impl<T> Drop for IntoIter<T> {
+    fn drop(&mut self) {
+        let remaining: *mut [T] = ptr::slice_from_raw_parts_mut(
+            // SAFETY: `start <= end <= capacity`.
+            unsafe { self.buffer.pointer.as_ptr().add(self.start) },
+            self.end - self.start,
+        );
+        self.start = self.end;
+        // SAFETY: the remaining elements are initialised and never read again.
+        unsafe { ptr::drop_in_place(remaining) };
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::panic::{self, AssertUnwindSafe};
+    use std::rc::Rc;
+
+    /// Counts how many times values sharing the same counter are dropped,
+    /// and can be told to panic when it is cloned or dropped.
+    #[derive(Debug)]
+    struct DropCounter {
+        drops: Rc<Cell<usize>>,
+        panic_on_clone: bool,
+        panic_on_drop: bool,
+    }
+
+    impl DropCounter {
+        fn new(drops: &Rc<Cell<usize>>) -> DropCounter {
+            DropCounter { drops: drops.clone(), panic_on_clone: false, panic_on_drop: false }
+        }
+
+        fn many(count: usize, drops: &Rc<Cell<usize>>) -> Vec<DropCounter> {
+            (0..count).map(|_| DropCounter::new(drops)).collect()
+        }
+    }
+
+    impl Clone for DropCounter {
+        fn clone(&self) -> Self {
+            if self.panic_on_clone {
+                panic!("clone panicked");
+            }
+            DropCounter::new(&self.drops)
+        }
+    }
+
+    impl Drop for DropCounter {
+        fn drop(&mut self) {
+            self.drops.set(self.drops.get() + 1);
+            if self.panic_on_drop {
+                panic!("drop panicked");
+            }
+        }
+    }
+
+    #[test]
+    fn test_dynamic_array_new() {
+        let array: DynamicArray<i64> = DynamicArray::new();
+        assert!(array.is_empty());
+        assert_eq!(array.capacity(), 0);
+        assert_eq!(array.get(0), None);
+    }
+
+    #[test]
+    fn test_push_grows_by_policy() {
+        let mut array: DynamicArray<i64> = DynamicArray::new();
+        let mut capacities: Vec<usize> = vec![];
+        for value in 0..17 {
+            array.push(value);
+            if capacities.last() != Some(&array.capacity()) {
+                capacities.push(array.capacity());
+            }
+        }
+
+        assert_eq!(capacities, vec![4, 8, 16, 32]);
+        assert_eq!(array.as_slice(), (0..17).collect::<Vec<i64>>().as_slice());
+    }
+
+    #[test]
+    fn test_custom_growth_factor() {
+        let policy: GrowthPolicy = GrowthPolicy { growth_factor: 1.5, shrink_threshold: 0.0, min_capacity: 2 };
+        let mut array: DynamicArray<i64> = DynamicArray::with_policy(policy);
+        for value in 0..10 {
+            array.push(value);
+        }
+        assert_eq!(array.capacity(), 12);
+
+        while array.pop().is_some() {}
+        assert_eq!(array.capacity(), 12);
+    }
+
+    #[test]
+    fn test_set_policy_rejects_invalid() {
+        let mut array: DynamicArray<String> = DynamicArray::from((0..64).map(|i| i.to_string()).collect());
+        let invalid: GrowthPolicy = GrowthPolicy { growth_factor: 0.1, shrink_threshold: 100.0, min_capacity: 4 };
+        assert!(array.set_policy(invalid).is_err());
+        assert_eq!(array.policy(), GrowthPolicy::new());
+
+        let lazy: GrowthPolicy = GrowthPolicy { growth_factor: 1.5, shrink_threshold: 0.0, min_capacity: 2 };
+        assert!(array.set_policy(lazy).is_ok());
+        assert_eq!(array.pop(), Some("63".to_string()));
+        assert_eq!(array.capacity(), 64);
+    }
+
+    #[test]
+    fn test_shrunk_capacity_keeps_live_elements() {
+        let policy: GrowthPolicy = GrowthPolicy { growth_factor: 0.1, shrink_threshold: 100.0, min_capacity: 4 };
+        assert_eq!(policy.shrunk_capacity(63), 63);
+        assert!(!policy.is_valid());
+    }
+
+    #[test]
+    fn test_shrink_never_grows() {
+        let greedy: GrowthPolicy = GrowthPolicy { growth_factor: 4.0, shrink_threshold: 0.5, min_capacity: 4 };
+        assert!(!greedy.is_valid());
+        assert!(greedy.shrunk_capacity(31) > 64);
+
+        let mut array: DynamicArray<i64> = DynamicArray::from((0..64).collect());
+        assert!(array.set_policy(greedy).is_err());
+
+        let steep: GrowthPolicy = GrowthPolicy { growth_factor: 4.0, shrink_threshold: 0.2, min_capacity: 4 };
+        assert!(array.set_policy(steep).is_ok());
+        for _ in 0..60 {
+            let capacity: usize = array.capacity();
+            array.pop();
+            assert!(array.capacity() <= capacity);
+        }
+        assert_eq!(array.as_slice(), &[0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_pop_shrinks_below_threshold() {
+        let mut array: DynamicArray<i64> = DynamicArray::from((0..32).collect());
+        assert_eq!(array.capacity(), 32);
+
+        for _ in 0..25 {
+            array.pop();
+        }
+        assert_eq!(array.len(), 7);
+        assert_eq!(array.capacity(), 14);
+        assert_eq!(array.as_slice(), &[0, 1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn test_empty_pop() {
+        let mut array: DynamicArray<i64> = DynamicArray::new();
+        assert_eq!(array.pop(), None);
+    }
+
+    #[test]
+    fn test_insert_and_remove() {
+        let mut array: DynamicArray<i64> = DynamicArray::from(vec![1, 3]);
+        assert_eq!(array.insert(0, 0), Ok(()));
+        assert_eq!(array.insert(2, 2), Ok(()));
+        assert_eq!(array.insert(4, 4), Ok(()));
+        assert!(array.insert(6, 6).is_err());
+        assert_eq!(array.as_slice(), &[0, 1, 2, 3, 4]);
+
+        assert_eq!(array.remove(1), Ok(1));
+        assert_eq!(array.remove(3), Ok(4));
+        assert!(array.remove(3).is_err());
+        assert_eq!(array.as_slice(), &[0, 2, 3]);
+    }
+
+    #[test]
+    fn test_get_and_set() {
+        let mut array: DynamicArray<i64> = DynamicArray::from(vec![5, 6, 7]);
+        assert_eq!(array.get(1), Some(&6));
+        assert_eq!(array.set(1, 60), Ok(6));
+        assert!(array.set(3, 8).is_err());
+        *array.get_mut(2).unwrap() += 1;
+        assert_eq!(array.as_slice(), &[5, 60, 8]);
+    }
+
+    #[test]
+    fn test_reserve_and_shrink_to_fit() {
+        let mut array: DynamicArray<i64> = DynamicArray::from(vec![1, 2, 3]);
+        array.reserve(100);
+        assert!(array.capacity() >= 103);
+        array.shrink_to_fit();
+        assert_eq!(array.capacity(), 3);
+        array.clear();
+        array.shrink_to_fit();
+        assert_eq!(array.capacity(), 0);
+    }
+
+    #[test]
+    fn test_iter() {
+        let mut array: DynamicArray<i64> = DynamicArray::from(vec![1, 2, 3]);
+        for value in array.iter_mut() {
+            *value *= 10;
+        }
+        assert_eq!((&array).into_iter().sum::<i64>(), 60);
+        assert_eq!(array.into_iter().rev().collect::<Vec<i64>>(), vec![30, 20, 10]);
+    }
+
+    #[test]
+    fn test_zero_sized_elements() {
+        let mut array: DynamicArray<()> = DynamicArray::new();
+        for _ in 0..1000 {
+            array.push(());
+        }
+        assert_eq!(array.len(), 1000);
+        assert_eq!(array.remove(10), Ok(()));
+        assert_eq!(array.into_iter().count(), 999);
+    }
+
+    #[test]
+    fn test_every_element_dropped_once() {
+        let drops: Rc<Cell<usize>> = Rc::new(Cell::new(0));
+        let mut array: DynamicArray<DropCounter> = DynamicArray::from(DropCounter::many(10, &drops));
+
+        drop(array.pop());
+        drop(array.remove(0));
+        drop(array.set(0, DropCounter::new(&drops)));
+        assert_eq!(drops.get(), 3);
+
+        drop(array);
+        assert_eq!(drops.get(), 11);
+    }
+
+    #[test]
+    fn test_partly_consumed_into_iter_drops_rest() {
+        let drops: Rc<Cell<usize>> = Rc::new(Cell::new(0));
+        let array: DynamicArray<DropCounter> = DynamicArray::from(DropCounter::many(6, &drops));
+
+        let mut iter: IntoIter<DropCounter> = array.into_iter();
+        drop(iter.next());
+        drop(iter.next_back());
+        assert_eq!(drops.get(), 2);
+
+        drop(iter);
+        assert_eq!(drops.get(), 6);
+    }
+
+    #[test]
+    fn test_panicking_clone_drops_partial_copy() {
+        let drops: Rc<Cell<usize>> = Rc::new(Cell::new(0));
+        let mut array: DynamicArray<DropCounter> = DynamicArray::from(DropCounter::many(5, &drops));
+        array.get_mut(3).unwrap().panic_on_clone = true;
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| array.clone()));
+        assert!(result.is_err());
+        assert_eq!(drops.get(), 3);
+
+        drop(array);
+        assert_eq!(drops.get(), 8);
+    }
+
+    #[test]
+    fn test_panicking_drop_still_drops_the_rest() {
+        let drops: Rc<Cell<usize>> = Rc::new(Cell::new(0));
+        let mut array: DynamicArray<DropCounter> = DynamicArray::from(DropCounter::many(5, &drops));
+        array.get_mut(1).unwrap().panic_on_drop = true;
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| drop(array)));
+        assert!(result.is_err());
+        assert_eq!(drops.get(), 5);
+    }
+}
@@ -0,0 +1,361 @@
+use std::fmt::{Debug, Display, Error, Formatter};
+use std::mem::MaybeUninit;
+use std::ptr;
+
+/// An array with a movable hole (the gap) at the cursor. Inserting or
+/// deleting at the cursor only changes the gap's edges; moving the cursor
+/// copies the elements between the old and new positions across the gap.
+///
+/// `slots[..gap_start]` and `slots[gap_end..]` are initialised, the slots in
+/// between are not.
+pub struct GapBuffer<T> {
+    slots: Box<[MaybeUninit<T>]>,
+    gap_start: usize,
+    gap_end: usize,
+}
+
+impl<T> GapBuffer<T> {
+    pub fn new() -> Self {
+        GapBuffer::with_capacity(16)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        let slots: Box<[MaybeUninit<T>]> = (0..capacity).map(|_| MaybeUninit::uninit()).collect();
+        GapBuffer { slots, gap_start: 0, gap_end: capacity }
+    }
+
+    pub fn from(data_vec: Vec<T>) -> Self {
+        let mut buffer: GapBuffer<T> = GapBuffer::with_capacity(data_vec.len() + 16);
+
+        for data in data_vec {
+            buffer.insert(data);
+        }
+
+        return buffer;
+    }
+
+    pub fn len(&self) -> usize {
+        return self.slots.len() - self.gap_len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.len() == 0;
+    }
+
+    pub fn capacity(&self) -> usize {
+        return self.slots.len();
+    }
+
+    pub fn gap_len(&self) -> usize {
+        return self.gap_end - self.gap_start;
+    }
+
+    /// The cursor sits between elements; it is the number of elements
+    /// before it.
+    pub fn cursor(&self) -> usize {
+        return self.gap_start;
+    }
+
+    pub fn move_cursor_to(&mut self, position: usize) -> Result<(), Error> {
+        if position > self.len() {
+            return Err(Error);
+        }
+
+        let base: *mut MaybeUninit<T> = self.slots.as_mut_ptr();
+        if position < self.gap_start {
+            let moved: usize = self.gap_start - position;
+            // SAFETY: `position..gap_start` is initialised and moves to the
+            // top of the gap; both ranges lie inside `slots`.
+            unsafe { ptr::copy(base.add(position), base.add(self.gap_end - moved), moved) };
+            self.gap_start -= moved;
+            self.gap_end -= moved;
+        } else if position > self.gap_start {
+            let moved: usize = position - self.gap_start;
+            // SAFETY: the `moved` slots after the gap are initialised and
+            // move down to the start of the gap.
+            unsafe { ptr::copy(base.add(self.gap_end), base.add(self.gap_start), moved) };
+            self.gap_start += moved;
+            self.gap_end += moved;
+        }
+
+        return Ok(());
+    }
+
+    pub fn move_left(&mut self) -> bool {
+        return self.gap_start > 0 && self.move_cursor_to(self.gap_start - 1).is_ok();
+    }
+
+    pub fn move_right(&mut self) -> bool {
+        return self.move_cursor_to(self.gap_start + 1).is_ok();
+    }
+
+    /// Doubles the capacity (or makes room for `needed` more), keeping the
+    /// gap at the cursor.
+    fn grow(&mut self, needed: usize) {
+        let old_capacity: usize = self.slots.len();
+        let new_capacity: usize = (old_capacity * 2).max(old_capacity + needed).max(16);
+        let mut slots: Box<[MaybeUninit<T>]> = (0..new_capacity).map(|_| MaybeUninit::uninit()).collect();
+        let after_gap: usize = old_capacity - self.gap_end;
+        let new_gap_end: usize = new_capacity - after_gap;
+
+        // SAFETY: both initialised runs are copied into the fresh, distinct
+        // allocation; the old box only holds `MaybeUninit`, so dropping it
+        // does not drop the elements again.
+        unsafe {
+            ptr::copy_nonoverlapping(self.slots.as_ptr(), slots.as_mut_ptr(), self.gap_start);
+            ptr::copy_nonoverlapping(
+                self.slots.as_ptr().add(self.gap_end),
+                slots.as_mut_ptr().add(new_gap_end),
+                after_gap,
+            );
+        }
+
+        self.slots = slots;
+        self.gap_end = new_gap_end;
+    }
+
+    /// Inserts `data` at the cursor and moves the cursor past it.
+    pub fn insert(&mut self, data: T) {
+        if self.gap_len() == 0 {
+            self.grow(1);
+        }
+
+        self.slots[self.gap_start].write(data);
+        self.gap_start += 1;
+    }
+
+    pub fn insert_all<I: IntoIterator<Item = T>>(&mut self, data: I) {
+        for item in data {
+            self.insert(item);
+        }
+    }
+
+    /// Removes the element before the cursor, like backspace.
+    pub fn delete_backward(&mut self) -> Option<T> {
+        if self.gap_start == 0 {
+            return None;
+        }
+
+        self.gap_start -= 1;
+        // SAFETY: the slot was initialised and is now part of the gap.
+        return Some(unsafe { self.slots[self.gap_start].assume_init_read() });
+    }
+
+    /// Removes the element after the cursor, like the delete key.
+    pub fn delete_forward(&mut self) -> Option<T> {
+        if self.gap_end == self.slots.len() {
+            return None;
+        }
+
+        self.gap_end += 1;
+        // SAFETY: as in `delete_backward`, on the other side of the gap.
+        return Some(unsafe { self.slots[self.gap_end - 1].assume_init_read() });
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len() {
+            return None;
+        }
+
+        let slot: usize = if index < self.gap_start { index } else { index + self.gap_len() };
+        // SAFETY: `slot` is outside the gap, so it is initialised.
+        return Some(unsafe { self.slots[slot].assume_init_ref() });
+    }
+
+    /// The elements before and after the cursor.
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        // SAFETY: both ranges are initialised and `MaybeUninit<T>` has the
+        // layout of `T`.
+        unsafe {
+            let base: *const T = self.slots.as_ptr() as *const T;
+            let before: &[T] = std::slice::from_raw_parts(base, self.gap_start);
+            let after: &[T] = std::slice::from_raw_parts(base.add(self.gap_end), self.slots.len() - self.gap_end);
+            return (before, after);
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        let (before, after): (&[T], &[T]) = self.as_slices();
+        before.iter().chain(after.iter())
+    }
+
+    pub fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.iter().cloned().collect()
+    }
+}
+
+impl GapBuffer<char> {
+    pub fn from_text(text: &str) -> Self {
+        GapBuffer::from(text.chars().collect())
+    }
+
+    pub fn insert_str(&mut self, text: &str) {
+        self.insert_all(text.chars());
+    }
+
+    pub fn line_count(&self) -> usize {
+        return self.iter().filter(|c| **c == '\n').count() + 1;
+    }
+
+    /// The char index where line `line` (counting from zero) starts.
+    pub fn line_start(&self, line: usize) -> Option<usize> {
+        if line == 0 {
+            return Some(0);
+        }
+
+        let mut seen: usize = 0;
+        for (index, c) in self.iter().enumerate() {
+            if *c == '\n' {
+                seen += 1;
+                if seen == line {
+                    return Some(index + 1);
+                }
+            }
+        }
+
+        return None;
+    }
+
+    /// The text of line `line`, without its newline.
+    pub fn line(&self, line: usize) -> Option<String> {
+        let start: usize = self.line_start(line)?;
+        Some(self.iter().skip(start).take_while(|c| **c != '\n').collect())
+    }
+
+    /// The zero-based line and column of the cursor.
+    pub fn cursor_position(&self) -> (usize, usize) {
+        let (before, _): (&[char], &[char]) = self.as_slices();
+        let line: usize = before.iter().filter(|c| **c == '\n').count();
+        let column: usize = before.iter().rev().take_while(|c| **c != '\n').count();
+        return (line, column);
+    }
+}
+
+impl<T> Drop for GapBuffer<T> {
+    fn drop(&mut self) {
+        let (before, after): (&[T], &[T]) = self.as_slices();
+        let before: *mut [T] = before as *const [T] as *mut [T];
+        let after: *mut [T] = after as *const [T] as *mut [T];
+        self.gap_start = 0;
+        self.gap_end = self.slots.len();
+        // SAFETY: both runs are initialised and never touched again.
+        unsafe {
+            ptr::drop_in_place(before);
+            ptr::drop_in_place(after);
+        }
+    }
+}
+
+impl Display for GapBuffer<char> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        for c in self.iter() {
+            write!(f, "{}", c)?;
+        }
+        return Ok(());
+    }
+}
+
+impl<T: Debug> Debug for GapBuffer<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_gap_buffer_new() {
+        let buffer: GapBuffer<char> = GapBuffer::new();
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.cursor(), 0);
+        assert_eq!(buffer.to_string(), "");
+    }
+
+    #[test]
+    fn test_insert_at_cursor() {
+        let mut buffer: GapBuffer<char> = GapBuffer::from_text("held");
+        assert_eq!(buffer.cursor(), 4);
+        assert_eq!(buffer.move_cursor_to(3), Ok(()));
+        buffer.insert_str("lo wor");
+        buffer.insert('l');
+        assert_eq!(buffer.to_string(), "hello world");
+        assert_eq!(buffer.cursor(), 10);
+    }
+
+    #[test]
+    fn test_move_cursor() {
+        let mut buffer: GapBuffer<char> = GapBuffer::from_text("abc");
+        assert!(buffer.move_cursor_to(4).is_err());
+        assert_eq!(buffer.move_cursor_to(0), Ok(()));
+        assert!(!buffer.move_left());
+        assert!(buffer.move_right());
+        buffer.insert('x');
+        assert!(buffer.move_right());
+        assert!(buffer.move_right());
+        assert!(!buffer.move_right());
+        assert_eq!(buffer.to_string(), "axbc");
+    }
+
+    #[test]
+    fn test_delete_at_cursor() {
+        let mut buffer: GapBuffer<char> = GapBuffer::from_text("abcd");
+        let _ = buffer.move_cursor_to(2);
+        assert_eq!(buffer.delete_backward(), Some('b'));
+        assert_eq!(buffer.delete_forward(), Some('c'));
+        assert_eq!(buffer.to_string(), "ad");
+        let _ = buffer.move_cursor_to(2);
+        assert_eq!(buffer.delete_forward(), None);
+        let _ = buffer.move_cursor_to(0);
+        assert_eq!(buffer.delete_backward(), None);
+    }
+
+    #[test]
+    fn test_gap_grows() {
+        let mut buffer: GapBuffer<i64> = GapBuffer::with_capacity(2);
+        buffer.insert(1);
+        buffer.insert(4);
+        let _ = buffer.move_cursor_to(1);
+        buffer.insert_all(vec![2, 3]);
+        assert!(buffer.capacity() >= 4);
+        assert_eq!(buffer.to_vec(), vec![1, 2, 3, 4]);
+        assert_eq!(buffer.get(3), Some(&4));
+        assert_eq!(buffer.get(4), None);
+    }
+
+    #[test]
+    fn test_lines() {
+        let mut buffer: GapBuffer<char> = GapBuffer::from_text("one\ntwo\n\nfour");
+        assert_eq!(buffer.line_count(), 4);
+        assert_eq!(buffer.line_start(1), Some(4));
+        assert_eq!(buffer.line(1), Some("two".to_string()));
+        assert_eq!(buffer.line(2), Some("".to_string()));
+        assert_eq!(buffer.line(3), Some("four".to_string()));
+        assert_eq!(buffer.line(4), None);
+
+        let _ = buffer.move_cursor_to(6);
+        assert_eq!(buffer.cursor_position(), (1, 2));
+    }
+
+    #[test]
+    fn test_drop_releases_elements() {
+        let counter: Rc<()> = Rc::new(());
+        let mut buffer: GapBuffer<Rc<()>> = GapBuffer::with_capacity(2);
+        for _ in 0..5 {
+            buffer.insert(counter.clone());
+        }
+        let _ = buffer.move_cursor_to(2);
+        drop(buffer.delete_forward());
+        assert_eq!(Rc::strong_count(&counter), 5);
+
+        drop(buffer);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+}
@@ -0,0 +1,10 @@
+#![allow(clippy::needless_return, clippy::new_without_default)]
+
+pub mod dynamic_array;
+pub mod nd_array;
+pub mod array_list;
+pub mod ring_buffer;
+pub mod gap_buffer;
+pub mod piece_table;
+pub mod bit_array;
+pub mod sparse_matrix;
@@ -0,0 +1,464 @@
+use std::fmt::Error;
+use std::mem::size_of;
+use std::ops::Range;
+
+/// The order elements are laid out in memory. Row-major stores the last
+/// index contiguously (rows one after another); column-major stores the first
+/// index contiguously (columns one after another).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Layout {
+    RowMajor,
+    ColumnMajor,
+}
+
+/// The strides of a contiguous array of `shape` in the given layout.
+pub fn strides_for(shape: &[usize], layout: Layout) -> Vec<usize> {
+    let mut strides: Vec<usize> = vec![0; shape.len()];
+    let mut step: usize = 1;
+
+    match layout {
+        Layout::RowMajor => {
+            for axis in (0..shape.len()).rev() {
+                strides[axis] = step;
+                step *= shape[axis];
+            }
+        }
+        Layout::ColumnMajor => {
+            for axis in 0..shape.len() {
+                strides[axis] = step;
+                step *= shape[axis];
+            }
+        }
+    }
+
+    return strides;
+}
+
+/// How a flat buffer is read as an n-dimensional array: the length of each
+/// axis, how many elements to step over to move one along each axis, where
+/// the first element is and the number the indices start from.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Strided {
+    pub shape: Vec<usize>,
+    pub strides: Vec<usize>,
+    pub offset: usize,
+    pub base: usize,
+}
+
+impl Strided {
+    pub fn new(shape: Vec<usize>, layout: Layout, base: usize) -> Self {
+        let strides: Vec<usize> = strides_for(&shape, layout);
+        Strided { shape, strides, offset: 0, base }
+    }
+
+    pub fn len(&self) -> usize {
+        return self.shape.iter().product();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.len() == 0;
+    }
+
+    /// The position in the flat buffer of `index`:
+    /// `offset + sum((index[axis] - base) * strides[axis])`.
+    pub fn offset_of(&self, index: &[usize]) -> Result<usize, Error> {
+        if index.len() != self.shape.len() {
+            return Err(Error);
+        }
+
+        let mut offset: usize = self.offset;
+        for (axis, position) in index.iter().enumerate() {
+            if *position < self.base || position - self.base >= self.shape[axis] {
+                return Err(Error);
+            }
+            offset += (position - self.base) * self.strides[axis];
+        }
+
+        return Ok(offset);
+    }
+
+    /// Which of the two contiguous layouts the strides describe, if either.
+    pub fn layout(&self) -> Option<Layout> {
+        if self.strides == strides_for(&self.shape, Layout::RowMajor) {
+            return Some(Layout::RowMajor);
+        }
+        if self.strides == strides_for(&self.shape, Layout::ColumnMajor) {
+            return Some(Layout::ColumnMajor);
+        }
+
+        return None;
+    }
+
+    pub fn swap_axes(&mut self, first: usize, second: usize) {
+        self.shape.swap(first, second);
+        self.strides.swap(first, second);
+    }
+
+    pub fn transpose(&mut self) {
+        self.shape.reverse();
+        self.strides.reverse();
+    }
+
+    /// Narrows each axis to `ranges[axis]`, given in base-numbered indices.
+    pub fn slice(&self, ranges: &[Range<usize>]) -> Result<Strided, Error> {
+        if ranges.len() != self.shape.len() {
+            return Err(Error);
+        }
+
+        let mut sliced: Strided = self.clone();
+        for (axis, range) in ranges.iter().enumerate() {
+            if range.start < self.base || range.start > range.end || range.end - self.base > self.shape[axis] {
+                return Err(Error);
+            }
+            sliced.offset += (range.start - self.base) * self.strides[axis];
+            sliced.shape[axis] = range.end - range.start;
+        }
+
+        return Ok(sliced);
+    }
+
+    /// Flat offsets of every element, visiting the last axis fastest.
+    pub fn offsets(&self) -> Vec<usize> {
+        let mut offsets: Vec<usize> = Vec::with_capacity(self.len());
+        if self.is_empty() {
+            return offsets;
+        }
+
+        let mut counter: Vec<usize> = vec![0; self.shape.len()];
+        loop {
+            let offset: usize = counter.iter().zip(&self.strides).map(|(i, stride)| i * stride).sum();
+            offsets.push(self.offset + offset);
+
+            let mut axis: usize = self.shape.len();
+            loop {
+                if axis == 0 {
+                    return offsets;
+                }
+                axis -= 1;
+                counter[axis] += 1;
+                if counter[axis] < self.shape[axis] {
+                    break;
+                }
+                counter[axis] = 0;
+            }
+        }
+    }
+}
+
+/// A dense n-dimensional array over a single `Vec`, with its shape and
+/// strides chosen at runtime.
+#[derive(Clone, PartialEq, Debug)]
+pub struct NdArray<T> {
+    pub data: Vec<T>,
+    pub strided: Strided,
+}
+
+impl<T> NdArray<T> {
+    pub fn new(shape: Vec<usize>, layout: Layout, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        let strided: Strided = Strided::new(shape, layout, 0);
+        NdArray { data: vec![fill; strided.len()], strided }
+    }
+
+    pub fn from(data: Vec<T>, shape: Vec<usize>, layout: Layout) -> Result<Self, Error> {
+        let strided: Strided = Strided::new(shape, layout, 0);
+        if strided.len() != data.len() {
+            return Err(Error);
+        }
+
+        return Ok(NdArray { data, strided });
+    }
+
+    /// Numbers indices from `base` instead of zero, e.g. one for the
+    /// 1-based arithmetic in `Arrays.md`.
+    pub fn with_base(mut self, base: usize) -> Self {
+        self.strided.base = base;
+        return self;
+    }
+
+    pub fn shape(&self) -> &[usize] {
+        &self.strided.shape
+    }
+
+    pub fn strides(&self) -> &[usize] {
+        &self.strided.strides
+    }
+
+    pub fn layout(&self) -> Option<Layout> {
+        self.strided.layout()
+    }
+
+    pub fn len(&self) -> usize {
+        return self.data.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.data.is_empty();
+    }
+
+    pub fn offset_of(&self, index: &[usize]) -> Result<usize, Error> {
+        self.strided.offset_of(index)
+    }
+
+    /// The memory address of `index` if the array started at `array_address`:
+    /// `array_address + element_size * offset`.
+    pub fn address_of(&self, array_address: usize, index: &[usize]) -> Result<usize, Error> {
+        let offset: usize = self.offset_of(index)?;
+        return Ok(array_address + size_of::<T>() * offset);
+    }
+
+    pub fn get(&self, index: &[usize]) -> Option<&T> {
+        let offset: usize = self.offset_of(index).ok()?;
+        self.data.get(offset)
+    }
+
+    pub fn get_mut(&mut self, index: &[usize]) -> Option<&mut T> {
+        let offset: usize = self.offset_of(index).ok()?;
+        self.data.get_mut(offset)
+    }
+
+    pub fn set(&mut self, index: &[usize], data: T) -> Result<T, Error> {
+        match self.get_mut(index) {
+            Some(slot) => Ok(std::mem::replace(slot, data)),
+            None => Err(Error),
+        }
+    }
+
+    /// Reverses the axes by reversing the strides; the data isn't moved, so
+    /// a row-major array becomes column-major and the other way round.
+    pub fn transpose(&mut self) {
+        self.strided.transpose();
+    }
+
+    pub fn swap_axes(&mut self, first: usize, second: usize) {
+        self.strided.swap_axes(first, second);
+    }
+
+    pub fn view(&self) -> NdView<'_, T> {
+        NdView { data: &self.data, strided: self.strided.clone() }
+    }
+
+    pub fn slice(&self, ranges: &[Range<usize>]) -> Result<NdView<'_, T>, Error> {
+        self.view().slice(ranges)
+    }
+
+    /// Gives the array a new shape with the same number of elements, keeping
+    /// them in row-major order. A contiguous row-major array only has its
+    /// strides recomputed; any other array is copied out first.
+    pub fn reshape(self, shape: Vec<usize>) -> Result<Self, Error>
+    where
+        T: Clone,
+    {
+        let reshaped_length: usize = shape.iter().product();
+        if reshaped_length != self.len() {
+            return Err(Error);
+        }
+
+        let base: usize = self.strided.base;
+        let data: Vec<T> = match self.layout() {
+            Some(Layout::RowMajor) => self.data,
+            _ => self.view().to_vec(),
+        };
+
+        return Ok(NdArray { data, strided: Strided::new(shape, Layout::RowMajor, base) });
+    }
+
+    /// Copies the elements into a new array stored in `layout`.
+    pub fn to_layout(&self, layout: Layout) -> Self
+    where
+        T: Clone,
+    {
+        let strided: Strided = Strided::new(self.shape().to_vec(), layout, self.strided.base);
+        let mut data: Vec<T> = Vec::with_capacity(self.len());
+
+        match layout {
+            Layout::RowMajor => data.extend(self.view().iter().cloned()),
+            Layout::ColumnMajor => {
+                let mut transposed: NdView<'_, T> = self.view();
+                transposed.transpose();
+                data.extend(transposed.iter().cloned());
+            }
+        }
+
+        return NdArray { data, strided };
+    }
+
+    pub fn iter(&self) -> std::vec::IntoIter<&T> {
+        self.view().iter()
+    }
+}
+
+/// A borrowed, possibly sliced or transposed, look at an `NdArray`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct NdView<'a, T> {
+    pub data: &'a [T],
+    pub strided: Strided,
+}
+
+impl<'a, T> NdView<'a, T> {
+    pub fn shape(&self) -> &[usize] {
+        &self.strided.shape
+    }
+
+    pub fn get(&self, index: &[usize]) -> Option<&'a T> {
+        let offset: usize = self.strided.offset_of(index).ok()?;
+        self.data.get(offset)
+    }
+
+    pub fn slice(&self, ranges: &[Range<usize>]) -> Result<NdView<'a, T>, Error> {
+        Ok(NdView { data: self.data, strided: self.strided.slice(ranges)? })
+    }
+
+    pub fn transpose(&mut self) {
+        self.strided.transpose();
+    }
+
+    /// Elements in logical row-major order, whatever the memory layout.
+    pub fn iter(&self) -> std::vec::IntoIter<&'a T> {
+        let data: &'a [T] = self.data;
+        let elements: Vec<&'a T> = self.strided.offsets().into_iter().map(|offset| &data[offset]).collect();
+        return elements.into_iter();
+    }
+
+    pub fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.iter().cloned().collect()
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(layout: Layout) -> NdArray<i64> {
+        // 2 rows by 3 columns holding 10 * row + column.
+        let data: Vec<i64> = match layout {
+            Layout::RowMajor => vec![0, 1, 2, 10, 11, 12],
+            Layout::ColumnMajor => vec![0, 10, 1, 11, 2, 12],
+        };
+        return NdArray::from(data, vec![2, 3], layout).unwrap();
+    }
+
+    #[test]
+    fn test_strides_for() {
+        assert_eq!(strides_for(&[2, 3, 4], Layout::RowMajor), vec![12, 4, 1]);
+        assert_eq!(strides_for(&[2, 3, 4], Layout::ColumnMajor), vec![1, 2, 6]);
+    }
+
+    #[test]
+    fn test_from_wrong_length() {
+        assert!(NdArray::from(vec![1, 2, 3], vec![2, 2], Layout::RowMajor).is_err());
+    }
+
+    #[test]
+    fn test_get_in_both_layouts() {
+        for layout in [Layout::RowMajor, Layout::ColumnMajor] {
+            let array: NdArray<i64> = grid(layout);
+            assert_eq!(array.get(&[1, 2]), Some(&12));
+            assert_eq!(array.get(&[0, 1]), Some(&1));
+            assert_eq!(array.get(&[2, 0]), None);
+            assert_eq!(array.get(&[0]), None);
+            assert_eq!(array.iter().cloned().collect::<Vec<i64>>(), vec![0, 1, 2, 10, 11, 12]);
+        }
+    }
+
+    #[test]
+    fn test_address_of_one_dimensional() {
+        // An array at address 1000 with 8 byte elements puts index 6 at 1048.
+        let array: NdArray<u64> = NdArray::new(vec![10], Layout::RowMajor, 0);
+        assert_eq!(array.address_of(1000, &[6]), Ok(1048));
+
+        let one_based: NdArray<u64> = array.with_base(1);
+        assert_eq!(one_based.address_of(1000, &[6]), Ok(1040));
+        assert!(one_based.address_of(1000, &[0]).is_err());
+    }
+
+    #[test]
+    fn test_address_of_one_based_grid() {
+        // Three rows of six, 1-based: (3, 4) is 1000 + 8 * ((3 - 1) * 6 + (4 - 1)).
+        let row_major: NdArray<u64> = NdArray::new(vec![3, 6], Layout::RowMajor, 0).with_base(1);
+        assert_eq!(row_major.address_of(1000, &[3, 4]), Ok(1000 + 8 * 15));
+
+        let column_major: NdArray<u64> = NdArray::new(vec![3, 6], Layout::ColumnMajor, 0).with_base(1);
+        assert_eq!(column_major.address_of(1000, &[3, 4]), Ok(1000 + 8 * ((4 - 1) * 3 + (3 - 1))));
+    }
+
+    #[test]
+    fn test_set() {
+        let mut array: NdArray<i64> = grid(Layout::RowMajor).with_base(1);
+        assert_eq!(array.set(&[2, 3], 99), Ok(12));
+        assert_eq!(array.data[5], 99);
+        assert!(array.set(&[0, 0], 1).is_err());
+    }
+
+    #[test]
+    fn test_transpose_swaps_layout_without_copying() {
+        let mut array: NdArray<i64> = grid(Layout::RowMajor);
+        let data_pointer: *const i64 = array.data.as_ptr();
+        array.transpose();
+
+        assert_eq!(array.shape(), &[3, 2]);
+        assert_eq!(array.layout(), Some(Layout::ColumnMajor));
+        assert_eq!(array.get(&[2, 1]), Some(&12));
+        assert_eq!(array.iter().cloned().collect::<Vec<i64>>(), vec![0, 10, 1, 11, 2, 12]);
+        assert_eq!(array.data.as_ptr(), data_pointer);
+    }
+
+    #[test]
+    fn test_swap_axes() {
+        let mut array: NdArray<i64> = NdArray::from((0..24).collect(), vec![2, 3, 4], Layout::RowMajor).unwrap();
+        array.swap_axes(0, 1);
+        assert_eq!(array.shape(), &[3, 2, 4]);
+        assert_eq!(array.get(&[1, 1, 3]), Some(&(12 + 4 + 3)));
+        assert_eq!(array.layout(), None);
+    }
+
+    #[test]
+    fn test_slice() {
+        let array: NdArray<i64> = NdArray::from((0..16).collect(), vec![4, 4], Layout::RowMajor).unwrap();
+        let view: NdView<'_, i64> = array.slice(&[1..3, 2..4]).unwrap();
+
+        assert_eq!(view.shape(), &[2, 2]);
+        assert_eq!(view.to_vec(), vec![6, 7, 10, 11]);
+        assert_eq!(view.get(&[1, 0]), Some(&10));
+        assert_eq!(view.slice(&[1..2, 0..2]).unwrap().to_vec(), vec![10, 11]);
+        assert!(array.slice(&[0..5, 0..1]).is_err());
+        assert!(array.slice(&[Range { start: 0, end: 1 }]).is_err());
+    }
+
+    #[test]
+    fn test_one_based_slice() {
+        let array: NdArray<i64> = grid(Layout::ColumnMajor).with_base(1);
+        let view: NdView<'_, i64> = array.slice(&[2..3, 1..3]).unwrap();
+        assert_eq!(view.to_vec(), vec![10, 11]);
+        assert_eq!(view.get(&[1, 2]), Some(&11));
+    }
+
+    #[test]
+    fn test_reshape() {
+        let array: NdArray<i64> = NdArray::from((0..12).collect(), vec![3, 4], Layout::RowMajor).unwrap();
+        let reshaped: NdArray<i64> = array.reshape(vec![2, 6]).unwrap();
+        assert_eq!(reshaped.get(&[1, 0]), Some(&6));
+        assert!(reshaped.clone().reshape(vec![5, 2]).is_err());
+
+        let mut transposed: NdArray<i64> = reshaped;
+        transposed.swap_axes(0, 1);
+        let flattened: NdArray<i64> = transposed.reshape(vec![12]).unwrap();
+        assert_eq!(flattened.get(&[1]), Some(&6));
+    }
+
+    #[test]
+    fn test_to_layout() {
+        let row_major: NdArray<i64> = grid(Layout::RowMajor);
+        let column_major: NdArray<i64> = row_major.to_layout(Layout::ColumnMajor);
+
+        assert_eq!(column_major, grid(Layout::ColumnMajor));
+        assert_eq!(column_major.to_layout(Layout::RowMajor), row_major);
+    }
+}
@@ -0,0 +1,434 @@
+use std::fmt::{Display, Error, Formatter};
+use std::ops::Range;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Source {
+    Original,
+    Add,
+}
+
+/// A run of `length` bytes from one of the two buffers. `newlines` caches
+/// how many line breaks the run holds so line lookups can skip whole pieces.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct Piece {
+    source: Source,
+    start: usize,
+    length: usize,
+    newlines: usize,
+}
+
+struct PieceNode {
+    piece: Piece,
+    next: Option<Box<PieceNode>>,
+}
+
+/// One edit, stored as the pieces it took out and how many it put in at
+/// `index`. Undoing it is the same splice the other way round.
+struct Change {
+    index: usize,
+    removed: Vec<Piece>,
+    inserted: usize,
+}
+
+/// A text buffer that never rewrites what it stores. The document is a list
+/// of pieces, each pointing into either the original text or the append-only
+/// add buffer, and every edit just splices that list.
+///
+/// Positions are byte offsets and must fall on char boundaries.
+pub struct PieceTable {
+    original: String,
+    add: String,
+    head: Option<Box<PieceNode>>,
+    pieces: usize,
+    length: usize,
+    undo_stack: Vec<Change>,
+    redo_stack: Vec<Change>,
+}
+
+impl PieceTable {
+    pub fn new() -> Self {
+        PieceTable::from("")
+    }
+
+    pub fn from(text: &str) -> Self {
+        let mut table: PieceTable = PieceTable {
+            original: text.to_string(),
+            add: String::new(),
+            head: None,
+            pieces: 0,
+            length: 0,
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+        };
+
+        if !text.is_empty() {
+            let piece: Piece = table.piece(Source::Original, 0, text.len());
+            table.splice(0, 0, vec![piece]);
+        }
+
+        return table;
+    }
+
+    pub fn len(&self) -> usize {
+        return self.length;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.length == 0;
+    }
+
+    pub fn piece_count(&self) -> usize {
+        return self.pieces;
+    }
+
+    fn buffer(&self, source: Source) -> &str {
+        match source {
+            Source::Original => &self.original,
+            Source::Add => &self.add,
+        }
+    }
+
+    fn text(&self, piece: &Piece) -> &str {
+        &self.buffer(piece.source)[piece.start..piece.start + piece.length]
+    }
+
+    fn piece(&self, source: Source, start: usize, length: usize) -> Piece {
+        let newlines: usize = self.buffer(source)[start..start + length].matches('\n').count();
+        Piece { source, start, length, newlines }
+    }
+
+    fn iter(&self) -> PieceIter<'_> {
+        PieceIter { next: self.head.as_deref() }
+    }
+
+    /// The piece holding byte `position` and the offset inside it. The end
+    /// of the document maps to one past the last piece.
+    fn locate(&self, position: usize) -> (usize, usize) {
+        let mut before: usize = 0;
+
+        for (index, piece) in self.iter().enumerate() {
+            if position < before + piece.length {
+                return (index, position - before);
+            }
+            before += piece.length;
+        }
+
+        return (self.pieces, 0);
+    }
+
+    fn is_char_boundary(&self, position: usize) -> bool {
+        let (index, offset): (usize, usize) = self.locate(position);
+        match self.iter().nth(index) {
+            Some(piece) => self.buffer(piece.source).is_char_boundary(piece.start + offset),
+            None => position == self.length,
+        }
+    }
+
+    /// Replaces `remove` pieces at `index` with `insert` and returns the
+    /// pieces taken out.
+    fn splice(&mut self, index: usize, remove: usize, insert: Vec<Piece>) -> Vec<Piece> {
+        let mut cursor: &mut Option<Box<PieceNode>> = &mut self.head;
+        for _ in 0..index {
+            cursor = &mut cursor.as_mut().expect("piece index in range").next;
+        }
+
+        let mut removed: Vec<Piece> = Vec::with_capacity(remove);
+        for _ in 0..remove {
+            let node: Box<PieceNode> = cursor.take().expect("piece index in range");
+            *cursor = node.next;
+            removed.push(node.piece);
+        }
+
+        self.pieces = self.pieces + insert.len() - removed.len();
+        self.length += insert.iter().map(|piece| piece.length).sum::<usize>();
+        self.length -= removed.iter().map(|piece| piece.length).sum::<usize>();
+
+        for piece in insert.into_iter().rev() {
+            let next: Option<Box<PieceNode>> = cursor.take();
+            *cursor = Some(Box::new(PieceNode { piece, next }));
+        }
+
+        return removed;
+    }
+
+    fn record(&mut self, index: usize, remove: usize, insert: Vec<Piece>) {
+        let inserted: usize = insert.len();
+        let removed: Vec<Piece> = self.splice(index, remove, insert);
+        self.undo_stack.push(Change { index, removed, inserted });
+        self.redo_stack.clear();
+    }
+
+    pub fn insert(&mut self, position: usize, text: &str) -> Result<(), Error> {
+        if position > self.length || !self.is_char_boundary(position) {
+            return Err(Error);
+        }
+        if text.is_empty() {
+            return Ok(());
+        }
+
+        let start: usize = self.add.len();
+        self.add.push_str(text);
+        let added: Piece = self.piece(Source::Add, start, text.len());
+
+        let (index, offset): (usize, usize) = self.locate(position);
+        if offset == 0 {
+            self.record(index, 0, vec![added]);
+        } else {
+            let split: Piece = *self.iter().nth(index).expect("located piece");
+            let left: Piece = self.piece(split.source, split.start, offset);
+            let right: Piece = self.piece(split.source, split.start + offset, split.length - offset);
+            self.record(index, 1, vec![left, added, right]);
+        }
+
+        return Ok(());
+    }
+
+    /// Whether `range` is in order, inside the text and on char boundaries.
+    fn is_valid_range(&self, range: &Range<usize>) -> bool {
+        return range.start <= range.end
+            && range.end <= self.length
+            && self.is_char_boundary(range.start)
+            && self.is_char_boundary(range.end);
+    }
+
+    pub fn delete(&mut self, range: Range<usize>) -> Result<(), Error> {
+        if !self.is_valid_range(&range) {
+            return Err(Error);
+        }
+        if range.is_empty() {
+            return Ok(());
+        }
+
+        let (first, start_offset): (usize, usize) = self.locate(range.start);
+        let (last, end_offset): (usize, usize) = self.locate(range.end);
+        let mut keep: Vec<Piece> = Vec::new();
+
+        if start_offset > 0 {
+            let piece: Piece = *self.iter().nth(first).expect("located piece");
+            keep.push(self.piece(piece.source, piece.start, start_offset));
+        }
+        if end_offset > 0 {
+            let piece: Piece = *self.iter().nth(last).expect("located piece");
+            keep.push(self.piece(piece.source, piece.start + end_offset, piece.length - end_offset));
+        }
+
+        let remove: usize = last - first + if end_offset > 0 { 1 } else { 0 };
+        self.record(first, remove, keep);
+        return Ok(());
+    }
+
+    /// Reverts the latest edit. Returns `false` when there is nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        let change: Change = match self.undo_stack.pop() {
+            Some(change) => change,
+            None => return false,
+        };
+
+        let inserted: usize = change.removed.len();
+        let removed: Vec<Piece> = self.splice(change.index, change.inserted, change.removed);
+        self.redo_stack.push(Change { index: change.index, removed, inserted });
+        return true;
+    }
+
+    /// Reapplies the latest undone edit. Any new edit clears the redo history.
+    pub fn redo(&mut self) -> bool {
+        let change: Change = match self.redo_stack.pop() {
+            Some(change) => change,
+            None => return false,
+        };
+
+        let inserted: usize = change.removed.len();
+        let removed: Vec<Piece> = self.splice(change.index, change.inserted, change.removed);
+        self.undo_stack.push(Change { index: change.index, removed, inserted });
+        return true;
+    }
+
+    pub fn line_count(&self) -> usize {
+        return self.iter().map(|piece| piece.newlines).sum::<usize>() + 1;
+    }
+
+    /// The byte offset where line `line` (counting from zero) starts.
+    pub fn line_start(&self, line: usize) -> Option<usize> {
+        if line == 0 {
+            return Some(0);
+        }
+
+        let mut before: usize = 0;
+        let mut seen: usize = 0;
+        for piece in self.iter() {
+            if seen + piece.newlines >= line {
+                let (offset, _): (usize, &str) = self
+                    .text(piece)
+                    .match_indices('\n')
+                    .nth(line - seen - 1)
+                    .expect("piece holds the newline");
+                return Some(before + offset + 1);
+            }
+            seen += piece.newlines;
+            before += piece.length;
+        }
+
+        return None;
+    }
+
+    /// The text of line `line`, without its newline.
+    pub fn line(&self, line: usize) -> Option<String> {
+        let start: usize = self.line_start(line)?;
+        let end: usize = match self.line_start(line + 1) {
+            Some(next) => next - 1,
+            None => self.length,
+        };
+        self.slice(start..end).ok()
+    }
+
+    /// Copies the bytes in `range` out of the pieces.
+    pub fn slice(&self, range: Range<usize>) -> Result<String, Error> {
+        if !self.is_valid_range(&range) {
+            return Err(Error);
+        }
+
+        let mut text: String = String::with_capacity(range.len());
+        let mut before: usize = 0;
+
+        for piece in self.iter() {
+            let start: usize = range.start.max(before);
+            let end: usize = range.end.min(before + piece.length);
+            if start < end {
+                text.push_str(&self.text(piece)[start - before..end - before]);
+            }
+            before += piece.length;
+        }
+
+        return Ok(text);
+    }
+}
+
+impl Drop for PieceTable {
+    fn drop(&mut self) {
+        let mut current: Option<Box<PieceNode>> = self.head.take();
+        while let Some(mut node) = current {
+            current = node.next.take();
+        }
+    }
+}
+
+impl Display for PieceTable {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        for piece in self.iter() {
+            write!(f, "{}", self.text(piece))?;
+        }
+        return Ok(());
+    }
+}
+
+struct PieceIter<'a> {
+    next: Option<&'a PieceNode>,
+}
+
+impl<'a> Iterator for PieceIter<'a> {
+    type Item = &'a Piece;
+
+    fn next(&mut self) -> Option<&'a Piece> {
+        let node: &'a PieceNode = self.next?;
+        self.next = node.next.as_deref();
+        Some(&node.piece)
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_piece_table_new() {
+        let table: PieceTable = PieceTable::new();
+        assert!(table.is_empty());
+        assert_eq!(table.piece_count(), 0);
+        assert_eq!(table.to_string(), "");
+        assert_eq!(table.line_count(), 1);
+    }
+
+    #[test]
+    fn test_insert_splits_pieces() {
+        let mut table: PieceTable = PieceTable::from("hello world");
+        assert_eq!(table.insert(5, ","), Ok(()));
+        assert_eq!(table.insert(0, ">> "), Ok(()));
+        assert_eq!(table.insert(table.len(), "!"), Ok(()));
+        assert_eq!(table.to_string(), ">> hello, world!");
+        assert_eq!(table.piece_count(), 5);
+        assert!(table.insert(100, "x").is_err());
+    }
+
+    #[test]
+    fn test_delete_across_pieces() {
+        let mut table: PieceTable = PieceTable::from("abcdef");
+        let _ = table.insert(3, "XYZ");
+        assert_eq!(table.to_string(), "abcXYZdef");
+
+        assert_eq!(table.delete(2..7), Ok(()));
+        assert_eq!(table.to_string(), "abef");
+        assert_eq!(table.len(), 4);
+        assert_eq!(table.delete(0..4), Ok(()));
+        assert!(table.is_empty());
+        assert!(table.delete(0..1).is_err());
+    }
+
+    #[test]
+    fn test_char_boundaries() {
+        let mut table: PieceTable = PieceTable::from("héllo");
+        assert!(table.insert(2, "x").is_err());
+        assert!(table.delete(0..2).is_err());
+        assert_eq!(table.insert(3, "é"), Ok(()));
+        assert_eq!(table.to_string(), "hééllo");
+    }
+
+    #[test]
+    fn test_undo_and_redo() {
+        let mut table: PieceTable = PieceTable::from("one three");
+        let _ = table.insert(4, "two ");
+        let _ = table.delete(0..4);
+        assert_eq!(table.to_string(), "two three");
+
+        assert!(table.undo());
+        assert_eq!(table.to_string(), "one two three");
+        assert!(table.undo());
+        assert_eq!(table.to_string(), "one three");
+        assert_eq!(table.piece_count(), 1);
+        assert!(!table.undo());
+
+        assert!(table.redo());
+        assert_eq!(table.to_string(), "one two three");
+        let _ = table.insert(0, "zero ");
+        assert!(!table.redo());
+        assert_eq!(table.to_string(), "zero one two three");
+    }
+
+    #[test]
+    fn test_lines() {
+        let mut table: PieceTable = PieceTable::from("one\nthree\n");
+        let _ = table.insert(4, "two\n");
+        assert_eq!(table.line_count(), 4);
+        assert_eq!(table.line_start(1), Some(4));
+        assert_eq!(table.line_start(2), Some(8));
+        assert_eq!(table.line(1), Some("two".to_string()));
+        assert_eq!(table.line(2), Some("three".to_string()));
+        assert_eq!(table.line(3), Some("".to_string()));
+        assert_eq!(table.line(4), None);
+    }
+
+    #[test]
+    fn test_slice() {
+        let mut table: PieceTable = PieceTable::from("abcdef");
+        let _ = table.insert(3, "123");
+        assert_eq!(table.slice(2..5), Ok("c12".to_string()));
+        assert_eq!(table.slice(0..table.len()), Ok(table.to_string()));
+        assert!(table.slice(0..table.len() + 1).is_err());
+        assert!(table.slice(Range { start: 3, end: 2 }).is_err());
+
+        let accented: PieceTable = PieceTable::from("héllo");
+        assert!(accented.slice(0..2).is_err());
+        assert_eq!(accented.slice(0..3), Ok("hé".to_string()));
+    }
+}
@@ -0,0 +1,387 @@
+use std::fmt::{Debug, Error, Formatter};
+use std::iter::Chain;
+use std::mem::MaybeUninit;
+use std::ops::{Index, IndexMut};
+use std::slice;
+
+use super::array_list::CapacityError;
+
+/// What a push does when the buffer is already full.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FullPolicy {
+    /// Evict the element at the opposite end and hand it back.
+    Overwrite,
+    /// Refuse the push and hand the new element back in a `CapacityError`.
+    Reject,
+}
+
+/// A bounded double-ended queue over one contiguous allocation.
+///
+/// The allocation is rounded up to a power of two so a logical index maps
+/// to a slot with `(head + index) & mask` rather than a division, but the
+/// buffer still counts as full at the capacity it was asked for. The
+/// elements are the `length` slots starting at `head`, wrapping round at the
+/// end of the allocation.
+pub struct RingBuffer<T> {
+    slots: Box<[MaybeUninit<T>]>,
+    capacity: usize,
+    head: usize,
+    length: usize,
+    pub policy: FullPolicy,
+}
+
+impl<T> RingBuffer<T> {
+    pub fn new(capacity: usize, policy: FullPolicy) -> Self {
+        let capacity: usize = capacity.max(1);
+        let slots: Box<[MaybeUninit<T>]> = (0..capacity.next_power_of_two()).map(|_| MaybeUninit::uninit()).collect();
+        RingBuffer { slots, capacity, head: 0, length: 0, policy }
+    }
+
+    pub fn capacity(&self) -> usize {
+        return self.capacity;
+    }
+
+    fn mask(&self) -> usize {
+        return self.slots.len() - 1;
+    }
+
+    fn slot(&self, index: usize) -> usize {
+        return (self.head + index) & self.mask();
+    }
+
+    pub fn len(&self) -> usize {
+        return self.length;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.length == 0;
+    }
+
+    pub fn is_full(&self) -> bool {
+        return self.length == self.capacity();
+    }
+
+    /// Adds `data` after the last element. On a full buffer this either
+    /// evicts and returns the front element or rejects `data`, depending on
+    /// the policy.
+    pub fn push_back(&mut self, data: T) -> Result<Option<T>, CapacityError<T>> {
+        let mut evicted: Option<T> = None;
+        if self.is_full() {
+            match self.policy {
+                FullPolicy::Reject => return Err(CapacityError(data)),
+                FullPolicy::Overwrite => evicted = self.pop_front(),
+            }
+        }
+
+        let slot: usize = self.slot(self.length);
+        self.slots[slot].write(data);
+        self.length += 1;
+        return Ok(evicted);
+    }
+
+    /// Adds `data` before the first element, evicting from the back when the
+    /// policy allows it.
+    pub fn push_front(&mut self, data: T) -> Result<Option<T>, CapacityError<T>> {
+        let mut evicted: Option<T> = None;
+        if self.is_full() {
+            match self.policy {
+                FullPolicy::Reject => return Err(CapacityError(data)),
+                FullPolicy::Overwrite => evicted = self.pop_back(),
+            }
+        }
+
+        self.head = self.head.wrapping_sub(1) & self.mask();
+        self.slots[self.head].write(data);
+        self.length += 1;
+        return Ok(evicted);
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.length == 0 {
+            return None;
+        }
+
+        let slot: usize = self.head;
+        self.head = self.slot(1);
+        self.length -= 1;
+        // SAFETY: the old head slot was initialised and is no longer part of
+        // the buffer's range.
+        return Some(unsafe { self.slots[slot].assume_init_read() });
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.length == 0 {
+            return None;
+        }
+
+        self.length -= 1;
+        let slot: usize = self.slot(self.length);
+        // SAFETY: as in `pop_front`, for the last slot.
+        return Some(unsafe { self.slots[slot].assume_init_read() });
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        self.get(self.length.checked_sub(1)?)
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.length {
+            return None;
+        }
+
+        // SAFETY: every logical index below `length` maps to an initialised slot.
+        return Some(unsafe { self.slots[self.slot(index)].assume_init_ref() });
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.length {
+            return None;
+        }
+
+        let slot: usize = self.slot(index);
+        // SAFETY: as in `get`.
+        return Some(unsafe { self.slots[slot].assume_init_mut() });
+    }
+
+    /// The elements in order as two slices: from `head` to the end of the
+    /// allocation, then whatever wrapped round to the start.
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let first_length: usize = self.length.min(self.slots.len() - self.head);
+        let second_length: usize = self.length - first_length;
+
+        // SAFETY: both ranges are initialised slots, and `MaybeUninit<T>` has
+        // the same layout as `T`.
+        unsafe {
+            let base: *const T = self.slots.as_ptr() as *const T;
+            let first: &[T] = slice::from_raw_parts(base.add(self.head), first_length);
+            let second: &[T] = slice::from_raw_parts(base, second_length);
+            return (first, second);
+        }
+    }
+
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let first_length: usize = self.length.min(self.slots.len() - self.head);
+        let second_length: usize = self.length - first_length;
+
+        // SAFETY: as in `as_slices`; the two ranges never overlap because
+        // together they hold at most `slots.len()` slots.
+        unsafe {
+            let base: *mut T = self.slots.as_mut_ptr() as *mut T;
+            let first: &mut [T] = slice::from_raw_parts_mut(base.add(self.head), first_length);
+            let second: &mut [T] = slice::from_raw_parts_mut(base, second_length);
+            return (first, second);
+        }
+    }
+
+    pub fn iter(&self) -> Chain<slice::Iter<'_, T>, slice::Iter<'_, T>> {
+        let (first, second): (&[T], &[T]) = self.as_slices();
+        first.iter().chain(second.iter())
+    }
+
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+}
+
+impl<T> Drop for RingBuffer<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T> Index<usize> for RingBuffer<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        match self.get(index) {
+            Some(data) => data,
+            None => panic!("index {} out of range for length {}", index, self.length),
+        }
+    }
+}
+
+impl<T> IndexMut<usize> for RingBuffer<T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        let length: usize = self.length;
+        match self.get_mut(index) {
+            Some(data) => data,
+            None => panic!("index {} out of range for length {}", index, length),
+        }
+    }
+}
+
+impl<T: Debug> Debug for RingBuffer<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    fn collect(buffer: &RingBuffer<i64>) -> Vec<i64> {
+        buffer.iter().cloned().collect()
+    }
+
+    #[test]
+    fn test_capacity_is_not_rounded() {
+        assert_eq!(RingBuffer::<i64>::new(5, FullPolicy::Reject).capacity(), 5);
+        assert_eq!(RingBuffer::<i64>::new(8, FullPolicy::Reject).capacity(), 8);
+        assert_eq!(RingBuffer::<i64>::new(0, FullPolicy::Reject).capacity(), 1);
+    }
+
+    #[test]
+    fn test_odd_capacity_bounds_both_policies() {
+        let mut rejecting: RingBuffer<i64> = RingBuffer::new(5, FullPolicy::Reject);
+        for value in 0..5 {
+            assert_eq!(rejecting.push_back(value), Ok(None));
+        }
+        assert!(rejecting.is_full());
+        assert_eq!(rejecting.push_back(5), Err(CapacityError(5)));
+
+        let mut window: RingBuffer<i64> = RingBuffer::new(5, FullPolicy::Overwrite);
+        for value in 0..5 {
+            assert_eq!(window.push_back(value), Ok(None));
+        }
+        for value in 5..23 {
+            assert_eq!(window.push_back(value), Ok(Some(value - 5)));
+            assert_eq!(window.len(), 5);
+        }
+        assert_eq!(collect(&window), vec![18, 19, 20, 21, 22]);
+        assert_eq!(window.push_front(17), Ok(Some(22)));
+        assert_eq!(collect(&window), vec![17, 18, 19, 20, 21]);
+    }
+
+    #[test]
+    fn test_push_and_pop_both_ends() {
+        let mut buffer: RingBuffer<i64> = RingBuffer::new(4, FullPolicy::Reject);
+        assert_eq!(buffer.push_back(2), Ok(None));
+        assert_eq!(buffer.push_front(1), Ok(None));
+        assert_eq!(buffer.push_back(3), Ok(None));
+        assert_eq!(collect(&buffer), vec![1, 2, 3]);
+        assert_eq!(buffer.front(), Some(&1));
+        assert_eq!(buffer.back(), Some(&3));
+
+        assert_eq!(buffer.pop_back(), Some(3));
+        assert_eq!(buffer.pop_front(), Some(1));
+        assert_eq!(buffer.pop_front(), Some(2));
+        assert_eq!(buffer.pop_front(), None);
+        assert_eq!(buffer.pop_back(), None);
+    }
+
+    #[test]
+    fn test_reject_when_full() {
+        let mut buffer: RingBuffer<i64> = RingBuffer::new(2, FullPolicy::Reject);
+        let _ = buffer.push_back(1);
+        let _ = buffer.push_back(2);
+        assert_eq!(buffer.push_back(3), Err(CapacityError(3)));
+        assert_eq!(buffer.push_front(0), Err(CapacityError(0)));
+        assert_eq!(collect(&buffer), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_overwrite_when_full() {
+        let mut buffer: RingBuffer<i64> = RingBuffer::new(4, FullPolicy::Overwrite);
+        for value in 0..4 {
+            let _ = buffer.push_back(value);
+        }
+        assert_eq!(buffer.push_back(4), Ok(Some(0)));
+        assert_eq!(buffer.push_back(5), Ok(Some(1)));
+        assert_eq!(collect(&buffer), vec![2, 3, 4, 5]);
+
+        assert_eq!(buffer.push_front(1), Ok(Some(5)));
+        assert_eq!(collect(&buffer), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_logical_index() {
+        let mut buffer: RingBuffer<i64> = RingBuffer::new(4, FullPolicy::Overwrite);
+        for value in 0..6 {
+            let _ = buffer.push_back(value);
+        }
+        assert_eq!(buffer[0], 2);
+        assert_eq!(buffer.get(3), Some(&5));
+        assert_eq!(buffer.get(4), None);
+
+        buffer[1] = 30;
+        *buffer.get_mut(2).unwrap() += 40;
+        assert_eq!(collect(&buffer), vec![2, 30, 44, 5]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_out_of_range() {
+        let buffer: RingBuffer<i64> = RingBuffer::new(4, FullPolicy::Reject);
+        let _ = buffer[0];
+    }
+
+    #[test]
+    fn test_as_slices() {
+        let mut buffer: RingBuffer<i64> = RingBuffer::new(4, FullPolicy::Reject);
+        let _ = buffer.push_back(1);
+        let _ = buffer.push_back(2);
+        assert_eq!(buffer.as_slices(), (&[1, 2][..], &[][..]));
+
+        let _ = buffer.pop_front();
+        let _ = buffer.push_back(3);
+        let _ = buffer.push_back(4);
+        let _ = buffer.push_back(5);
+        assert_eq!(buffer.as_slices(), (&[2, 3, 4][..], &[5][..]));
+
+        assert_eq!(buffer.push_front(0), Err(CapacityError(0)));
+        let (first, second): (&mut [i64], &mut [i64]) = buffer.as_mut_slices();
+        first[0] = 10;
+        second[0] = 50;
+        assert_eq!(collect(&buffer), vec![10, 3, 4, 50]);
+    }
+
+    #[test]
+    fn test_as_slices_after_push_front_wraps() {
+        let mut buffer: RingBuffer<i64> = RingBuffer::new(4, FullPolicy::Reject);
+        let _ = buffer.push_back(1);
+        let _ = buffer.push_front(0);
+        assert_eq!(buffer.as_slices(), (&[0][..], &[1][..]));
+    }
+
+    #[test]
+    fn test_fifo_over_many_wraps() {
+        let mut buffer: RingBuffer<i64> = RingBuffer::new(8, FullPolicy::Reject);
+        let mut next_in: i64 = 0;
+        let mut next_out: i64 = 0;
+
+        for round in 0..100 {
+            for _ in 0..(round % 8) {
+                if buffer.push_back(next_in).is_ok() {
+                    next_in += 1;
+                }
+            }
+            for _ in 0..(round % 5) {
+                if let Some(value) = buffer.pop_front() {
+                    assert_eq!(value, next_out);
+                    next_out += 1;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_drop_releases_elements() {
+        let counter: Rc<()> = Rc::new(());
+        let mut buffer: RingBuffer<Rc<()>> = RingBuffer::new(4, FullPolicy::Overwrite);
+        for _ in 0..7 {
+            let _ = buffer.push_back(counter.clone());
+        }
+        assert_eq!(Rc::strong_count(&counter), 5);
+
+        drop(buffer);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+}
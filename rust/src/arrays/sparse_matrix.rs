@@ -0,0 +1,396 @@
+use std::fmt::Error;
+use std::ops::{Add, Mul, Range};
+
+/// What a sparse matrix can hold: `T::default()` is the implicit zero, and
+/// duplicate coordinates are summed.
+pub trait Scalar: Copy + Default + PartialEq + Add<Output = Self> + Mul<Output = Self> {}
+
+impl<T: Copy + Default + PartialEq + Add<Output = T> + Mul<Output = T>> Scalar for T {}
+
+/// Compressed storage along one axis, shared by CSR (major = row) and CSC
+/// (major = column). The entries of major line `i` are
+/// `offsets[i]..offsets[i + 1]`, sorted by their minor index.
+#[derive(Clone, PartialEq, Debug)]
+struct Compressed<T> {
+    offsets: Vec<usize>,
+    minors: Vec<usize>,
+    values: Vec<T>,
+}
+
+impl<T: Scalar> Compressed<T> {
+    /// Sorts the triplets, sums duplicates and drops explicit zeros.
+    fn from_triplets(majors: usize, mut triplets: Vec<(usize, usize, T)>) -> Self {
+        triplets.sort_by_key(|(major, minor, _)| (*major, *minor));
+
+        let mut merged: Vec<(usize, usize, T)> = Vec::with_capacity(triplets.len());
+        for (major, minor, value) in triplets {
+            match merged.last_mut() {
+                Some(last) if last.0 == major && last.1 == minor => last.2 = last.2 + value,
+                _ => merged.push((major, minor, value)),
+            }
+        }
+        merged.retain(|(_, _, value)| *value != T::default());
+
+        let mut offsets: Vec<usize> = vec![0; majors + 1];
+        for (major, _, _) in &merged {
+            offsets[major + 1] += 1;
+        }
+        for major in 0..majors {
+            offsets[major + 1] += offsets[major];
+        }
+
+        Compressed {
+            offsets,
+            minors: merged.iter().map(|(_, minor, _)| *minor).collect(),
+            values: merged.iter().map(|(_, _, value)| *value).collect(),
+        }
+    }
+
+    /// Re-compresses along the other axis with a counting sort, in
+    /// O(non-zeros + minors). Walking the majors in order keeps each new
+    /// line sorted.
+    fn swap_axes(&self, minors: usize) -> Self {
+        let mut offsets: Vec<usize> = vec![0; minors + 1];
+        for minor in &self.minors {
+            offsets[minor + 1] += 1;
+        }
+        for minor in 0..minors {
+            offsets[minor + 1] += offsets[minor];
+        }
+
+        let mut next: Vec<usize> = offsets.clone();
+        let mut new_minors: Vec<usize> = vec![0; self.values.len()];
+        let mut values: Vec<T> = vec![T::default(); self.values.len()];
+        for major in 0..self.offsets.len() - 1 {
+            for entry in self.offsets[major]..self.offsets[major + 1] {
+                let slot: usize = next[self.minors[entry]];
+                next[self.minors[entry]] += 1;
+                new_minors[slot] = major;
+                values[slot] = self.values[entry];
+            }
+        }
+
+        Compressed { offsets, minors: new_minors, values }
+    }
+
+    fn get(&self, major: usize, minor: usize) -> T {
+        let line: Range<usize> = self.offsets[major]..self.offsets[major + 1];
+        match self.minors[line.clone()].binary_search(&minor) {
+            Ok(position) => self.values[line.start + position],
+            Err(_) => T::default(),
+        }
+    }
+
+    /// Every stored entry as `(major, minor, value)`.
+    fn iter(&self) -> impl Iterator<Item = (usize, usize, T)> + '_ {
+        (0..self.offsets.len() - 1).flat_map(move |major| {
+            (self.offsets[major]..self.offsets[major + 1]).map(move |entry| (major, self.minors[entry], self.values[entry]))
+        })
+    }
+}
+
+/// Coordinate (triplet) form: an unordered list of `(row, column, value)`.
+/// Cheap to build, so it is how the compressed forms are constructed.
+#[derive(Clone, PartialEq, Debug)]
+pub struct CooMatrix<T> {
+    rows: usize,
+    columns: usize,
+    entries: Vec<(usize, usize, T)>,
+}
+
+impl<T: Scalar> CooMatrix<T> {
+    pub fn new(rows: usize, columns: usize) -> Self {
+        CooMatrix { rows, columns, entries: Vec::new() }
+    }
+
+    pub fn from(rows: usize, columns: usize, entries: Vec<(usize, usize, T)>) -> Result<Self, Error> {
+        let mut matrix: CooMatrix<T> = CooMatrix::new(rows, columns);
+
+        for (row, column, value) in entries {
+            matrix.push(row, column, value)?;
+        }
+
+        return Ok(matrix);
+    }
+
+    pub fn shape(&self) -> (usize, usize) {
+        return (self.rows, self.columns);
+    }
+
+    /// The number of triplets pushed, counting duplicates.
+    pub fn len(&self) -> usize {
+        return self.entries.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.entries.is_empty();
+    }
+
+    /// Adds a triplet. Repeated coordinates are summed on conversion.
+    pub fn push(&mut self, row: usize, column: usize, value: T) -> Result<(), Error> {
+        if row >= self.rows || column >= self.columns {
+            return Err(Error);
+        }
+
+        self.entries.push((row, column, value));
+        return Ok(());
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize, T)> + '_ {
+        self.entries.iter().copied()
+    }
+
+    pub fn to_csr(&self) -> CsrMatrix<T> {
+        CsrMatrix { rows: self.rows, columns: self.columns, storage: Compressed::from_triplets(self.rows, self.entries.clone()) }
+    }
+
+    pub fn to_csc(&self) -> CscMatrix<T> {
+        let swapped: Vec<(usize, usize, T)> = self.entries.iter().map(|(row, column, value)| (*column, *row, *value)).collect();
+        CscMatrix { rows: self.rows, columns: self.columns, storage: Compressed::from_triplets(self.columns, swapped) }
+    }
+}
+
+/// Compressed sparse row form: fast row access and matrix-vector products.
+#[derive(Clone, PartialEq, Debug)]
+pub struct CsrMatrix<T> {
+    rows: usize,
+    columns: usize,
+    storage: Compressed<T>,
+}
+
+impl<T: Scalar> CsrMatrix<T> {
+    pub fn shape(&self) -> (usize, usize) {
+        return (self.rows, self.columns);
+    }
+
+    pub fn non_zeros(&self) -> usize {
+        return self.storage.values.len();
+    }
+
+    pub fn get(&self, row: usize, column: usize) -> Result<T, Error> {
+        if row >= self.rows || column >= self.columns {
+            return Err(Error);
+        }
+        return Ok(self.storage.get(row, column));
+    }
+
+    /// The non-zeros in row-major order as `(row, column, value)`.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize, T)> + '_ {
+        self.storage.iter()
+    }
+
+    /// The non-zeros of one row as `(column, value)`.
+    pub fn row(&self, row: usize) -> Result<impl Iterator<Item = (usize, T)> + '_, Error> {
+        if row >= self.rows {
+            return Err(Error);
+        }
+        return Ok(self.row_entries(row));
+    }
+
+    fn row_entries(&self, row: usize) -> impl Iterator<Item = (usize, T)> + '_ {
+        let line: Range<usize> = self.storage.offsets[row]..self.storage.offsets[row + 1];
+        line.map(move |entry| (self.storage.minors[entry], self.storage.values[entry]))
+    }
+
+    /// Computes `self * vector`, one dot product per row.
+    pub fn multiply(&self, vector: &[T]) -> Result<Vec<T>, Error> {
+        if vector.len() != self.columns {
+            return Err(Error);
+        }
+
+        let mut result: Vec<T> = Vec::with_capacity(self.rows);
+        for row in 0..self.rows {
+            result.push(self.row_entries(row).fold(T::default(), |sum, (column, value)| sum + value * vector[column]));
+        }
+
+        return Ok(result);
+    }
+
+    pub fn to_csc(&self) -> CscMatrix<T> {
+        CscMatrix { rows: self.rows, columns: self.columns, storage: self.storage.swap_axes(self.columns) }
+    }
+
+    pub fn to_coo(&self) -> CooMatrix<T> {
+        CooMatrix { rows: self.rows, columns: self.columns, entries: self.iter().collect() }
+    }
+
+    /// The CSC arrays of a matrix are the CSR arrays of its transpose.
+    pub fn transpose(&self) -> CsrMatrix<T> {
+        CsrMatrix { rows: self.columns, columns: self.rows, storage: self.storage.swap_axes(self.columns) }
+    }
+}
+
+/// Compressed sparse column form: fast column access and transposed
+/// products.
+#[derive(Clone, PartialEq, Debug)]
+pub struct CscMatrix<T> {
+    rows: usize,
+    columns: usize,
+    storage: Compressed<T>,
+}
+
+impl<T: Scalar> CscMatrix<T> {
+    pub fn shape(&self) -> (usize, usize) {
+        return (self.rows, self.columns);
+    }
+
+    pub fn non_zeros(&self) -> usize {
+        return self.storage.values.len();
+    }
+
+    pub fn get(&self, row: usize, column: usize) -> Result<T, Error> {
+        if row >= self.rows || column >= self.columns {
+            return Err(Error);
+        }
+        return Ok(self.storage.get(column, row));
+    }
+
+    /// The non-zeros in column-major order as `(row, column, value)`.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize, T)> + '_ {
+        self.storage.iter().map(|(column, row, value)| (row, column, value))
+    }
+
+    /// The non-zeros of one column as `(row, value)`.
+    pub fn column(&self, column: usize) -> Result<impl Iterator<Item = (usize, T)> + '_, Error> {
+        if column >= self.columns {
+            return Err(Error);
+        }
+        return Ok(self.column_entries(column));
+    }
+
+    fn column_entries(&self, column: usize) -> impl Iterator<Item = (usize, T)> + '_ {
+        let line: Range<usize> = self.storage.offsets[column]..self.storage.offsets[column + 1];
+        line.map(move |entry| (self.storage.minors[entry], self.storage.values[entry]))
+    }
+
+    /// Computes `self * vector` by scattering each column scaled by its
+    /// vector entry.
+    pub fn multiply(&self, vector: &[T]) -> Result<Vec<T>, Error> {
+        if vector.len() != self.columns {
+            return Err(Error);
+        }
+
+        let mut result: Vec<T> = vec![T::default(); self.rows];
+        for (column, scale) in vector.iter().enumerate() {
+            for (row, value) in self.column_entries(column) {
+                result[row] = result[row] + value * *scale;
+            }
+        }
+
+        return Ok(result);
+    }
+
+    pub fn to_csr(&self) -> CsrMatrix<T> {
+        CsrMatrix { rows: self.rows, columns: self.columns, storage: self.storage.swap_axes(self.rows) }
+    }
+
+    pub fn to_coo(&self) -> CooMatrix<T> {
+        CooMatrix { rows: self.rows, columns: self.columns, entries: self.iter().collect() }
+    }
+
+    pub fn transpose(&self) -> CscMatrix<T> {
+        CscMatrix { rows: self.columns, columns: self.rows, storage: self.storage.swap_axes(self.rows) }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 1 0 2 0
+    /// 0 0 0 0
+    /// 0 3 0 4
+    fn example() -> CooMatrix<i64> {
+        CooMatrix::from(3, 4, vec![(2, 3, 4), (0, 0, 1), (2, 1, 3), (0, 2, 2)]).unwrap()
+    }
+
+    #[test]
+    fn test_coo_push() {
+        let mut matrix: CooMatrix<i64> = CooMatrix::new(2, 2);
+        assert!(matrix.is_empty());
+        assert_eq!(matrix.push(1, 1, 5), Ok(()));
+        assert!(matrix.push(2, 0, 5).is_err());
+        assert!(matrix.push(0, 2, 5).is_err());
+        assert_eq!(matrix.len(), 1);
+        assert_eq!(matrix.shape(), (2, 2));
+    }
+
+    #[test]
+    fn test_to_csr() {
+        let csr: CsrMatrix<i64> = example().to_csr();
+        assert_eq!(csr.non_zeros(), 4);
+        assert_eq!(csr.storage.offsets, vec![0, 2, 2, 4]);
+        assert_eq!(csr.storage.minors, vec![0, 2, 1, 3]);
+        assert_eq!(csr.get(2, 1), Ok(3));
+        assert_eq!(csr.get(1, 1), Ok(0));
+        assert!(csr.get(3, 0).is_err());
+        assert_eq!(csr.iter().collect::<Vec<_>>(), vec![(0, 0, 1), (0, 2, 2), (2, 1, 3), (2, 3, 4)]);
+        assert_eq!(csr.row(1).unwrap().count(), 0);
+        assert_eq!(csr.row(2).unwrap().collect::<Vec<_>>(), vec![(1, 3), (3, 4)]);
+        assert!(csr.row(3).is_err());
+    }
+
+    #[test]
+    fn test_to_csc() {
+        let csc: CscMatrix<i64> = example().to_csc();
+        assert_eq!(csc.storage.offsets, vec![0, 1, 2, 3, 4]);
+        assert_eq!(csc.get(0, 2), Ok(2));
+        assert_eq!(csc.iter().collect::<Vec<_>>(), vec![(0, 0, 1), (2, 1, 3), (0, 2, 2), (2, 3, 4)]);
+        assert_eq!(csc.column(3).unwrap().collect::<Vec<_>>(), vec![(2, 4)]);
+        assert!(csc.column(4).is_err());
+    }
+
+    #[test]
+    fn test_duplicates_summed_and_zeros_dropped() {
+        let coo: CooMatrix<i64> = CooMatrix::from(2, 2, vec![(0, 1, 2), (0, 1, 3), (1, 0, 5), (1, 0, -5), (1, 1, 0)]).unwrap();
+        let csr: CsrMatrix<i64> = coo.to_csr();
+        assert_eq!(csr.non_zeros(), 1);
+        assert_eq!(csr.get(0, 1), Ok(5));
+        assert_eq!(coo.to_csc().non_zeros(), 1);
+    }
+
+    #[test]
+    fn test_conversions_round_trip() {
+        let csr: CsrMatrix<i64> = example().to_csr();
+        let csc: CscMatrix<i64> = csr.to_csc();
+        assert_eq!(csc, example().to_csc());
+        assert_eq!(csc.to_csr(), csr);
+        assert_eq!(csr.to_coo().to_csr(), csr);
+        assert_eq!(csc.to_coo().to_csc(), csc);
+    }
+
+    #[test]
+    fn test_transpose() {
+        let csr: CsrMatrix<i64> = example().to_csr();
+        let transposed: CsrMatrix<i64> = csr.transpose();
+        assert_eq!(transposed.shape(), (4, 3));
+        for (row, column, value) in csr.iter() {
+            assert_eq!(transposed.get(column, row), Ok(value));
+        }
+        assert_eq!(transposed.non_zeros(), csr.non_zeros());
+        assert_eq!(transposed.transpose(), csr);
+
+        let csc: CscMatrix<i64> = example().to_csc();
+        assert_eq!(csc.transpose().to_csr(), transposed);
+    }
+
+    #[test]
+    fn test_multiply() {
+        let vector: Vec<i64> = vec![1, 2, 3, 4];
+        assert_eq!(example().to_csr().multiply(&vector), Ok(vec![7, 0, 22]));
+        assert_eq!(example().to_csc().multiply(&vector), Ok(vec![7, 0, 22]));
+        assert!(example().to_csr().multiply(&[1, 2]).is_err());
+        assert!(example().to_csc().multiply(&[1, 2]).is_err());
+    }
+
+    #[test]
+    fn test_float_identity() {
+        let size: usize = 1000;
+        let coo: CooMatrix<f64> = CooMatrix::from(size, size, (0..size).map(|index| (index, index, 1.0)).collect()).unwrap();
+        let vector: Vec<f64> = (0..size).map(|index| index as f64 * 0.5).collect();
+        assert_eq!(coo.to_csr().multiply(&vector), Ok(vector.clone()));
+        assert_eq!(coo.to_csc().multiply(&vector), Ok(vector));
+    }
+}
@@ -0,0 +1,3 @@
+#![allow(clippy::needless_return, clippy::new_without_default)]
+
+pub mod rope;
@@ -0,0 +1,672 @@
+use std::fmt::{Debug, Display, Error, Formatter};
+use std::ops::Range;
+
+/// The most bytes a leaf holds. Small edits go straight into a leaf while it
+/// has room, so typing doesn't grow the tree one node per keystroke.
+const MAX_LEAF: usize = 1024;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct Metrics {
+    bytes: usize,
+    chars: usize,
+    newlines: usize,
+}
+
+impl Metrics {
+    fn of(text: &str) -> Self {
+        Metrics { bytes: text.len(), chars: text.chars().count(), newlines: text.matches('\n').count() }
+    }
+
+    fn add(self, other: Metrics) -> Self {
+        Metrics {
+            bytes: self.bytes + other.bytes,
+            chars: self.chars + other.chars,
+            newlines: self.newlines + other.newlines,
+        }
+    }
+}
+
+/// Text lives in the leaves; a branch caches the totals of its subtree and
+/// its height, and the two children's heights differ by at most one.
+#[derive(Clone)]
+enum Node {
+    Leaf { text: String, metrics: Metrics },
+    Branch { left: Box<Node>, right: Box<Node>, metrics: Metrics, height: usize },
+}
+
+/// The byte offset of char `at` in `text`, or the length for `at == chars`.
+fn byte_offset(text: &str, at: usize) -> usize {
+    text.char_indices().nth(at).map(|(byte, _)| byte).unwrap_or(text.len())
+}
+
+impl Node {
+    fn leaf(text: String) -> Node {
+        let metrics: Metrics = Metrics::of(&text);
+        Node::Leaf { text, metrics }
+    }
+
+    fn branch(left: Node, right: Node) -> Node {
+        let metrics: Metrics = left.metrics().add(right.metrics());
+        let height: usize = left.height().max(right.height()) + 1;
+        Node::Branch { left: Box::new(left), right: Box::new(right), metrics, height }
+    }
+
+    /// Cuts `text` into leaves at char boundaries and stacks them into a
+    /// perfectly balanced tree.
+    fn build(text: &str) -> Node {
+        let mut leaves: Vec<Node> = Vec::new();
+        let mut rest: &str = text;
+
+        while !rest.is_empty() {
+            let mut end: usize = rest.len().min(MAX_LEAF);
+            while !rest.is_char_boundary(end) {
+                end -= 1;
+            }
+            leaves.push(Node::leaf(rest[..end].to_string()));
+            rest = &rest[end..];
+        }
+
+        return Node::stack(leaves);
+    }
+
+    fn stack(mut nodes: Vec<Node>) -> Node {
+        match nodes.len() {
+            0 => Node::leaf(String::new()),
+            1 => nodes.pop().expect("one node"),
+            length => {
+                let right: Vec<Node> = nodes.split_off(length / 2);
+                Node::branch(Node::stack(nodes), Node::stack(right))
+            }
+        }
+    }
+
+    fn metrics(&self) -> Metrics {
+        match self {
+            Node::Leaf { metrics, .. } | Node::Branch { metrics, .. } => *metrics,
+        }
+    }
+
+    fn height(&self) -> usize {
+        match self {
+            Node::Leaf { .. } => 0,
+            Node::Branch { height, .. } => *height,
+        }
+    }
+
+    fn into_children(self) -> (Node, Node) {
+        match self {
+            Node::Branch { left, right, .. } => (*left, *right),
+            Node::Leaf { .. } => unreachable!("a node taller than its sibling is a branch"),
+        }
+    }
+
+    /// Joins two trees whose heights differ by at most two, rotating once
+    /// or twice to restore the height invariant.
+    fn balance(left: Node, right: Node) -> Node {
+        if left.height() > right.height() + 1 {
+            let (outer, inner): (Node, Node) = left.into_children();
+            if outer.height() >= inner.height() {
+                return Node::branch(outer, Node::branch(inner, right));
+            }
+            let (inner_left, inner_right): (Node, Node) = inner.into_children();
+            return Node::branch(Node::branch(outer, inner_left), Node::branch(inner_right, right));
+        }
+
+        if right.height() > left.height() + 1 {
+            let (inner, outer): (Node, Node) = right.into_children();
+            if outer.height() >= inner.height() {
+                return Node::branch(Node::branch(left, inner), outer);
+            }
+            let (inner_left, inner_right): (Node, Node) = inner.into_children();
+            return Node::branch(Node::branch(left, inner_left), Node::branch(inner_right, outer));
+        }
+
+        return Node::branch(left, right);
+    }
+
+    /// Concatenates two trees of any heights in O(|height difference|) by
+    /// walking down the taller one's inner spine.
+    fn join(left: Node, right: Node) -> Node {
+        if left.metrics().bytes == 0 {
+            return right;
+        }
+        if right.metrics().bytes == 0 {
+            return left;
+        }
+
+        if left.height() > right.height() + 1 {
+            let (outer, inner): (Node, Node) = left.into_children();
+            return Node::balance(outer, Node::join(inner, right));
+        }
+        if right.height() > left.height() + 1 {
+            let (inner, outer): (Node, Node) = right.into_children();
+            return Node::balance(Node::join(left, inner), outer);
+        }
+
+        match (left, right) {
+            (Node::Leaf { text: mut first, .. }, Node::Leaf { text: second, .. })
+                if first.len() + second.len() <= MAX_LEAF =>
+            {
+                first.push_str(&second);
+                Node::leaf(first)
+            }
+            (left, right) => Node::branch(left, right),
+        }
+    }
+
+    /// Splits before char `at`; both halves stay balanced.
+    fn split(self, at: usize) -> (Node, Node) {
+        match self {
+            Node::Leaf { mut text, .. } => {
+                let right: String = text.split_off(byte_offset(&text, at));
+                (Node::leaf(text), Node::leaf(right))
+            }
+            Node::Branch { left, right, .. } => {
+                let left_chars: usize = left.metrics().chars;
+                if at < left_chars {
+                    let (first, second): (Node, Node) = left.split(at);
+                    (first, Node::join(second, *right))
+                } else if at == left_chars {
+                    (*left, *right)
+                } else {
+                    let (first, second): (Node, Node) = right.split(at - left_chars);
+                    (Node::join(*left, first), second)
+                }
+            }
+        }
+    }
+
+    /// Inserts into the leaf holding char `at` if it has room, updating the
+    /// totals on the way back up. Returns `false` without changing anything
+    /// otherwise.
+    fn insert_in_leaf(&mut self, at: usize, text: &str) -> bool {
+        match self {
+            Node::Leaf { text: leaf, metrics } => {
+                if leaf.len() + text.len() > MAX_LEAF {
+                    return false;
+                }
+                leaf.insert_str(byte_offset(leaf, at), text);
+                *metrics = Metrics::of(leaf);
+                true
+            }
+            Node::Branch { left, right, metrics, .. } => {
+                let left_chars: usize = left.metrics().chars;
+                let inserted: bool = if at <= left_chars {
+                    left.insert_in_leaf(at, text)
+                } else {
+                    right.insert_in_leaf(at - left_chars, text)
+                };
+                if inserted {
+                    *metrics = metrics.add(Metrics::of(text));
+                }
+                inserted
+            }
+        }
+    }
+
+    fn char_to_byte(&self, at: usize) -> usize {
+        match self {
+            Node::Leaf { text, .. } => byte_offset(text, at),
+            Node::Branch { left, right, .. } => {
+                let left_metrics: Metrics = left.metrics();
+                if at < left_metrics.chars {
+                    left.char_to_byte(at)
+                } else {
+                    left_metrics.bytes + right.char_to_byte(at - left_metrics.chars)
+                }
+            }
+        }
+    }
+
+    /// `None` if byte `at` falls inside a char.
+    fn byte_to_char(&self, at: usize) -> Option<usize> {
+        match self {
+            Node::Leaf { text, .. } => match text.is_char_boundary(at) {
+                true => Some(text[..at].chars().count()),
+                false => None,
+            },
+            Node::Branch { left, right, .. } => {
+                let left_metrics: Metrics = left.metrics();
+                if at < left_metrics.bytes {
+                    left.byte_to_char(at)
+                } else {
+                    Some(left_metrics.chars + right.byte_to_char(at - left_metrics.bytes)?)
+                }
+            }
+        }
+    }
+
+    /// The number of newlines before char `at`.
+    fn char_to_line(&self, at: usize) -> usize {
+        match self {
+            Node::Leaf { text, .. } => text.chars().take(at).filter(|c| *c == '\n').count(),
+            Node::Branch { left, right, .. } => {
+                let left_metrics: Metrics = left.metrics();
+                if at < left_metrics.chars {
+                    left.char_to_line(at)
+                } else {
+                    left_metrics.newlines + right.char_to_line(at - left_metrics.chars)
+                }
+            }
+        }
+    }
+
+    /// The char index of the `count`th newline, counting from one.
+    fn find_newline(&self, count: usize) -> usize {
+        match self {
+            Node::Leaf { text, .. } => text
+                .chars()
+                .enumerate()
+                .filter(|(_, c)| *c == '\n')
+                .nth(count - 1)
+                .map(|(index, _)| index)
+                .expect("the leaf holds the newline"),
+            Node::Branch { left, right, .. } => {
+                let left_metrics: Metrics = left.metrics();
+                if count <= left_metrics.newlines {
+                    left.find_newline(count)
+                } else {
+                    left_metrics.chars + right.find_newline(count - left_metrics.newlines)
+                }
+            }
+        }
+    }
+}
+
+/// A text buffer stored as a height-balanced binary tree of string chunks,
+/// so inserts, removes and positional lookups cost O(log n) rather than the
+/// O(n) walk of a linked list.
+///
+/// Positions are char indices unless a method says otherwise, which keeps
+/// every edit on a UTF-8 boundary. Lines are separated by `'\n'`.
+#[derive(Clone)]
+pub struct Rope {
+    root: Node,
+}
+
+impl Rope {
+    pub fn new() -> Self {
+        Rope { root: Node::leaf(String::new()) }
+    }
+
+    pub fn from(text: &str) -> Self {
+        Rope { root: Node::build(text) }
+    }
+
+    pub fn len_bytes(&self) -> usize {
+        return self.root.metrics().bytes;
+    }
+
+    pub fn len_chars(&self) -> usize {
+        return self.root.metrics().chars;
+    }
+
+    pub fn len_lines(&self) -> usize {
+        return self.root.metrics().newlines + 1;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.len_bytes() == 0;
+    }
+
+    pub fn height(&self) -> usize {
+        return self.root.height();
+    }
+
+    fn take_root(&mut self) -> Node {
+        std::mem::replace(&mut self.root, Node::leaf(String::new()))
+    }
+
+    pub fn insert(&mut self, at: usize, text: &str) -> Result<(), Error> {
+        if at > self.len_chars() {
+            return Err(Error);
+        }
+        if text.is_empty() || self.root.insert_in_leaf(at, text) {
+            return Ok(());
+        }
+
+        let (left, right): (Node, Node) = self.take_root().split(at);
+        self.root = Node::join(Node::join(left, Node::build(text)), right);
+        return Ok(());
+    }
+
+    pub fn remove(&mut self, range: Range<usize>) -> Result<(), Error> {
+        if range.start > range.end || range.end > self.len_chars() {
+            return Err(Error);
+        }
+
+        let (left, rest): (Node, Node) = self.take_root().split(range.start);
+        let (_, right): (Node, Node) = rest.split(range.end - range.start);
+        self.root = Node::join(left, right);
+        return Ok(());
+    }
+
+    /// Splits before char `at`, consuming the rope.
+    pub fn split(self, at: usize) -> Result<(Rope, Rope), Error> {
+        if at > self.len_chars() {
+            return Err(Error);
+        }
+
+        let (left, right): (Node, Node) = self.root.split(at);
+        return Ok((Rope { root: left }, Rope { root: right }));
+    }
+
+    pub fn concat(self, other: Rope) -> Rope {
+        Rope { root: Node::join(self.root, other.root) }
+    }
+
+    pub fn char(&self, at: usize) -> Option<char> {
+        if at >= self.len_chars() {
+            return None;
+        }
+
+        let byte: usize = self.root.char_to_byte(at);
+        self.chunk_at_byte(byte).and_then(|(chunk, offset)| chunk[offset..].chars().next())
+    }
+
+    pub fn byte(&self, at: usize) -> Option<u8> {
+        self.chunk_at_byte(at).map(|(chunk, offset)| chunk.as_bytes()[offset])
+    }
+
+    /// The leaf holding byte `at` and the offset into it.
+    fn chunk_at_byte(&self, mut at: usize) -> Option<(&str, usize)> {
+        let mut node: &Node = &self.root;
+        loop {
+            match node {
+                Node::Leaf { text, .. } => return if at < text.len() { Some((text, at)) } else { None },
+                Node::Branch { left, right, .. } => {
+                    let left_bytes: usize = left.metrics().bytes;
+                    if at < left_bytes {
+                        node = left;
+                    } else {
+                        at -= left_bytes;
+                        node = right;
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn char_to_byte(&self, at: usize) -> Result<usize, Error> {
+        if at > self.len_chars() {
+            return Err(Error);
+        }
+        return Ok(self.root.char_to_byte(at));
+    }
+
+    /// Fails if `at` is past the end or inside a multi-byte char.
+    pub fn byte_to_char(&self, at: usize) -> Result<usize, Error> {
+        if at > self.len_bytes() {
+            return Err(Error);
+        }
+        return self.root.byte_to_char(at).ok_or(Error);
+    }
+
+    /// The line (counting from zero) that char `at` is on.
+    pub fn char_to_line(&self, at: usize) -> Result<usize, Error> {
+        if at > self.len_chars() {
+            return Err(Error);
+        }
+        return Ok(self.root.char_to_line(at));
+    }
+
+    /// The char index where line `line` starts.
+    pub fn line_to_char(&self, line: usize) -> Option<usize> {
+        match line {
+            0 => Some(0),
+            line if line < self.len_lines() => Some(self.root.find_newline(line) + 1),
+            _ => None,
+        }
+    }
+
+    /// The text of line `line`, without its newline.
+    pub fn line(&self, line: usize) -> Option<String> {
+        let start: usize = self.line_to_char(line)?;
+        let end: usize = match self.line_to_char(line + 1) {
+            Some(next) => next - 1,
+            None => self.len_chars(),
+        };
+        self.slice(start..end).ok()
+    }
+
+    /// Copies the chars in `range` into a `String`.
+    pub fn slice(&self, range: Range<usize>) -> Result<String, Error> {
+        if range.start > range.end || range.end > self.len_chars() {
+            return Err(Error);
+        }
+
+        let start: usize = self.root.char_to_byte(range.start);
+        let end: usize = self.root.char_to_byte(range.end);
+        let mut text: String = String::with_capacity(end - start);
+        let mut before: usize = 0;
+
+        for chunk in self.chunks() {
+            let from: usize = start.max(before);
+            let to: usize = end.min(before + chunk.len());
+            if from < to {
+                text.push_str(&chunk[from - before..to - before]);
+            }
+            before += chunk.len();
+            if before >= end {
+                break;
+            }
+        }
+
+        return Ok(text);
+    }
+
+    /// The leaves' text, in order.
+    pub fn chunks(&self) -> Chunks<'_> {
+        Chunks { stack: vec![&self.root] }
+    }
+}
+
+pub struct Chunks<'a> {
+    stack: Vec<&'a Node>,
+}
+
+impl<'a> Iterator for Chunks<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        while let Some(node) = self.stack.pop() {
+            match node {
+                Node::Leaf { text, .. } if !text.is_empty() => return Some(text),
+                Node::Leaf { .. } => {}
+                Node::Branch { left, right, .. } => {
+                    self.stack.push(right);
+                    self.stack.push(left);
+                }
+            }
+        }
+
+        return None;
+    }
+}
+
+impl Display for Rope {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        for chunk in self.chunks() {
+            write!(f, "{}", chunk)?;
+        }
+        return Ok(());
+    }
+}
+
+impl Debug for Rope {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        f.debug_tuple("Rope").field(&self.to_string()).finish()
+    }
+}
+
+impl PartialEq for Rope {
+    fn eq(&self, other: &Self) -> bool {
+        return self.len_bytes() == other.len_bytes() && self.chunks().flat_map(str::bytes).eq(other.chunks().flat_map(str::bytes));
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A height-balanced tree of n leaves is at most about 1.44 log2(n) tall.
+    fn assert_balanced(rope: &Rope) {
+        let leaves: usize = rope.chunks().count().max(1);
+        let bound: f64 = 1.45 * ((leaves + 2) as f64).log2() + 1.0;
+        assert!((rope.height() as f64) <= bound, "height {} with {} leaves", rope.height(), leaves);
+    }
+
+    fn long_text(lines: usize) -> String {
+        (0..lines).map(|line| format!("line {} ünïcödé 😀\n", line)).collect()
+    }
+
+    #[test]
+    fn test_rope_new() {
+        let rope: Rope = Rope::new();
+        assert!(rope.is_empty());
+        assert_eq!(rope.len_lines(), 1);
+        assert_eq!(rope.to_string(), "");
+        assert_eq!(rope.chunks().count(), 0);
+    }
+
+    #[test]
+    fn test_rope_from() {
+        let text: String = long_text(2000);
+        let rope: Rope = Rope::from(&text);
+        assert_eq!(rope.to_string(), text);
+        assert_eq!(rope.len_bytes(), text.len());
+        assert_eq!(rope.len_chars(), text.chars().count());
+        assert!(rope.chunks().all(|chunk| chunk.len() <= MAX_LEAF));
+        assert_balanced(&rope);
+    }
+
+    #[test]
+    fn test_insert_and_remove_match_string() {
+        let mut rope: Rope = Rope::new();
+        let mut model: Vec<char> = Vec::new();
+        let mut seed: usize = 7;
+
+        for step in 0..3000 {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            let at: usize = (seed >> 33) % (model.len() + 1);
+            if step % 4 == 3 {
+                let end: usize = (at + (seed >> 20) % 40).min(model.len());
+                assert_eq!(rope.remove(at..end), Ok(()));
+                model.drain(at..end);
+            } else {
+                let text: &str = if step % 3 == 0 { "é😀\n" } else { "abc" };
+                assert_eq!(rope.insert(at, text), Ok(()));
+                for (offset, c) in text.chars().enumerate() {
+                    model.insert(at + offset, c);
+                }
+            }
+        }
+
+        assert_eq!(rope.to_string(), model.iter().collect::<String>());
+        assert_eq!(rope.len_chars(), model.len());
+        assert_balanced(&rope);
+    }
+
+    #[test]
+    fn test_large_insert_stays_balanced() {
+        let mut rope: Rope = Rope::from(&long_text(500));
+        let middle: usize = rope.len_chars() / 2;
+        let inserted: String = long_text(3000);
+        assert_eq!(rope.insert(middle, &inserted), Ok(()));
+        assert_eq!(rope.slice(middle..middle + inserted.chars().count()), Ok(inserted));
+        assert_balanced(&rope);
+    }
+
+    #[test]
+    fn test_out_of_range() {
+        let mut rope: Rope = Rope::from("abc");
+        assert!(rope.insert(4, "x").is_err());
+        assert!(rope.remove(2..4).is_err());
+        assert!(rope.slice(Range { start: 3, end: 2 }).is_err());
+        assert_eq!(rope.char(3), None);
+        assert_eq!(rope.to_string(), "abc");
+    }
+
+    #[test]
+    fn test_split_and_concat() {
+        let text: String = long_text(1000);
+        let at: usize = 12345;
+        let (left, right): (Rope, Rope) = Rope::from(&text).split(at).unwrap();
+        assert_eq!(left.len_chars(), at);
+        assert_eq!(left.to_string() + &right.to_string(), text);
+        assert_balanced(&left);
+        assert_balanced(&right);
+
+        let joined: Rope = right.concat(left);
+        assert_eq!(joined.len_chars(), text.chars().count());
+        assert_balanced(&joined);
+
+        let small: Rope = Rope::from("ab").concat(Rope::from(&long_text(2000)));
+        assert_balanced(&small);
+        assert!(Rope::from("ab").split(3).is_err());
+    }
+
+    #[test]
+    fn test_char_and_byte_indexing() {
+        let rope: Rope = Rope::from("aé😀b");
+        assert_eq!(rope.len_bytes(), 8);
+        assert_eq!(rope.len_chars(), 4);
+        assert_eq!(rope.char(1), Some('é'));
+        assert_eq!(rope.char(2), Some('😀'));
+        assert_eq!(rope.byte(0), Some(b'a'));
+        assert_eq!(rope.byte(8), None);
+
+        assert_eq!(rope.char_to_byte(2), Ok(3));
+        assert_eq!(rope.char_to_byte(4), Ok(8));
+        assert!(rope.char_to_byte(5).is_err());
+        assert_eq!(rope.byte_to_char(7), Ok(3));
+        assert!(rope.byte_to_char(2).is_err());
+        assert!(rope.byte_to_char(9).is_err());
+    }
+
+    #[test]
+    fn test_indexing_across_leaves() {
+        let text: String = long_text(1000);
+        let rope: Rope = Rope::from(&text);
+        let chars: Vec<char> = text.chars().collect();
+
+        for at in (0..chars.len()).step_by(997) {
+            assert_eq!(rope.char(at), Some(chars[at]));
+            let byte: usize = rope.char_to_byte(at).unwrap();
+            assert_eq!(byte, text.char_indices().nth(at).unwrap().0);
+            assert_eq!(rope.byte_to_char(byte), Ok(at));
+        }
+    }
+
+    #[test]
+    fn test_lines() {
+        let text: String = long_text(1000);
+        let rope: Rope = Rope::from(&text);
+        assert_eq!(rope.len_lines(), 1001);
+        assert_eq!(rope.line(0), Some("line 0 ünïcödé 😀".to_string()));
+        assert_eq!(rope.line(777), Some("line 777 ünïcödé 😀".to_string()));
+        assert_eq!(rope.line(1000), Some("".to_string()));
+        assert_eq!(rope.line(1001), None);
+
+        let start: usize = rope.line_to_char(500).unwrap();
+        assert_eq!(rope.char_to_line(start), Ok(500));
+        assert_eq!(rope.char_to_line(start - 1), Ok(499));
+    }
+
+    #[test]
+    fn test_chunks_and_equality() {
+        let text: String = long_text(300);
+        let rope: Rope = Rope::from(&text);
+        assert!(rope.chunks().count() > 1);
+        assert_eq!(rope.chunks().collect::<String>(), text);
+
+        let half: usize = text.char_indices().nth(text.chars().count() / 2).unwrap().0;
+        let mut edited: Rope = Rope::from("");
+        let _ = edited.insert(0, &text[half..]);
+        let _ = edited.insert(0, &text[..half]);
+        assert_eq!(edited, rope);
+        assert_ne!(edited, Rope::from("other"));
+    }
+}
@@ -0,0 +1,384 @@
+use std::cell::{Cell, UnsafeCell};
+use std::marker::PhantomData;
+use std::mem::MaybeUninit;
+use std::ptr;
+use std::sync::atomic::{fence, AtomicIsize, AtomicPtr, Ordering};
+use std::sync::Arc;
+
+use super::hazard_pointer::{Domain, Guard};
+
+/// The capacity of a new deque's buffer, which doubles whenever it fills.
+const MIN_CAPACITY: usize = 16;
+
+/// A circular array indexed by the deque's ever-growing positions: position
+/// `i` lives in slot `i & mask`. Slots are only ever copied bitwise, so which
+/// of them hold live elements is decided by `top` and `bottom` alone.
+struct Buffer<T> {
+    slots: Box<[UnsafeCell<MaybeUninit<T>>]>,
+    mask: isize,
+}
+
+impl<T> Buffer<T> {
+    fn new(capacity: usize) -> *mut Buffer<T> {
+        let slots: Box<[UnsafeCell<MaybeUninit<T>>]> = (0..capacity).map(|_| UnsafeCell::new(MaybeUninit::uninit())).collect();
+        Box::into_raw(Box::new(Buffer { slots, mask: capacity as isize - 1 }))
+    }
+
+    fn capacity(&self) -> isize {
+        self.mask + 1
+    }
+
+    /// Volatile, because a thief may be reading a slot the owner is
+    /// reusing; the thief then loses its CAS on `top` and discards what it
+    /// read.
+    unsafe fn write(&self, index: isize, data: MaybeUninit<T>) {
+        ptr::write_volatile(self.slots[(index & self.mask) as usize].get(), data);
+    }
+
+    unsafe fn read(&self, index: isize) -> MaybeUninit<T> {
+        ptr::read_volatile(self.slots[(index & self.mask) as usize].get())
+    }
+}
+
+struct Inner<T> {
+    top: AtomicIsize,
+    bottom: AtomicIsize,
+    buffer: AtomicPtr<Buffer<T>>,
+    domain: Domain,
+}
+
+// SAFETY: elements are moved out by exactly one thread each, see
+// `TreiberStack`.
+unsafe impl<T: Send> Send for Inner<T> {}
+unsafe impl<T: Send> Sync for Inner<T> {}
+
+/// Drops the elements still between `top` and `bottom`; buffers that were
+/// outgrown are freed by the domain and hold only bitwise copies.
+impl<T> Drop for Inner<T> {
+    fn drop(&mut self) {
+        let (top, bottom): (isize, isize) = (*self.top.get_mut(), *self.bottom.get_mut());
+        // SAFETY: with `&mut self` no worker or stealer is left.
+        let buffer: Box<Buffer<T>> = unsafe { Box::from_raw(*self.buffer.get_mut()) };
+
+        for index in top..bottom {
+            unsafe { buffer.read(index).assume_init_drop() };
+        }
+    }
+}
+
+/// The result of a steal.
+#[derive(PartialEq, Eq, Debug)]
+pub enum Steal<T> {
+    /// There was nothing to take.
+    Empty,
+    /// Another thread took the element first; the deque may still hold more.
+    Retry,
+    Success(T),
+}
+
+impl<T> Steal<T> {
+    pub fn success(self) -> Option<T> {
+        match self {
+            Steal::Success(data) => Some(data),
+            _ => None,
+        }
+    }
+}
+
+/// The owner's end of a Chase–Lev work-stealing deque (Chase and Lev 2005,
+/// with the memory orderings of Lê et al. 2013).
+///
+/// The owner pushes and pops at `bottom` like a stack, while any number of
+/// `Stealer`s take the oldest element at `top`. The two ends only contend
+/// over the last element, which both sides claim with a CAS on `top`, so the
+/// owner's common path is a few plain loads and stores.
+///
+/// When the buffer fills, the owner copies the live range into one twice
+/// the size. Thieves may still be reading the old buffer, so it is retired
+/// to a hazard-pointer domain and scanned straight away: it is freed then if
+/// no thief protects it, or else at a later grow or when the deque drops.
+///
+/// A `Worker` can be sent to another thread but not shared, since only one
+/// thread may push and pop.
+pub struct Worker<T> {
+    inner: Arc<Inner<T>>,
+    marker: PhantomData<Cell<()>>,
+}
+
+impl<T> Worker<T> {
+    pub fn new() -> Self {
+        let inner: Inner<T> = Inner {
+            top: AtomicIsize::new(0),
+            bottom: AtomicIsize::new(0),
+            buffer: AtomicPtr::new(Buffer::new(MIN_CAPACITY)),
+            domain: Domain::new(),
+        };
+        Worker { inner: Arc::new(inner), marker: PhantomData }
+    }
+
+    pub fn stealer(&self) -> Stealer<T> {
+        Stealer { inner: self.inner.clone() }
+    }
+
+    /// Exact for the owner, except that thieves may shrink it at any time.
+    pub fn len(&self) -> usize {
+        let bottom: isize = self.inner.bottom.load(Ordering::Relaxed);
+        let top: isize = self.inner.top.load(Ordering::Relaxed);
+        return (bottom - top).max(0) as usize;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The capacity of the current buffer.
+    pub fn capacity(&self) -> usize {
+        // SAFETY: only the owner replaces the buffer.
+        unsafe { (*self.inner.buffer.load(Ordering::Relaxed)).capacity() as usize }
+    }
+
+    pub fn push(&self, data: T) {
+        let inner: &Inner<T> = &self.inner;
+        let bottom: isize = inner.bottom.load(Ordering::Relaxed);
+        let top: isize = inner.top.load(Ordering::Acquire);
+        let mut buffer: *mut Buffer<T> = inner.buffer.load(Ordering::Relaxed);
+
+        // SAFETY: only the owner writes slots or frees buffers, and it never
+        // writes to a slot between `top` and `bottom`.
+        unsafe {
+            if bottom - top >= (*buffer).capacity() {
+                buffer = self.grow(buffer, top, bottom);
+            }
+            (*buffer).write(bottom, MaybeUninit::new(data));
+        }
+        inner.bottom.store(bottom + 1, Ordering::Release);
+    }
+
+    /// Takes the newest element.
+    pub fn pop(&self) -> Option<T> {
+        let inner: &Inner<T> = &self.inner;
+        let bottom: isize = inner.bottom.load(Ordering::Relaxed) - 1;
+        let buffer: *mut Buffer<T> = inner.buffer.load(Ordering::Relaxed);
+
+        // Claim the slot before looking at `top`, so a thief that reads
+        // `bottom` after this can't take it too.
+        inner.bottom.store(bottom, Ordering::Relaxed);
+        fence(Ordering::SeqCst);
+        let top: isize = inner.top.load(Ordering::Relaxed);
+
+        if top > bottom {
+            inner.bottom.store(bottom + 1, Ordering::Relaxed);
+            return None;
+        }
+
+        // SAFETY: `top <= bottom`, so the slot holds an element.
+        let data: MaybeUninit<T> = unsafe { (*buffer).read(bottom) };
+        if top == bottom {
+            // The last element: race the thieves for it through `top`.
+            let won: bool = inner.top.compare_exchange(top, top + 1, Ordering::SeqCst, Ordering::Relaxed).is_ok();
+            inner.bottom.store(bottom + 1, Ordering::Relaxed);
+            if !won {
+                return None;
+            }
+        }
+
+        // SAFETY: this thread claimed the element, and no other will.
+        return Some(unsafe { data.assume_init() });
+    }
+
+    /// Moves the live range into a buffer twice as large and retires the old
+    /// one.
+    ///
+    /// # Safety
+    /// Only the owner may call this, with `buffer` the current buffer.
+    unsafe fn grow(&self, buffer: *mut Buffer<T>, top: isize, bottom: isize) -> *mut Buffer<T> {
+        let grown: *mut Buffer<T> = Buffer::new(2 * (*buffer).capacity() as usize);
+        for index in top..bottom {
+            (*grown).write(index, (*buffer).read(index));
+        }
+
+        // SeqCst pairs with the SeqCst hazard store and reload in `protect`:
+        // either a thief's reload sees `grown`, or the scan below sees its
+        // hazard on `buffer`.
+        self.inner.buffer.store(grown, Ordering::SeqCst);
+        let guard: Guard = self.inner.domain.guard();
+        guard.retire(buffer);
+        // Grows are too rare to ever reach the scan threshold on their own.
+        guard.scan();
+        return grown;
+    }
+}
+
+/// A thief's end of the deque. It can be cloned and shared freely.
+pub struct Stealer<T> {
+    inner: Arc<Inner<T>>,
+}
+
+impl<T> Clone for Stealer<T> {
+    fn clone(&self) -> Self {
+        Stealer { inner: self.inner.clone() }
+    }
+}
+
+impl<T> Stealer<T> {
+    /// Takes the oldest element.
+    pub fn steal(&self) -> Steal<T> {
+        let inner: &Inner<T> = &self.inner;
+        let top: isize = inner.top.load(Ordering::Acquire);
+        fence(Ordering::SeqCst);
+        let bottom: isize = inner.bottom.load(Ordering::Acquire);
+
+        if top >= bottom {
+            return Steal::Empty;
+        }
+
+        // Any buffer installed after `bottom` was read still holds every
+        // position from `top` on.
+        let guard: Guard = inner.domain.guard();
+        let buffer: *mut Buffer<T> = guard.protect(0, &inner.buffer);
+        // SAFETY: the buffer is protected; the read is only trusted if the
+        // CAS below shows nobody else took `top` in the meantime.
+        let data: MaybeUninit<T> = unsafe { (*buffer).read(top) };
+
+        if inner.top.compare_exchange(top, top + 1, Ordering::SeqCst, Ordering::Relaxed).is_err() {
+            return Steal::Retry;
+        }
+        return Steal::Success(unsafe { data.assume_init() });
+    }
+
+    /// A snapshot that may be stale as soon as it returns.
+    pub fn is_empty(&self) -> bool {
+        let top: isize = self.inner.top.load(Ordering::Acquire);
+        let bottom: isize = self.inner.bottom.load(Ordering::Acquire);
+        return top >= bottom;
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::concurrent::drop_counter::Counted;
+    use std::collections::HashSet;
+    use std::sync::atomic::{AtomicBool, AtomicUsize};
+    use std::thread;
+
+    #[test]
+    fn test_owner_is_lifo_and_thief_fifo() {
+        let worker: Worker<i64> = Worker::new();
+        let stealer: Stealer<i64> = worker.stealer();
+        assert_eq!(worker.pop(), None);
+        assert_eq!(stealer.steal(), Steal::Empty);
+
+        for data in 1..=5 {
+            worker.push(data);
+        }
+        assert_eq!(worker.len(), 5);
+        assert_eq!(stealer.steal(), Steal::Success(1));
+        assert_eq!(stealer.clone().steal().success(), Some(2));
+        assert_eq!(worker.pop(), Some(5));
+        assert_eq!(worker.pop(), Some(4));
+        assert_eq!(stealer.steal(), Steal::Success(3));
+        assert_eq!(worker.pop(), None);
+        assert!(worker.is_empty() && stealer.is_empty());
+    }
+
+    #[test]
+    fn test_grows_and_keeps_order() {
+        let worker: Worker<usize> = Worker::new();
+        let stealer: Stealer<usize> = worker.stealer();
+
+        // Wrap the positions around the first buffer before it grows.
+        for data in 0..10 {
+            worker.push(data);
+            assert_eq!(stealer.steal(), Steal::Success(data));
+        }
+        for data in 0..100 {
+            worker.push(data);
+        }
+        assert_eq!(worker.capacity(), 128);
+
+        for data in 0..50 {
+            assert_eq!(stealer.steal(), Steal::Success(data));
+        }
+        for data in (50..100).rev() {
+            assert_eq!(worker.pop(), Some(data));
+        }
+        assert!(worker.is_empty());
+    }
+
+    #[test]
+    fn test_grow_frees_unprotected_buffers() {
+        let mut worker: Worker<usize> = Worker::new();
+        for data in 0..1000 {
+            worker.push(data);
+        }
+        assert_eq!(worker.capacity(), 1024);
+        assert_eq!(Arc::get_mut(&mut worker.inner).unwrap().domain.retired_len(), 0);
+    }
+
+    #[test]
+    fn test_thieves_and_owner_take_each_element_once() {
+        let worker: Worker<usize> = Worker::new();
+        let done: AtomicBool = AtomicBool::new(false);
+
+        let stolen: Vec<Vec<usize>> = thread::scope(|scope| {
+            let thieves: Vec<_> = (0..3)
+                .map(|_| {
+                    let (stealer, done) = (worker.stealer(), &done);
+                    scope.spawn(move || {
+                        let mut taken: Vec<usize> = Vec::new();
+                        while !done.load(Ordering::Acquire) || !stealer.is_empty() {
+                            if let Steal::Success(data) = stealer.steal() {
+                                taken.push(data);
+                            }
+                        }
+                        taken
+                    })
+                })
+                .collect();
+
+            let mut popped: Vec<usize> = Vec::new();
+            for data in 0..20_000 {
+                worker.push(data);
+                if data % 3 == 0 {
+                    popped.extend(worker.pop());
+                }
+            }
+            done.store(true, Ordering::Release);
+
+            let mut all: Vec<Vec<usize>> = thieves.into_iter().map(|thief| thief.join().unwrap()).collect();
+            all.push(popped);
+            all
+        });
+
+        let mut all: HashSet<usize> = HashSet::new();
+        for data in stolen.into_iter().flatten() {
+            assert!(all.insert(data), "{} taken twice", data);
+        }
+        while let Some(data) = worker.pop() {
+            assert!(all.insert(data), "{} taken twice", data);
+        }
+        assert_eq!(all.len(), 20_000);
+    }
+
+    #[test]
+    fn test_drops_every_element_once() {
+        let dropped: Arc<AtomicUsize> = Arc::new(AtomicUsize::new(0));
+        let worker: Worker<Counted> = Worker::new();
+        let stealer: Stealer<Counted> = worker.stealer();
+
+        for _ in 0..100 {
+            worker.push(Counted(dropped.clone()));
+        }
+        drop(stealer.steal());
+        drop(worker.pop());
+        assert_eq!(dropped.load(Ordering::SeqCst), 2);
+
+        drop(worker);
+        assert_eq!(dropped.load(Ordering::SeqCst), 2);
+        drop(stealer);
+        assert_eq!(dropped.load(Ordering::SeqCst), 100);
+    }
+}
@@ -0,0 +1,283 @@
+use std::cell::UnsafeCell;
+use std::ptr;
+use std::sync::atomic::{AtomicBool, AtomicPtr, Ordering};
+
+/// How many pointers one guard can protect at a time. The Michael–Scott
+/// queue needs two: the head and the node after it.
+pub const HAZARDS_PER_GUARD: usize = 2;
+
+/// A record scans the domain once it holds this many retired nodes.
+pub(crate) const SCAN_THRESHOLD: usize = 64;
+
+/// A node that has been unlinked but may still be read by another thread,
+/// with the function that frees it as the type it was allocated as.
+struct Retired {
+    pointer: *mut (),
+    free: unsafe fn(*mut ()),
+}
+
+/// # Safety
+/// `pointer` must have come from `Box::<T>::into_raw` and not been freed.
+unsafe fn free_box<T>(pointer: *mut ()) {
+    drop(Box::from_raw(pointer as *mut T));
+}
+
+/// One thread's slot in a domain. Records are pushed onto the domain's list
+/// and only freed with it; a thread claims a free one through `active` and
+/// gives it back when its guard drops. `retired` belongs to whichever thread
+/// holds the record, which is why the record is never shared while active.
+struct Record {
+    hazards: [AtomicPtr<()>; HAZARDS_PER_GUARD],
+    active: AtomicBool,
+    next: *mut Record,
+    retired: UnsafeCell<Vec<Retired>>,
+}
+
+/// Safe memory reclamation with hazard pointers, after Michael (2004).
+///
+/// Before a thread dereferences a node that others may unlink, it publishes
+/// the node's address in one of its hazard slots and then checks that the
+/// node is still reachable. A node that has been unlinked is retired rather
+/// than freed, and retired nodes are only freed once no hazard slot in the
+/// domain holds their address. Every record holds fewer than
+/// `SCAN_THRESHOLD` unfreed nodes after a scan, plus whatever the hazards
+/// pin, so memory stays bounded.
+///
+/// This also rules out ABA on compare-and-swap. A CAS on a pointer can only
+/// be fooled if the node it read was freed and its address reused for a new
+/// node in the meantime, and a node cannot be freed while a hazard slot
+/// protects it.
+pub struct Domain {
+    records: AtomicPtr<Record>,
+}
+
+impl Domain {
+    pub fn new() -> Self {
+        Domain { records: AtomicPtr::new(ptr::null_mut()) }
+    }
+
+    /// Claims an idle record, or adds a new one when every record is in use.
+    pub fn guard(&self) -> Guard<'_> {
+        let mut current: *mut Record = self.records.load(Ordering::Acquire);
+        while !current.is_null() {
+            // SAFETY: records are only freed when the domain drops, which
+            // can't happen while `self` is borrowed.
+            let record: &Record = unsafe { &*current };
+            if !record.active.load(Ordering::Relaxed) && record.active.compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed).is_ok() {
+                return Guard { domain: self, record };
+            }
+            current = record.next;
+        }
+
+        let record: *mut Record = Box::into_raw(Box::new(Record {
+            hazards: std::array::from_fn(|_| AtomicPtr::new(ptr::null_mut())),
+            active: AtomicBool::new(true),
+            next: ptr::null_mut(),
+            retired: UnsafeCell::new(Vec::new()),
+        }));
+        let mut head: *mut Record = self.records.load(Ordering::Acquire);
+        loop {
+            // SAFETY: the record is not published yet, so this thread owns it.
+            unsafe { (*record).next = head };
+            match self.records.compare_exchange_weak(head, record, Ordering::AcqRel, Ordering::Acquire) {
+                Ok(_) => break,
+                Err(current) => head = current,
+            }
+        }
+
+        // SAFETY: as above, the record lives as long as the domain.
+        return Guard { domain: self, record: unsafe { &*record } };
+    }
+
+    /// Every address currently protected by any thread, sorted.
+    fn protected(&self) -> Vec<*mut ()> {
+        let mut protected: Vec<*mut ()> = Vec::new();
+        let mut current: *mut Record = self.records.load(Ordering::Acquire);
+
+        while !current.is_null() {
+            // SAFETY: see `guard`.
+            let record: &Record = unsafe { &*current };
+            for hazard in &record.hazards {
+                let pointer: *mut () = hazard.load(Ordering::SeqCst);
+                if !pointer.is_null() {
+                    protected.push(pointer);
+                }
+            }
+            current = record.next;
+        }
+
+        protected.sort_unstable();
+        return protected;
+    }
+
+    /// Retired nodes not freed yet, summed over every record. It takes
+    /// `&mut self` because it reads retired lists that belong to guards.
+    pub fn retired_len(&mut self) -> usize {
+        let mut length: usize = 0;
+        let mut current: *mut Record = *self.records.get_mut();
+
+        while !current.is_null() {
+            // SAFETY: see `guard`; no guard can be alive during `&mut self`.
+            let record: &Record = unsafe { &*current };
+            length += unsafe { (*record.retired.get()).len() };
+            current = record.next;
+        }
+
+        return length;
+    }
+}
+
+/// With `&mut self` no guard can be alive, so nothing is protected and
+/// every retired node and record can go.
+impl Drop for Domain {
+    fn drop(&mut self) {
+        let mut current: *mut Record = *self.records.get_mut();
+
+        while !current.is_null() {
+            // SAFETY: records came from `Box::into_raw` and each is visited
+            // once.
+            let record: Box<Record> = unsafe { Box::from_raw(current) };
+            for retired in record.retired.into_inner() {
+                // SAFETY: each retired node was retired exactly once.
+                unsafe { (retired.free)(retired.pointer) };
+            }
+            current = record.next;
+        }
+    }
+}
+
+/// A claimed record. It is tied to the thread that claimed it, since the
+/// borrowed `Record` is not `Sync`, and it releases the record on drop.
+pub struct Guard<'a> {
+    domain: &'a Domain,
+    record: &'a Record,
+}
+
+impl Guard<'_> {
+    /// Loads `source` and keeps the node it points to from being freed
+    /// until `slot` is cleared or reused. The hazard is published before the
+    /// second load, so if `source` still holds the same pointer then, the
+    /// node was reachable after it was protected.
+    pub fn protect<T>(&self, slot: usize, source: &AtomicPtr<T>) -> *mut T {
+        let hazard: &AtomicPtr<()> = &self.record.hazards[slot];
+        let mut pointer: *mut T = source.load(Ordering::SeqCst);
+
+        loop {
+            hazard.store(pointer as *mut (), Ordering::SeqCst);
+            let current: *mut T = source.load(Ordering::SeqCst);
+            if current == pointer {
+                return pointer;
+            }
+            pointer = current;
+        }
+    }
+
+    pub fn clear(&self, slot: usize) {
+        self.record.hazards[slot].store(ptr::null_mut(), Ordering::Release);
+    }
+
+    /// Frees `pointer` once no thread protects it.
+    ///
+    /// # Safety
+    /// `pointer` must come from `Box::<T>::into_raw`, must already be
+    /// unreachable from the shared structure, and must be retired only once.
+    pub unsafe fn retire<T>(&self, pointer: *mut T) {
+        let retired: &mut Vec<Retired> = &mut *self.record.retired.get();
+        retired.push(Retired { pointer: pointer as *mut (), free: free_box::<T> });
+
+        if retired.len() >= SCAN_THRESHOLD {
+            self.scan();
+        }
+    }
+
+    /// Frees every node this guard's record has retired that no thread
+    /// protects. `retire` calls it once enough nodes pile up; callers that
+    /// retire rarely but want memory back promptly can call it directly.
+    pub fn scan(&self) {
+        let protected: Vec<*mut ()> = self.domain.protected();
+        // SAFETY: this guard holds the record, so no other thread touches
+        // its retired list.
+        let retired: &mut Vec<Retired> = unsafe { &mut *self.record.retired.get() };
+
+        retired.retain(|node| {
+            if protected.binary_search(&node.pointer).is_ok() {
+                return true;
+            }
+            // SAFETY: the node is unreachable and no hazard protects it, so
+            // no thread can read it any more.
+            unsafe { (node.free)(node.pointer) };
+            false
+        });
+    }
+}
+
+impl Drop for Guard<'_> {
+    fn drop(&mut self) {
+        for slot in 0..HAZARDS_PER_GUARD {
+            self.clear(slot);
+        }
+        self.record.active.store(false, Ordering::Release);
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::concurrent::drop_counter::Counted;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_records_are_reused() {
+        let domain: Domain = Domain::new();
+        let first: *const Record = domain.guard().record;
+        let second: *const Record = domain.guard().record;
+        assert_eq!(first, second);
+
+        let held: Guard = domain.guard();
+        let other: Guard = domain.guard();
+        assert_ne!(held.record as *const Record, other.record as *const Record);
+    }
+
+    #[test]
+    fn test_protected_nodes_survive_scans() {
+        let freed: Arc<AtomicUsize> = Arc::new(AtomicUsize::new(0));
+        let domain: Domain = Domain::new();
+        let shared: AtomicPtr<Counted> = AtomicPtr::new(Box::into_raw(Box::new(Counted(freed.clone()))));
+
+        let reader: Guard = domain.guard();
+        let pinned: *mut Counted = reader.protect(0, &shared);
+
+        let writer: Guard = domain.guard();
+        shared.store(ptr::null_mut(), Ordering::SeqCst);
+        unsafe { writer.retire(pinned) };
+        for _ in 0..SCAN_THRESHOLD - 1 {
+            unsafe { writer.retire(Box::into_raw(Box::new(Counted(freed.clone())))) };
+        }
+        assert_eq!(freed.load(Ordering::SeqCst), SCAN_THRESHOLD - 1);
+
+        reader.clear(0);
+        for _ in 0..SCAN_THRESHOLD - 1 {
+            unsafe { writer.retire(Box::into_raw(Box::new(Counted(freed.clone())))) };
+        }
+        assert_eq!(freed.load(Ordering::SeqCst), 2 * SCAN_THRESHOLD - 1);
+    }
+
+    #[test]
+    fn test_drop_frees_everything_retired() {
+        let freed: Arc<AtomicUsize> = Arc::new(AtomicUsize::new(0));
+        let mut domain: Domain = Domain::new();
+        {
+            let guard: Guard = domain.guard();
+            for _ in 0..10 {
+                unsafe { guard.retire(Box::into_raw(Box::new(Counted(freed.clone())))) };
+            }
+        }
+        assert_eq!(freed.load(Ordering::SeqCst), 0);
+        assert_eq!(domain.retired_len(), 10);
+        drop(domain);
+        assert_eq!(freed.load(Ordering::SeqCst), 10);
+    }
+}
@@ -0,0 +1,125 @@
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// One finished call on a shared structure, stamped with a logical clock
+/// when it was invoked and when it returned. The operation holds both the
+/// arguments and the result that was observed.
+pub struct Event<O> {
+    pub operation: O,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Collects the events of several threads against one clock.
+pub struct Recorder<O> {
+    clock: AtomicUsize,
+    events: Mutex<Vec<Event<O>>>,
+}
+
+impl<O> Recorder<O> {
+    pub fn new() -> Self {
+        Recorder { clock: AtomicUsize::new(0), events: Mutex::new(Vec::new()) }
+    }
+
+    pub fn record<F: FnOnce() -> O>(&self, call: F) {
+        let start: usize = self.clock.fetch_add(1, Ordering::SeqCst);
+        let operation: O = call();
+        let end: usize = self.clock.fetch_add(1, Ordering::SeqCst);
+        self.events.lock().unwrap().push(Event { operation, start, end });
+    }
+
+    pub fn into_events(self) -> Vec<Event<O>> {
+        self.events.into_inner().unwrap()
+    }
+}
+
+/// Whether the history can be explained by some sequential order that
+/// respects real time: an event that returned before another was invoked
+/// must come first. `step` applies one operation to the sequential model
+/// and says whether the model would have produced the observed result.
+///
+/// This is Wing and Gong's search with Lowe's memoisation: a set of finished
+/// events and a model state that already failed once is not explored again,
+/// which keeps histories from many threads tractable.
+pub fn is_linearizable<S: Clone + Eq + Hash, O, F: Fn(&mut S, &O) -> bool>(events: &[Event<O>], initial: S, step: F) -> bool {
+    let mut done: Vec<bool> = vec![false; events.len()];
+    let mut failed: HashSet<(Vec<bool>, S)> = HashSet::new();
+    return search(events, &mut done, events.len(), &initial, &step, &mut failed);
+}
+
+fn search<S: Clone + Eq + Hash, O, F: Fn(&mut S, &O) -> bool>(
+    events: &[Event<O>],
+    done: &mut [bool],
+    remaining: usize,
+    state: &S,
+    step: &F,
+    failed: &mut HashSet<(Vec<bool>, S)>,
+) -> bool {
+    if remaining == 0 {
+        return true;
+    }
+    if failed.contains(&(done.to_vec(), state.clone())) {
+        return false;
+    }
+
+    let horizon: usize = (0..events.len()).filter(|&i| !done[i]).map(|i| events[i].end).min().unwrap_or(usize::MAX);
+    for i in 0..events.len() {
+        if done[i] || events[i].start > horizon {
+            continue;
+        }
+
+        let mut next: S = state.clone();
+        if step(&mut next, &events[i].operation) {
+            done[i] = true;
+            if search(events, done, remaining - 1, &next, step, failed) {
+                return true;
+            }
+            done[i] = false;
+        }
+    }
+
+    failed.insert((done.to_vec(), state.clone()));
+    return false;
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Copy)]
+    enum Register {
+        Write(i64),
+        Read(i64),
+    }
+
+    fn step(state: &mut i64, operation: &Register) -> bool {
+        match *operation {
+            Register::Write(value) => {
+                *state = value;
+                true
+            }
+            Register::Read(value) => *state == value,
+        }
+    }
+
+    #[test]
+    fn test_overlapping_calls_may_reorder() {
+        let events: Vec<Event<Register>> = vec![
+            Event { operation: Register::Read(1), start: 0, end: 3 },
+            Event { operation: Register::Write(1), start: 1, end: 2 },
+        ];
+        assert!(is_linearizable(&events, 0, step));
+    }
+
+    #[test]
+    fn test_real_time_order_is_kept() {
+        let events: Vec<Event<Register>> = vec![
+            Event { operation: Register::Read(1), start: 0, end: 1 },
+            Event { operation: Register::Write(1), start: 2, end: 3 },
+        ];
+        assert!(!is_linearizable(&events, 0, step));
+    }
+}
@@ -0,0 +1,12 @@
+#![allow(clippy::needless_return, clippy::new_without_default)]
+
+pub mod hazard_pointer;
+pub mod treiber_stack;
+pub mod ms_queue;
+pub mod chase_lev_deque;
+pub mod work_stealing_pool;
+pub mod parallel_merge_sort;
+#[cfg(test)]
+pub(crate) mod linearizability;
+#[cfg(test)]
+pub(crate) mod drop_counter;
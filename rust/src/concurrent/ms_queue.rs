@@ -0,0 +1,271 @@
+use std::marker::PhantomData;
+use std::mem::MaybeUninit;
+use std::ptr;
+use std::sync::atomic::{AtomicPtr, Ordering};
+
+use super::hazard_pointer::{Domain, Guard};
+
+/// The node `head` points to is a dummy whose data has already been taken,
+/// or was never set for the first one, so `data` is only initialised in the
+/// nodes after it.
+struct Node<T> {
+    data: MaybeUninit<T>,
+    next: AtomicPtr<Node<T>>,
+}
+
+impl<T> Node<T> {
+    fn new(data: MaybeUninit<T>) -> *mut Node<T> {
+        Box::into_raw(Box::new(Node { data, next: AtomicPtr::new(ptr::null_mut()) }))
+    }
+}
+
+/// Michael and Scott's lock-free queue: a singly linked list with a dummy
+/// node in front, so `push` only touches the tail and `pop` only the head.
+/// `push` links its node in two steps, the `next` of the last node and then
+/// `tail`; any thread that finds `tail` lagging swings it forward first, so
+/// a stalled push never blocks the others.
+///
+/// `pop` moves `head` to the node after it, whose data it then takes. Both
+/// the old head and that node are protected with hazard pointers while it
+/// reads them, and the old head is retired once it is unlinked.
+pub struct MsQueue<T> {
+    head: AtomicPtr<Node<T>>,
+    tail: AtomicPtr<Node<T>>,
+    domain: Domain,
+    marker: PhantomData<T>,
+}
+
+// SAFETY: see `TreiberStack`.
+unsafe impl<T: Send> Send for MsQueue<T> {}
+unsafe impl<T: Send> Sync for MsQueue<T> {}
+
+impl<T> MsQueue<T> {
+    pub fn new() -> Self {
+        let dummy: *mut Node<T> = Node::new(MaybeUninit::uninit());
+        MsQueue { head: AtomicPtr::new(dummy), tail: AtomicPtr::new(dummy), domain: Domain::new(), marker: PhantomData }
+    }
+
+    pub fn push(&self, data: T) {
+        let node: *mut Node<T> = Node::new(MaybeUninit::new(data));
+        let guard: Guard = self.domain.guard();
+
+        loop {
+            let tail: *mut Node<T> = guard.protect(0, &self.tail);
+            // SAFETY: `tail` is protected, and the dummy in front means it
+            // is never null.
+            let next: *mut Node<T> = unsafe { (*tail).next.load(Ordering::Acquire) };
+
+            if !next.is_null() {
+                let _ = self.tail.compare_exchange(tail, next, Ordering::Release, Ordering::Relaxed);
+                continue;
+            }
+            // A node that has already been dequeued has a `next`, so this
+            // can only succeed on the last node in the list.
+            if unsafe { (*tail).next.compare_exchange(ptr::null_mut(), node, Ordering::Release, Ordering::Relaxed) }.is_ok() {
+                let _ = self.tail.compare_exchange(tail, node, Ordering::Release, Ordering::Relaxed);
+                return;
+            }
+        }
+    }
+
+    pub fn pop(&self) -> Option<T> {
+        let guard: Guard = self.domain.guard();
+
+        loop {
+            let head: *mut Node<T> = guard.protect(0, &self.head);
+            let tail: *mut Node<T> = self.tail.load(Ordering::Acquire);
+            // SAFETY: `head` is protected and never null.
+            let next: *mut Node<T> = guard.protect(1, unsafe { &(*head).next });
+
+            // `next` was only reachable when it was protected if `head` was.
+            if self.head.load(Ordering::Acquire) != head {
+                continue;
+            }
+            if next.is_null() {
+                return None;
+            }
+            if head == tail {
+                let _ = self.tail.compare_exchange(tail, next, Ordering::Release, Ordering::Relaxed);
+                continue;
+            }
+
+            if self.head.compare_exchange(head, next, Ordering::AcqRel, Ordering::Relaxed).is_ok() {
+                // SAFETY: the successful CAS made `next` the new dummy, so
+                // this is the only thread that takes its data, and it is
+                // still protected.
+                let data: T = unsafe { ptr::read((*next).data.as_ptr()) };
+                guard.clear(0);
+                guard.clear(1);
+                unsafe { guard.retire(head) };
+                return Some(data);
+            }
+        }
+    }
+
+    /// A snapshot that may be stale as soon as it returns.
+    pub fn is_empty(&self) -> bool {
+        let guard: Guard = self.domain.guard();
+        let head: *mut Node<T> = guard.protect(0, &self.head);
+        // SAFETY: `head` is protected and never null.
+        unsafe { (*head).next.load(Ordering::Acquire) }.is_null()
+    }
+}
+
+/// Frees the dummy without touching its data, then drops the data of every
+/// node behind it.
+impl<T> Drop for MsQueue<T> {
+    fn drop(&mut self) {
+        // SAFETY: `&mut self` means no other thread can reach the list.
+        let dummy: Box<Node<T>> = unsafe { Box::from_raw(*self.head.get_mut()) };
+        let mut current: *mut Node<T> = dummy.next.load(Ordering::Relaxed);
+        drop(dummy);
+
+        while !current.is_null() {
+            let mut node: Box<Node<T>> = unsafe { Box::from_raw(current) };
+            current = *node.next.get_mut();
+            unsafe { node.data.assume_init_drop() };
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::concurrent::drop_counter::Counted;
+    use crate::concurrent::hazard_pointer::{HAZARDS_PER_GUARD, SCAN_THRESHOLD};
+    use crate::concurrent::linearizability::{is_linearizable, Event, Recorder};
+    use std::collections::VecDeque;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::{Arc, Barrier};
+    use std::thread;
+
+    #[derive(Clone, Copy, Debug)]
+    enum Call {
+        Push(i64),
+        Pop(Option<i64>),
+    }
+
+    fn step(model: &mut VecDeque<i64>, call: &Call) -> bool {
+        match *call {
+            Call::Push(data) => {
+                model.push_back(data);
+                true
+            }
+            Call::Pop(result) => model.pop_front() == result,
+        }
+    }
+
+    #[test]
+    fn test_single_thread_order() {
+        let queue: MsQueue<i64> = MsQueue::new();
+        assert!(queue.is_empty());
+        assert_eq!(queue.pop(), None);
+
+        for data in 1..=5 {
+            queue.push(data);
+        }
+        assert!(!queue.is_empty());
+        for data in 1..=5 {
+            assert_eq!(queue.pop(), Some(data));
+        }
+        assert_eq!(queue.pop(), None);
+
+        queue.push(6);
+        assert_eq!(queue.pop(), Some(6));
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_linearizable_histories() {
+        for round in 0..200 {
+            let queue: MsQueue<i64> = MsQueue::new();
+            queue.push(-1);
+            let recorder: Recorder<Call> = Recorder::new();
+            let barrier: Barrier = Barrier::new(8);
+
+            thread::scope(|scope| {
+                for thread in 0..8 {
+                    let (queue, recorder, barrier) = (&queue, &recorder, &barrier);
+                    scope.spawn(move || {
+                        barrier.wait();
+                        for step in 0..4 {
+                            if (round + thread + step) % 3 == 0 {
+                                recorder.record(|| Call::Pop(queue.pop()));
+                            } else {
+                                let data: i64 = (thread * 10 + step) as i64;
+                                recorder.record(|| {
+                                    queue.push(data);
+                                    Call::Push(data)
+                                });
+                            }
+                        }
+                    });
+                }
+            });
+
+            let events: Vec<Event<Call>> = recorder.into_events();
+            assert!(is_linearizable(&events, VecDeque::from(vec![-1]), step), "round {}: {:?}", round, events.iter().map(|event| event.operation).collect::<Vec<Call>>());
+        }
+    }
+
+    #[test]
+    fn test_keeps_each_producers_order() {
+        let queue: MsQueue<(usize, usize)> = MsQueue::new();
+        let received: AtomicUsize = AtomicUsize::new(0);
+
+        thread::scope(|scope| {
+            for producer in 0..4 {
+                let queue: &MsQueue<(usize, usize)> = &queue;
+                scope.spawn(move || {
+                    for sequence in 0..5000 {
+                        queue.push((producer, sequence));
+                    }
+                });
+            }
+            for _ in 0..4 {
+                scope.spawn(|| {
+                    let mut last: [Option<usize>; 4] = [None; 4];
+                    while received.load(Ordering::Relaxed) < 20_000 {
+                        if let Some((producer, sequence)) = queue.pop() {
+                            assert!(last[producer].is_none_or(|last| last < sequence), "{} after {:?}", sequence, last[producer]);
+                            last[producer] = Some(sequence);
+                            received.fetch_add(1, Ordering::Relaxed);
+                        }
+                    }
+                });
+            }
+        });
+
+        assert_eq!(received.into_inner(), 20_000);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_drops_every_element_once() {
+        let dropped: Arc<AtomicUsize> = Arc::new(AtomicUsize::new(0));
+        let mut queue: MsQueue<Counted> = MsQueue::new();
+
+        thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| {
+                    for round in 0..1000 {
+                        queue.push(Counted(dropped.clone()));
+                        if round % 3 != 0 {
+                            drop(queue.pop());
+                        }
+                    }
+                });
+            }
+        });
+
+        let remaining: usize = 4000 - dropped.load(Ordering::SeqCst);
+        assert!(remaining >= 4 * 333);
+        // Each of the four records is left with fewer than SCAN_THRESHOLD
+        // nodes after a scan, plus any the four threads' hazards pinned.
+        assert!(queue.domain.retired_len() <= 4 * (SCAN_THRESHOLD + 4 * HAZARDS_PER_GUARD));
+        drop(queue);
+        assert_eq!(dropped.load(Ordering::SeqCst), 4000);
+    }
+}
@@ -0,0 +1,154 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use super::work_stealing_pool::{Context, Pool};
+use crate::linked_lists::xdllwt_raw::LinkedList;
+
+/// What to do with a sorted list once it is ready.
+type Done<'env, T> = Box<dyn FnOnce(&Context<'_, 'env>, LinkedList<T>) + Send + 'env>;
+
+/// The two sorted halves of a split, and what to do with them once both
+/// have arrived. Whichever half finishes second does the merge.
+struct Join<'env, T> {
+    left: Option<LinkedList<T>>,
+    right: Option<LinkedList<T>>,
+    done: Option<Done<'env, T>>,
+}
+
+/// Sorts `list` on `pool`, stably. The list is halved into tasks until the
+/// pieces are at most `segment` long; each segment is merge sorted on one
+/// thread, and the halves are merged back as soon as both are sorted, on
+/// whichever thread finished last.
+pub fn merge_sort<T: Ord + Send>(pool: &Pool, list: LinkedList<T>, segment: usize) -> LinkedList<T> {
+    let sorted: Mutex<Option<LinkedList<T>>> = Mutex::new(None);
+    let segment: usize = segment.max(1);
+
+    pool.run(|context| {
+        let done: Done<'_, T> = Box::new(|_, list| *sorted.lock().unwrap() = Some(list));
+        sort(context, list, segment, done);
+    });
+
+    return sorted.into_inner().unwrap().unwrap_or_else(LinkedList::new);
+}
+
+fn sort<'env, T: Ord + Send + 'env>(context: &Context<'_, 'env>, mut list: LinkedList<T>, segment: usize, done: Done<'env, T>) {
+    if list.len() <= segment {
+        done(context, sort_sequential(list));
+        return;
+    }
+
+    let right: LinkedList<T> = split_half(&mut list);
+    let join: Arc<Mutex<Join<'env, T>>> = Arc::new(Mutex::new(Join { left: None, right: None, done: Some(done) }));
+    let other: Arc<Mutex<Join<'env, T>>> = join.clone();
+
+    context.spawn(move |context| sort(context, list, segment, Box::new(move |context, left| arrive(context, &join, Some(left), None))));
+    context.spawn(move |context| sort(context, right, segment, Box::new(move |context, right| arrive(context, &other, None, Some(right)))));
+}
+
+fn arrive<'env, T: Ord>(context: &Context<'_, 'env>, join: &Mutex<Join<'env, T>>, left: Option<LinkedList<T>>, right: Option<LinkedList<T>>) {
+    let mut state: MutexGuard<'_, Join<'env, T>> = join.lock().unwrap();
+    state.left = state.left.take().or(left);
+    state.right = state.right.take().or(right);
+
+    if state.left.is_some() && state.right.is_some() {
+        let (left, right): (LinkedList<T>, LinkedList<T>) = (state.left.take().unwrap(), state.right.take().unwrap());
+        let done: Done<'env, T> = state.done.take().unwrap();
+        drop(state);
+        done(context, merge(left, right));
+    }
+}
+
+fn sort_sequential<T: Ord>(mut list: LinkedList<T>) -> LinkedList<T> {
+    if list.len() <= 1 {
+        return list;
+    }
+
+    let right: LinkedList<T> = split_half(&mut list);
+    return merge(sort_sequential(list), sort_sequential(right));
+}
+
+/// Cuts off and returns the back half of `list`; the list keeps the front
+/// half, rounded down.
+fn split_half<T>(list: &mut LinkedList<T>) -> LinkedList<T> {
+    list.split_off(list.len() / 2).unwrap_or_else(|_| LinkedList::new())
+}
+
+/// Relinks the nodes of both lists into one, so no element is moved or
+/// reallocated. Takes from `left` on ties, which is what keeps the sort
+/// stable.
+fn merge<T: Ord>(mut left: LinkedList<T>, mut right: LinkedList<T>) -> LinkedList<T> {
+    let mut merged: LinkedList<T> = LinkedList::new();
+
+    while let (Some(first), Some(second)) = (left.front(), right.front()) {
+        let from_right: bool = second < first;
+        move_front(if from_right { &mut right } else { &mut left }, &mut merged);
+    }
+
+    merged.append(&mut left);
+    merged.append(&mut right);
+    return merged;
+}
+
+/// Moves the front node of a non-empty `source` onto the end of `target`.
+fn move_front<T>(source: &mut LinkedList<T>, target: &mut LinkedList<T>) {
+    let rest: LinkedList<T> = source.split_off(1).unwrap_or_else(|_| LinkedList::new());
+    target.append(source);
+    *source = rest;
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shuffled(count: i64) -> Vec<i64> {
+        (0..count).map(|i| (i * 7919) % count).collect()
+    }
+
+    #[test]
+    fn test_sorts_like_vec_sort() {
+        let pool: Pool = Pool::new(4);
+
+        for (count, segment) in [(0, 8), (1, 8), (2, 1), (1000, 1), (5000, 64), (4099, 5000)] {
+            let data_vec: Vec<i64> = shuffled(count).into_iter().map(|data| data % 97).collect();
+            let sorted: LinkedList<i64> = merge_sort(&pool, LinkedList::from(data_vec.clone()), segment);
+
+            let mut expected: Vec<i64> = data_vec;
+            expected.sort();
+            assert_eq!(sorted.len(), expected.len());
+            assert_eq!(sorted, LinkedList::from(expected));
+        }
+    }
+
+    #[derive(Debug)]
+    struct Keyed(i64, usize);
+
+    impl PartialEq for Keyed {
+        fn eq(&self, other: &Self) -> bool {
+            self.0 == other.0
+        }
+    }
+
+    impl Eq for Keyed {}
+
+    impl PartialOrd for Keyed {
+        fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Keyed {
+        fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+            self.0.cmp(&other.0)
+        }
+    }
+
+    #[test]
+    fn test_is_stable() {
+        let data_vec: Vec<Keyed> = (0..3000).map(|index| Keyed((index as i64 * 31) % 10, index)).collect();
+        let sorted: LinkedList<Keyed> = merge_sort(&Pool::new(3), LinkedList::from(data_vec), 16);
+
+        let keys: Vec<(i64, usize)> = sorted.iter().map(|keyed| (keyed.0, keyed.1)).collect();
+        assert!(keys.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+}
@@ -0,0 +1,240 @@
+use std::marker::PhantomData;
+use std::mem::ManuallyDrop;
+use std::ptr;
+use std::sync::atomic::{AtomicPtr, Ordering};
+
+use super::hazard_pointer::{Domain, Guard};
+
+/// `data` is moved out by whichever `pop` unlinks the node, so the node
+/// itself must not drop it again when it is freed.
+struct Node<T> {
+    data: ManuallyDrop<T>,
+    next: *mut Node<T>,
+}
+
+/// Treiber's lock-free stack: a singly linked list whose head is swapped
+/// with compare-and-swap. A `push` or `pop` only retries when another call
+/// changed the head in between, so some thread always makes progress.
+///
+/// `pop` reads the head's `next` before swapping it in, so the head has to
+/// stay allocated even if a concurrent `pop` unlinks it first. It is
+/// protected with a hazard pointer, which also keeps its address from being
+/// reused while the CAS is pending and so rules out ABA.
+pub struct TreiberStack<T> {
+    head: AtomicPtr<Node<T>>,
+    domain: Domain,
+    marker: PhantomData<T>,
+}
+
+// SAFETY: elements move between threads but are never shared by reference,
+// so sending them is all that is required.
+unsafe impl<T: Send> Send for TreiberStack<T> {}
+unsafe impl<T: Send> Sync for TreiberStack<T> {}
+
+impl<T> TreiberStack<T> {
+    pub fn new() -> Self {
+        TreiberStack { head: AtomicPtr::new(ptr::null_mut()), domain: Domain::new(), marker: PhantomData }
+    }
+
+    pub fn push(&self, data: T) {
+        let node: *mut Node<T> = Box::into_raw(Box::new(Node { data: ManuallyDrop::new(data), next: ptr::null_mut() }));
+        let mut head: *mut Node<T> = self.head.load(Ordering::Relaxed);
+
+        loop {
+            // SAFETY: the node isn't published until the CAS succeeds.
+            unsafe { (*node).next = head };
+            match self.head.compare_exchange_weak(head, node, Ordering::Release, Ordering::Relaxed) {
+                Ok(_) => return,
+                Err(current) => head = current,
+            }
+        }
+    }
+
+    pub fn pop(&self) -> Option<T> {
+        let guard: Guard = self.domain.guard();
+
+        loop {
+            let head: *mut Node<T> = guard.protect(0, &self.head);
+            if head.is_null() {
+                return None;
+            }
+
+            // SAFETY: `head` is protected, so it hasn't been freed, and
+            // `next` never changes once the node is published.
+            let next: *mut Node<T> = unsafe { (*head).next };
+            if self.head.compare_exchange(head, next, Ordering::AcqRel, Ordering::Relaxed).is_ok() {
+                // SAFETY: the successful CAS unlinked `head`, so this is the
+                // only thread that takes its data or retires it.
+                let data: T = unsafe { ManuallyDrop::into_inner(ptr::read(&(*head).data)) };
+                guard.clear(0);
+                unsafe { guard.retire(head) };
+                return Some(data);
+            }
+        }
+    }
+
+    /// A snapshot that may be stale as soon as it returns.
+    pub fn is_empty(&self) -> bool {
+        self.head.load(Ordering::Acquire).is_null()
+    }
+}
+
+/// The nodes still linked own their data; the domain frees the retired
+/// ones, whose data has already been popped.
+impl<T> Drop for TreiberStack<T> {
+    fn drop(&mut self) {
+        let mut current: *mut Node<T> = *self.head.get_mut();
+
+        while !current.is_null() {
+            // SAFETY: `&mut self` means no other thread can reach the list.
+            let mut node: Box<Node<T>> = unsafe { Box::from_raw(current) };
+            current = node.next;
+            unsafe { ManuallyDrop::drop(&mut node.data) };
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::concurrent::drop_counter::Counted;
+    use crate::concurrent::hazard_pointer::{HAZARDS_PER_GUARD, SCAN_THRESHOLD};
+    use crate::concurrent::linearizability::{is_linearizable, Event, Recorder};
+    use std::collections::HashSet;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::{Arc, Barrier};
+    use std::thread;
+
+    #[derive(Clone, Copy, Debug)]
+    enum Call {
+        Push(i64),
+        Pop(Option<i64>),
+    }
+
+    fn step(model: &mut Vec<i64>, call: &Call) -> bool {
+        match *call {
+            Call::Push(data) => {
+                model.push(data);
+                true
+            }
+            Call::Pop(result) => model.pop() == result,
+        }
+    }
+
+    #[test]
+    fn test_single_thread_order() {
+        let stack: TreiberStack<i64> = TreiberStack::new();
+        assert!(stack.is_empty());
+        assert_eq!(stack.pop(), None);
+
+        for data in 1..=5 {
+            stack.push(data);
+        }
+        for data in (1..=5).rev() {
+            assert_eq!(stack.pop(), Some(data));
+        }
+        assert_eq!(stack.pop(), None);
+        assert!(stack.is_empty());
+    }
+
+    #[test]
+    fn test_linearizable_histories() {
+        for round in 0..200 {
+            let stack: TreiberStack<i64> = TreiberStack::new();
+            stack.push(-1);
+            let recorder: Recorder<Call> = Recorder::new();
+            let barrier: Barrier = Barrier::new(8);
+
+            thread::scope(|scope| {
+                for thread in 0..8 {
+                    let (stack, recorder, barrier) = (&stack, &recorder, &barrier);
+                    scope.spawn(move || {
+                        barrier.wait();
+                        for step in 0..4 {
+                            if (round + thread + step) % 3 == 0 {
+                                recorder.record(|| Call::Pop(stack.pop()));
+                            } else {
+                                let data: i64 = (thread * 10 + step) as i64;
+                                recorder.record(|| {
+                                    stack.push(data);
+                                    Call::Push(data)
+                                });
+                            }
+                        }
+                    });
+                }
+            });
+
+            let events: Vec<Event<Call>> = recorder.into_events();
+            assert!(is_linearizable(&events, vec![-1], step), "round {}: {:?}", round, events.iter().map(|event| event.operation).collect::<Vec<Call>>());
+        }
+    }
+
+    #[test]
+    fn test_many_threads_conserve_elements() {
+        let stack: TreiberStack<usize> = TreiberStack::new();
+        let popped: AtomicUsize = AtomicUsize::new(0);
+        let seen: Vec<Vec<usize>> = thread::scope(|scope| {
+            let workers: Vec<_> = (0..8)
+                .map(|thread| {
+                    let (stack, popped) = (&stack, &popped);
+                    scope.spawn(move || {
+                        let mut seen: Vec<usize> = Vec::new();
+                        for data in 0..5000 {
+                            stack.push(thread * 5000 + data);
+                            if data % 2 == 1 {
+                                for _ in 0..2 {
+                                    if let Some(data) = stack.pop() {
+                                        seen.push(data);
+                                        popped.fetch_add(1, Ordering::Relaxed);
+                                    }
+                                }
+                            }
+                        }
+                        seen
+                    })
+                })
+                .collect();
+            workers.into_iter().map(|worker| worker.join().unwrap()).collect()
+        });
+
+        let mut all: HashSet<usize> = HashSet::new();
+        for data in seen.into_iter().flatten() {
+            assert!(all.insert(data), "{} popped twice", data);
+        }
+        while let Some(data) = stack.pop() {
+            assert!(all.insert(data), "{} popped twice", data);
+        }
+        assert_eq!(all.len(), 40_000);
+        assert!(popped.into_inner() > 0);
+    }
+
+    #[test]
+    fn test_drops_every_element_once() {
+        let dropped: Arc<AtomicUsize> = Arc::new(AtomicUsize::new(0));
+        let mut stack: TreiberStack<Counted> = TreiberStack::new();
+
+        thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| {
+                    for round in 0..1000 {
+                        stack.push(Counted(dropped.clone()));
+                        if round % 3 != 0 {
+                            drop(stack.pop());
+                        }
+                    }
+                });
+            }
+        });
+
+        let remaining: usize = 4000 - dropped.load(Ordering::SeqCst);
+        assert!(remaining >= 4 * 333);
+        // Each of the four records is left with fewer than SCAN_THRESHOLD
+        // nodes after a scan, plus any the four threads' hazards pinned.
+        assert!(stack.domain.retired_len() <= 4 * (SCAN_THRESHOLD + 4 * HAZARDS_PER_GUARD));
+        drop(stack);
+        assert_eq!(dropped.load(Ordering::SeqCst), 4000);
+    }
+}
@@ -0,0 +1,174 @@
+use std::panic;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use super::chase_lev_deque::{Steal, Stealer, Worker};
+
+type Task<'env> = Box<dyn FnOnce(&Context<'_, 'env>) + Send + 'env>;
+
+/// What a running task sees of the thread running it.
+pub struct Context<'a, 'env> {
+    worker: &'a Worker<Task<'env>>,
+    pending: &'a AtomicUsize,
+    index: usize,
+}
+
+impl<'env> Context<'_, 'env> {
+    /// Queues `task` on this thread's own deque, where it runs next unless
+    /// an idle thread steals it first.
+    pub fn spawn<F: FnOnce(&Context<'_, 'env>) + Send + 'env>(&self, task: F) {
+        self.pending.fetch_add(1, Ordering::Relaxed);
+        self.worker.push(Box::new(task));
+    }
+
+    /// Which of the pool's threads is running the task.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+/// Counts a task as finished even if it panics, so the other threads still
+/// see the pool drain and stop.
+struct Finish<'a>(&'a AtomicUsize);
+
+impl Drop for Finish<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// A fixed number of scoped threads, each with its own Chase–Lev deque.
+/// A thread runs the tasks it spawned newest first, which keeps recursive
+/// work depth-first and cache-warm, and when it runs dry it steals the
+/// oldest task of another thread, which for divide and conquer is the
+/// biggest piece left.
+///
+/// `run` counts tasks that have been spawned but not finished and returns
+/// once that reaches zero. Tasks may borrow anything that outlives the call.
+pub struct Pool {
+    threads: usize,
+}
+
+impl Pool {
+    /// At least one thread is always used.
+    pub fn new(threads: usize) -> Self {
+        Pool { threads: threads.max(1) }
+    }
+
+    pub fn threads(&self) -> usize {
+        self.threads
+    }
+
+    /// Runs `root` and everything it spawns, directly or not. A panicking
+    /// task is re-raised here once the other threads have finished.
+    pub fn run<'env, F: FnOnce(&Context<'_, 'env>) + Send + 'env>(&self, root: F) {
+        let workers: Vec<Worker<Task<'env>>> = (0..self.threads).map(|_| Worker::new()).collect();
+        let stealers: Vec<Stealer<Task<'env>>> = workers.iter().map(Worker::stealer).collect();
+        let pending: AtomicUsize = AtomicUsize::new(1);
+        workers[0].push(Box::new(root));
+
+        let result: thread::Result<()> = thread::scope(|scope| {
+            let handles: Vec<_> = workers
+                .into_iter()
+                .enumerate()
+                .map(|(index, worker)| {
+                    let (stealers, pending) = (&stealers, &pending);
+                    scope.spawn(move || work(&Context { worker: &worker, pending, index }, stealers))
+                })
+                .collect();
+            handles.into_iter().map(|handle| handle.join()).fold(Ok(()), Result::and)
+        });
+
+        if let Err(payload) = result {
+            panic::resume_unwind(payload);
+        }
+    }
+}
+
+fn work<'env>(context: &Context<'_, 'env>, stealers: &[Stealer<Task<'env>>]) {
+    loop {
+        match context.worker.pop().or_else(|| steal(context.index, stealers)) {
+            Some(task) => {
+                let _finish: Finish = Finish(context.pending);
+                task(context);
+            }
+            None if context.pending.load(Ordering::Acquire) == 0 => return,
+            None => thread::yield_now(),
+        }
+    }
+}
+
+/// Tries every other thread once, starting with the next one, and goes
+/// round again only if a steal lost a race.
+fn steal<'env>(index: usize, stealers: &[Stealer<Task<'env>>]) -> Option<Task<'env>> {
+    loop {
+        let mut retry: bool = false;
+
+        for offset in 1..stealers.len() {
+            match stealers[(index + offset) % stealers.len()].steal() {
+                Steal::Success(task) => return Some(task),
+                Steal::Retry => retry = true,
+                Steal::Empty => {}
+            }
+        }
+
+        if !retry {
+            return None;
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::sync::Mutex;
+
+    /// Spawns a binary tree of tasks `depth` levels deep and counts the leaves.
+    fn fan_out<'env>(context: &Context<'_, 'env>, depth: usize, leaves: &'env AtomicUsize, threads: &'env Mutex<HashSet<usize>>) {
+        threads.lock().unwrap().insert(context.index());
+        if depth == 0 {
+            leaves.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+
+        for _ in 0..2 {
+            context.spawn(move |context| fan_out(context, depth - 1, leaves, threads));
+        }
+    }
+
+    #[test]
+    fn test_runs_every_spawned_task() {
+        for threads in [1, 4] {
+            let pool: Pool = Pool::new(threads);
+            let leaves: AtomicUsize = AtomicUsize::new(0);
+            let seen: Mutex<HashSet<usize>> = Mutex::new(HashSet::new());
+
+            pool.run(|context| fan_out(context, 12, &leaves, &seen));
+            assert_eq!(leaves.into_inner(), 1 << 12);
+            assert!(seen.into_inner().unwrap().iter().all(|&index| index < pool.threads()));
+        }
+    }
+
+    #[test]
+    fn test_zero_threads_means_one() {
+        let pool: Pool = Pool::new(0);
+        assert_eq!(pool.threads(), 1);
+
+        let mut ran: bool = false;
+        pool.run(|_| ran = true);
+        assert!(ran);
+    }
+
+    #[test]
+    #[should_panic(expected = "task failed")]
+    fn test_panics_reach_the_caller() {
+        Pool::new(3).run(|context| {
+            for step in 0..10 {
+                context.spawn(move |_| assert_ne!(step, 7, "task failed"));
+            }
+        });
+    }
+}
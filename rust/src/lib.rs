@@ -1 +1,6 @@
 pub mod linked_lists;
+pub mod arrays;
+pub mod stacks;
+pub mod queues;
+pub mod binary_trees;
+pub mod concurrent;
@@ -0,0 +1,317 @@
+use std::fmt::{Debug, Error, Formatter};
+use std::rc::Rc;
+use std::cell::RefCell;
+
+pub struct Node<T> {
+    pub data: Option<T>,
+    pub next: Option<Rc<RefCell<Node<T>>>>,
+}
+
+impl<T> Node<T> {
+    pub fn new() -> Node<T> {
+        Node { data: None, next: None }
+    }
+
+    pub fn from(data: T) -> Node<T> {
+        Node { data: Some(data), next: None }
+    }
+}
+
+/// A circular singly linked list. The tail's `next` points back at the head,
+/// which is also the list's current position, so rotating the list only
+/// has to move the tail pointer along by one node.
+pub struct CircularList<T> {
+    pub tail: Option<Rc<RefCell<Node<T>>>>,
+    pub length: usize,
+}
+
+impl<T> CircularList<T> {
+    pub fn new() -> Self {
+        CircularList { tail: None, length: 0 }
+    }
+
+    pub fn from(data_vec: Vec<T>) -> Self {
+        let mut list: CircularList<T> = CircularList::new();
+
+        for data in data_vec {
+            list.push_to_end(data);
+        }
+
+        return list;
+    }
+
+    fn head(&self) -> Option<Rc<RefCell<Node<T>>>> {
+        match &self.tail {
+            Some(tail) => tail.borrow().next.clone(),
+            None => None,
+        }
+    }
+
+    pub fn push_to_front(&mut self, data: T) {
+        let new_node: Rc<RefCell<Node<T>>> = Rc::new(RefCell::new(Node::from(data)));
+
+        match &self.tail {
+            Some(tail) => {
+                new_node.borrow_mut().next = tail.borrow_mut().next.take();
+                tail.borrow_mut().next = Some(new_node);
+            }
+            None => {
+                new_node.borrow_mut().next = Some(new_node.clone());
+                self.tail = Some(new_node);
+            }
+        }
+
+        self.length += 1;
+    }
+
+    pub fn push_to_end(&mut self, data: T) {
+        self.push_to_front(data);
+        self.rotate();
+    }
+
+    pub fn current(&self) -> Option<T>
+    where
+        T: Clone,
+    {
+        match self.head() {
+            Some(head) => head.borrow().data.clone(),
+            None => None,
+        }
+    }
+
+    pub fn rotate(&mut self) {
+        if let Some(head) = self.head() {
+            self.tail = Some(head);
+        }
+    }
+
+    pub fn advance(&mut self, k: usize) {
+        if self.length == 0 {
+            return;
+        }
+
+        for _ in 0..(k % self.length) {
+            self.rotate();
+        }
+    }
+
+    pub fn remove_current(&mut self) -> Result<T, Error> {
+        let tail: Rc<RefCell<Node<T>>> = match self.tail.clone() {
+            Some(tail) => tail,
+            None => return Err(Error),
+        };
+        let head: Rc<RefCell<Node<T>>> = tail.borrow_mut().next.take().unwrap();
+
+        if Rc::ptr_eq(&head, &tail) {
+            self.tail = None;
+        } else {
+            tail.borrow_mut().next = head.borrow_mut().next.take();
+        }
+
+        self.length -= 1;
+        let data: Option<T> = head.borrow_mut().data.take();
+        return data.ok_or(Error);
+    }
+
+    pub fn iter(&self) -> Iter<T> {
+        Iter { current: self.head(), remaining: self.length }
+    }
+
+    pub fn josephus(mut self, k: usize) -> Result<Vec<T>, Error> {
+        if k == 0 {
+            return Err(Error);
+        }
+
+        let mut order: Vec<T> = Vec::with_capacity(self.length);
+        while self.length > 0 {
+            self.advance(k - 1);
+            order.push(self.remove_current()?);
+        }
+
+        return Ok(order);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.length == 0;
+    }
+}
+
+impl<T> Drop for CircularList<T> {
+    fn drop(&mut self) {
+        // Cut the cycle at the tail, then unlink node by node so that long
+        // lists don't recurse through every `Rc` drop.
+        let mut current_node: Option<Rc<RefCell<Node<T>>>> = match self.tail.take() {
+            Some(tail) => tail.borrow_mut().next.take(),
+            None => None,
+        };
+
+        while let Some(node) = current_node {
+            current_node = node.borrow_mut().next.take();
+        }
+    }
+}
+
+impl<T: Debug + Clone> Debug for CircularList<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq + Clone> PartialEq for CircularList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        return self.length == other.length && self.iter().eq(other.iter());
+    }
+}
+
+/// Visits every node once, starting from the current position.
+pub struct Iter<T> {
+    current: Option<Rc<RefCell<Node<T>>>>,
+    remaining: usize,
+}
+
+impl<T: Clone> Iterator for Iter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let node: Rc<RefCell<Node<T>>> = self.current.take()?;
+        self.current = node.borrow().next.clone();
+        self.remaining -= 1;
+        return node.borrow().data.clone();
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Weak;
+
+    fn collect(list: &CircularList<i64>) -> Vec<i64> {
+        list.iter().collect()
+    }
+
+    #[test]
+    fn test_circular_list_new() {
+        let list: CircularList<i64> = CircularList::new();
+        assert!(list.tail.is_none());
+        assert_eq!(list.length, 0);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn test_circular_list_from() {
+        let list: CircularList<i64> = CircularList::from(vec![8, 56, 19, 80]);
+        assert_eq!(collect(&list), vec![8, 56, 19, 80]);
+        assert_eq!(list.length, 4);
+        assert_eq!(list.current(), Some(8));
+    }
+
+    #[test]
+    fn test_one_tail_links_to_itself() {
+        let list: CircularList<i64> = CircularList::from(vec![3]);
+        let tail: Rc<RefCell<Node<i64>>> = list.tail.clone().unwrap();
+        let next: Rc<RefCell<Node<i64>>> = tail.borrow().next.clone().unwrap();
+        assert!(Rc::ptr_eq(&tail, &next));
+    }
+
+    #[test]
+    fn test_push_to_front() {
+        let mut list: CircularList<i64> = CircularList::from(vec![2, 3]);
+        list.push_to_front(1);
+        assert_eq!(collect(&list), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_empty_rotate() {
+        let mut list: CircularList<i64> = CircularList::new();
+        list.rotate();
+        list.advance(3);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn test_rotate() {
+        let mut list: CircularList<i64> = CircularList::from(vec![1, 2, 3]);
+        list.rotate();
+        assert_eq!(collect(&list), vec![2, 3, 1]);
+        list.push_to_end(4);
+        assert_eq!(collect(&list), vec![2, 3, 1, 4]);
+    }
+
+    #[test]
+    fn test_advance() {
+        let mut list: CircularList<i64> = CircularList::from(vec![1, 2, 3, 4]);
+        list.advance(6);
+        assert_eq!(list.current(), Some(3));
+        list.advance(0);
+        assert_eq!(list.current(), Some(3));
+    }
+
+    #[test]
+    fn test_empty_remove_current() {
+        let mut list: CircularList<i64> = CircularList::new();
+        assert!(list.remove_current().is_err());
+    }
+
+    #[test]
+    fn test_remove_current() {
+        let mut list: CircularList<i64> = CircularList::from(vec![1, 2, 3]);
+        list.rotate();
+        assert_eq!(list.remove_current(), Ok(2));
+        assert_eq!(collect(&list), vec![3, 1]);
+        assert_eq!(list.remove_current(), Ok(3));
+        assert_eq!(list.remove_current(), Ok(1));
+        assert!(list.tail.is_none());
+        assert_eq!(list.length, 0);
+    }
+
+    #[test]
+    fn test_iter_stops_after_one_cycle() {
+        let list: CircularList<i64> = CircularList::from(vec![5, 6, 7]);
+        assert_eq!(list.iter().count(), 3);
+        let mut iter: Iter<i64> = list.iter();
+        assert_eq!(iter.next(), Some(5));
+        assert_eq!(iter.next(), Some(6));
+        assert_eq!(iter.next(), Some(7));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn test_josephus() {
+        let list: CircularList<i64> = CircularList::from(vec![1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(list.josephus(3), Ok(vec![3, 6, 2, 7, 5, 1, 4]));
+    }
+
+    #[test]
+    fn test_one_josephus() {
+        let list: CircularList<i64> = CircularList::from(vec![1, 2, 3]);
+        assert_eq!(list.josephus(1), Ok(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn test_zero_josephus() {
+        let list: CircularList<i64> = CircularList::from(vec![1, 2, 3]);
+        assert!(list.josephus(0).is_err());
+    }
+
+    #[test]
+    fn test_drop_frees_nodes() {
+        let list: CircularList<i64> = CircularList::from(vec![1, 2, 3]);
+        let weak_tail: Weak<RefCell<Node<i64>>> = Rc::downgrade(list.tail.as_ref().unwrap());
+        let weak_head: Weak<RefCell<Node<i64>>> = Rc::downgrade(&list.head().unwrap());
+        drop(list);
+        assert!(weak_tail.upgrade().is_none());
+        assert!(weak_head.upgrade().is_none());
+    }
+
+    #[test]
+    fn test_long_drop() {
+        let list: CircularList<i64> = CircularList::from((0..200_000).collect());
+        drop(list);
+    }
+}
@@ -1,42 +1,303 @@
-#![allow(unused_variables)]
-
-use std::rc::Rc;
+use std::fmt::Error;
+use std::rc::{Rc, Weak};
 use std::cell::RefCell;
 
-#[derive(Clone, PartialEq, Debug)]
-pub struct Node<T> {}
-
-impl<T> Node<T> {}
-
-#[derive(PartialEq, Debug)]
-pub struct LinkedList<T> {}
+/// `next` owns the following node while `prev` only points back at the one
+/// before, so the two directions never form an `Rc` cycle.
+#[derive(Clone, Debug)]
+pub struct Node<T> {
+    pub data: Option<T>,
+    pub prev: Option<Weak<RefCell<Node<T>>>>,
+    pub next: Option<Rc<RefCell<Node<T>>>>,
+}
 
-impl<T> LinkedList<T> {
-    pub fn new() -> Self {}
+impl<T> Node<T> {
+    pub fn new() -> Node<T> {
+        Node { data: None, prev: None, next: None }
+    }
 
-    pub fn from(data_vec: Vec<T>) -> Self {}
+    pub fn from(data: T) -> Node<T> {
+        Node { data: Some(data), prev: None, next: None }
+    }
+}
 
-    pub fn push_to_front(&mut self, data: T) {}
+/// Nodes compare by their data and the data of every node after them.
+/// `prev` is left out, since a `Weak` can't be compared.
+impl<T: PartialEq> PartialEq for Node<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data && same_data(self.next.clone(), other.next.clone())
+    }
+}
 
-    pub fn pop_from_front(&mut self) {}
+/// Walks two chains side by side in a loop; comparing through `next`
+/// recursively would overflow the stack on a long list, like the default
+/// drop.
+fn same_data<T: PartialEq>(mut first: Option<Rc<RefCell<Node<T>>>>, mut second: Option<Rc<RefCell<Node<T>>>>) -> bool {
+    loop {
+        match (first, second) {
+            (None, None) => return true,
+            (Some(first_node), Some(second_node)) => {
+                if first_node.borrow().data != second_node.borrow().data {
+                    return false;
+                }
+                first = first_node.borrow().next.clone();
+                second = second_node.borrow().next.clone();
+            }
+            _ => return false,
+        }
+    }
+}
 
-    pub fn into_front(self) -> Option<T> {}
+/// A doubly linked list with a tail. Both ends are reachable directly, so
+/// pushing and popping at either end is O(1), and a node that is already in
+/// hand can be unlinked in O(1).
+#[derive(Debug)]
+pub struct LinkedList<T> {
+    pub head: Option<Rc<RefCell<Node<T>>>>,
+    pub tail: Option<Rc<RefCell<Node<T>>>>,
+    pub length: usize,
+}
 
-    pub fn push_to_end(&mut self, data: T) {}
+/// Lists compare by their elements in order.
+impl<T: PartialEq> PartialEq for LinkedList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        return self.length == other.length && same_data(self.head.clone(), other.head.clone());
+    }
+}
 
-    pub fn pop_from_end(&mut self) {}
+impl<T> LinkedList<T> {
+    pub fn new() -> Self {
+        LinkedList { head: None, tail: None, length: 0 }
+    }
+
+    pub fn from(data_vec: Vec<T>) -> Self {
+        let mut list: LinkedList<T> = LinkedList::new();
+
+        for data in data_vec {
+            list.push_to_end(data);
+        }
+
+        return list;
+    }
+
+    pub fn push_to_front(&mut self, data: T) {
+        let node: Rc<RefCell<Node<T>>> = Rc::new(RefCell::new(Node::from(data)));
+
+        match self.head.take() {
+            Some(head) => {
+                head.borrow_mut().prev = Some(Rc::downgrade(&node));
+                node.borrow_mut().next = Some(head);
+            }
+            None => self.tail = Some(node.clone()),
+        }
+
+        self.head = Some(node);
+        self.length += 1;
+    }
+
+    pub fn pop_from_front(&mut self) -> Result<T, Error> {
+        let head: Rc<RefCell<Node<T>>> = self.head.clone().ok_or(Error)?;
+        return self.unlink(&head);
+    }
+
+    pub fn push_to_end(&mut self, data: T) {
+        let node: Rc<RefCell<Node<T>>> = Rc::new(RefCell::new(Node::from(data)));
+
+        match self.tail.take() {
+            Some(tail) => {
+                node.borrow_mut().prev = Some(Rc::downgrade(&tail));
+                tail.borrow_mut().next = Some(node.clone());
+            }
+            None => self.head = Some(node.clone()),
+        }
+
+        self.tail = Some(node);
+        self.length += 1;
+    }
+
+    pub fn pop_from_end(&mut self) -> Result<T, Error> {
+        let tail: Rc<RefCell<Node<T>>> = self.tail.clone().ok_or(Error)?;
+        return self.unlink(&tail);
+    }
+
+    /// Walks from whichever end is closer to `index`.
+    pub fn find_by_index(&self, index: usize) -> Result<Rc<RefCell<Node<T>>>, Error> {
+        if index >= self.length {
+            return Err(Error);
+        }
+
+        if index < self.length / 2 {
+            let mut current_node: Rc<RefCell<Node<T>>> = self.head.clone().ok_or(Error)?;
+            for _ in 0..index {
+                let next: Rc<RefCell<Node<T>>> = current_node.borrow().next.clone().ok_or(Error)?;
+                current_node = next;
+            }
+            return Ok(current_node);
+        }
+
+        let mut current_node: Rc<RefCell<Node<T>>> = self.tail.clone().ok_or(Error)?;
+        for _ in index..self.length - 1 {
+            let prev: Rc<RefCell<Node<T>>> = current_node.borrow().prev.as_ref().and_then(Weak::upgrade).ok_or(Error)?;
+            current_node = prev;
+        }
+        return Ok(current_node);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    pub fn add_before(&mut self, index: usize, data: T) -> Result<(), Error> {
+        let node: Rc<RefCell<Node<T>>> = self.find_by_index(index)?;
+        self.link_before(&node, data);
+        return Ok(());
+    }
+
+    pub fn add_after(&mut self, index: usize, data: T) -> Result<(), Error> {
+        let node: Rc<RefCell<Node<T>>> = self.find_by_index(index)?;
+        self.link_after(&node, data);
+        return Ok(());
+    }
+
+    pub fn remove(&mut self, index: usize) -> Result<T, Error> {
+        let node: Rc<RefCell<Node<T>>> = self.find_by_index(index)?;
+        return self.unlink(&node);
+    }
+
+    /// Inserts `data` right before `node`, which must belong to this list.
+    pub fn link_before(&mut self, node: &Rc<RefCell<Node<T>>>, data: T) {
+        let prev: Option<Rc<RefCell<Node<T>>>> = node.borrow().prev.as_ref().and_then(Weak::upgrade);
+        match prev {
+            Some(prev) => self.link_after(&prev, data),
+            None => self.push_to_front(data),
+        }
+    }
+
+    /// Inserts `data` right after `node`, which must belong to this list.
+    pub fn link_after(&mut self, node: &Rc<RefCell<Node<T>>>, data: T) {
+        let new_node: Rc<RefCell<Node<T>>> = Rc::new(RefCell::new(Node::from(data)));
+        let next: Option<Rc<RefCell<Node<T>>>> = node.borrow_mut().next.take();
+
+        match next {
+            Some(next) => {
+                next.borrow_mut().prev = Some(Rc::downgrade(&new_node));
+                new_node.borrow_mut().next = Some(next);
+            }
+            None => self.tail = Some(new_node.clone()),
+        }
+
+        new_node.borrow_mut().prev = Some(Rc::downgrade(node));
+        node.borrow_mut().next = Some(new_node);
+        self.length += 1;
+    }
+
+    /// Takes `node` out of the list in O(1) and returns its data. The node
+    /// must belong to this list; a node from another list would corrupt this
+    /// one's head or tail. A node that was already unlinked has no data left
+    /// and is rejected without touching the list.
+    pub fn unlink(&mut self, node: &Rc<RefCell<Node<T>>>) -> Result<T, Error> {
+        if node.borrow().data.is_none() {
+            return Err(Error);
+        }
+
+        let prev: Option<Rc<RefCell<Node<T>>>> = node.borrow_mut().prev.take().and_then(|prev| prev.upgrade());
+        let next: Option<Rc<RefCell<Node<T>>>> = node.borrow_mut().next.take();
+
+        match &next {
+            Some(next) => next.borrow_mut().prev = prev.as_ref().map(Rc::downgrade),
+            None => self.tail = prev.clone(),
+        }
+        match prev {
+            Some(prev) => prev.borrow_mut().next = next,
+            None => self.head = next,
+        }
+
+        self.length -= 1;
+        return node.borrow_mut().data.take().ok_or(Error);
+    }
+
+    /// Splits the list in two at `at`. The list keeps the first `at`
+    /// elements and the rest are returned as a new list.
+    pub fn split_off(&mut self, at: usize) -> Result<LinkedList<T>, Error> {
+        if at > self.length {
+            return Err(Error);
+        }
+        if at == self.length {
+            return Ok(LinkedList::new());
+        }
+        if at == 0 {
+            return Ok(std::mem::replace(self, LinkedList::new()));
+        }
+
+        let node: Rc<RefCell<Node<T>>> = self.find_by_index(at)?;
+        let prev: Rc<RefCell<Node<T>>> = node.borrow_mut().prev.take().and_then(|prev| prev.upgrade()).ok_or(Error)?;
+        prev.borrow_mut().next = None;
+
+        let back: LinkedList<T> = LinkedList { head: Some(node), tail: self.tail.replace(prev), length: self.length - at };
+        self.length = at;
+        return Ok(back);
+    }
+
+    /// Moves every element of `other` to the end of this list in O(1),
+    /// leaving `other` empty.
+    pub fn append(&mut self, other: &mut LinkedList<T>) {
+        let other_head: Option<Rc<RefCell<Node<T>>>> = other.head.take();
+        let other_tail: Option<Rc<RefCell<Node<T>>>> = other.tail.take();
+        let other_length: usize = std::mem::take(&mut other.length);
+
+        let Some(other_head) = other_head else {
+            return;
+        };
+
+        match self.tail.take() {
+            Some(tail) => {
+                other_head.borrow_mut().prev = Some(Rc::downgrade(&tail));
+                tail.borrow_mut().next = Some(other_head);
+            }
+            None => self.head = Some(other_head),
+        }
+
+        self.tail = other_tail;
+        self.length += other_length;
+    }
+}
 
-    pub fn into_back(self) -> Option<T> {}
+impl<T: Clone> LinkedList<T> {
+    pub fn into_front(&self) -> Option<T> {
+        self.head.as_ref().and_then(|head| head.borrow().data.clone())
+    }
 
-    pub fn find_by_index(self, index: usize) {}
+    pub fn into_back(&self) -> Option<T> {
+        self.tail.as_ref().and_then(|tail| tail.borrow().data.clone())
+    }
+}
 
-    pub fn find_by_value(self, value: T) {}
+impl<T: PartialEq> LinkedList<T> {
+    pub fn find_by_value(&self, value: &T) -> Result<Rc<RefCell<Node<T>>>, Error> {
+        let mut current_node: Option<Rc<RefCell<Node<T>>>> = self.head.clone();
 
-    pub fn is_empty(self) -> bool {}
+        while let Some(node) = current_node {
+            if node.borrow().data.as_ref() == Some(value) {
+                return Ok(node);
+            }
+            current_node = node.borrow().next.clone();
+        }
 
-    pub fn add_before(index: usize) {}
+        return Err(Error);
+    }
+}
 
-    pub fn add_after(index: usize) {}
+/// Drops the nodes one at a time; the default drop would recurse through
+/// every `next` and can overflow the stack on a long list.
+impl<T> Drop for LinkedList<T> {
+    fn drop(&mut self) {
+        self.tail = None;
+        let mut current_node: Option<Rc<RefCell<Node<T>>>> = self.head.take();
+
+        while let Some(node) = current_node {
+            current_node = node.borrow_mut().next.take();
+        }
+    }
 }
 
 
@@ -45,136 +306,361 @@ impl<T> LinkedList<T> {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_node_new() {}
+    fn collect(list: &LinkedList<i64>) -> Vec<i64> {
+        let mut values: Vec<i64> = Vec::new();
+        let mut current_node: Option<Rc<RefCell<Node<i64>>>> = list.head.clone();
+        while let Some(node) = current_node {
+            values.push(node.borrow().data.unwrap());
+            current_node = node.borrow().next.clone();
+        }
+        return values;
+    }
 
-    #[test]
-    fn test_node_from() {}
+    fn collect_backwards(list: &LinkedList<i64>) -> Vec<i64> {
+        let mut values: Vec<i64> = Vec::new();
+        let mut current_node: Option<Rc<RefCell<Node<i64>>>> = list.tail.clone();
+        while let Some(node) = current_node {
+            values.push(node.borrow().data.unwrap());
+            current_node = node.borrow().prev.as_ref().and_then(Weak::upgrade);
+        }
+        return values;
+    }
 
     #[test]
-    fn test_linked_list_new() {}
+    fn test_node_new() {
+        let manual_node: Node<i64> = Node { data: None, prev: None, next: None };
+        let function_node: Node<i64> = Node::new();
+        assert_eq!(manual_node, function_node);
+    }
 
     #[test]
-    fn test_empty_linked_list_from() {}
+    fn test_node_from() {
+        let manual_node: Node<i64> = Node { data: Some(8), prev: None, next: None };
+        let function_node: Node<i64> = Node::from(8);
+        assert_eq!(manual_node, function_node);
+    }
 
-    #[test]
-    fn test_one_linked_list_from() {}
+    #[test]
+    fn test_linked_list_new() {
+        let manual_list: LinkedList<i64> = LinkedList { head: None, tail: None, length: 0 };
+        let function_list: LinkedList<i64> = LinkedList::new();
+        assert_eq!(manual_list, function_list);
+    }
 
     #[test]
-    fn test_four_linked_list_from() {}
+    fn test_empty_linked_list_from() {
+        let function_list: LinkedList<i64> = LinkedList::from(vec![]);
+        assert_eq!(function_list, LinkedList::new());
+    }
 
     #[test]
-    pub fn test_empty_push_to_front() {}
+    fn test_one_linked_list_from() {
+        let manual_list: LinkedList<i64> = LinkedList {
+            head: Some(Rc::new(RefCell::new(Node::from(8)))),
+            tail: Some(Rc::new(RefCell::new(Node::from(8)))),
+            length: 1,
+        };
 
-    #[test]
-    pub fn test_push_to_front() {}
+        let function_list: LinkedList<i64> = LinkedList::from(vec![8]);
+        assert_eq!(manual_list, function_list);
+        assert!(Rc::ptr_eq(function_list.head.as_ref().unwrap(), function_list.tail.as_ref().unwrap()));
+    }
 
     #[test]
-    pub fn test_empty_pop_from_front() {}
+    fn test_four_linked_list_from() {
+        let list: LinkedList<i64> = LinkedList::from(vec![8, 56, 19, 80]);
+        assert_eq!(list.length, 4);
+        assert_eq!(collect(&list), vec![8, 56, 19, 80]);
+        assert_eq!(collect_backwards(&list), vec![80, 19, 56, 8]);
+    }
 
     #[test]
-    pub fn test_one_pop_from_front() {}
+    pub fn test_empty_push_to_front() {
+        let mut list: LinkedList<i64> = LinkedList::new();
+        list.push_to_front(8);
+        assert_eq!(list, LinkedList::from(vec![8]));
+        assert!(Rc::ptr_eq(list.head.as_ref().unwrap(), list.tail.as_ref().unwrap()));
+    }
 
     #[test]
-    pub fn test_pop_from_front() {}
+    pub fn test_push_to_front() {
+        let mut list: LinkedList<i64> = LinkedList::from(vec![19, 80]);
+        list.push_to_front(56);
+        list.push_to_front(8);
+        assert_eq!(collect(&list), vec![8, 56, 19, 80]);
+        assert_eq!(collect_backwards(&list), vec![80, 19, 56, 8]);
+    }
 
     #[test]
-    pub fn pop_from_front() {}
-
+    pub fn test_empty_pop_from_front() {
+        let mut list: LinkedList<i64> = LinkedList::new();
+        assert_eq!(list.pop_from_front(), Err(Error));
+        assert_eq!(list.length, 0);
+    }
 
     #[test]
-    fn test_empty_into_front() {}
+    pub fn test_one_pop_from_front() {
+        let mut list: LinkedList<i64> = LinkedList::from(vec![8]);
+        assert_eq!(list.pop_from_front(), Ok(8));
+        assert_eq!(list, LinkedList::new());
+    }
 
     #[test]
-    fn test_into_front() {}
+    pub fn test_pop_from_front() {
+        let mut list: LinkedList<i64> = LinkedList::from(vec![8, 56, 19]);
+        assert_eq!(list.pop_from_front(), Ok(8));
+        assert_eq!(collect(&list), vec![56, 19]);
+        assert_eq!(collect_backwards(&list), vec![19, 56]);
+        assert!(list.head.as_ref().unwrap().borrow().prev.is_none());
+    }
 
     #[test]
-    fn test_empty_push_to_end() {}
+    pub fn test_empty_into_front() {
+        let list: LinkedList<i64> = LinkedList::new();
+        assert_eq!(list.into_front(), None);
+    }
 
     #[test]
-    fn test_push_to_end() {}
-    
-    #[test]
-    fn test_empty_pop_from_end() {}
+    pub fn test_into_front() {
+        let list: LinkedList<i64> = LinkedList::from(vec![8, 56]);
+        assert_eq!(list.into_front(), Some(8));
+    }
 
     #[test]
-    fn test_one_pop_from_end() {}
+    pub fn test_empty_push_to_end() {
+        let mut list: LinkedList<i64> = LinkedList::new();
+        list.push_to_end(8);
+        assert_eq!(list, LinkedList::from(vec![8]));
+    }
 
     #[test]
-    fn test_pop_from_end() {}
+    pub fn test_push_to_end() {
+        let mut list: LinkedList<i64> = LinkedList::from(vec![8]);
+        list.push_to_end(56);
+        list.push_to_end(19);
+        assert_eq!(collect(&list), vec![8, 56, 19]);
+        assert_eq!(collect_backwards(&list), vec![19, 56, 8]);
+    }
 
     #[test]
-    fn test_empty_into_back() {}
+    pub fn test_empty_pop_from_end() {
+        let mut list: LinkedList<i64> = LinkedList::new();
+        assert_eq!(list.pop_from_end(), Err(Error));
+    }
 
     #[test]
-    fn test_into_back() {}
+    pub fn test_one_pop_from_end() {
+        let mut list: LinkedList<i64> = LinkedList::from(vec![8]);
+        assert_eq!(list.pop_from_end(), Ok(8));
+        assert_eq!(list, LinkedList::new());
+    }
 
     #[test]
-    fn test_empty_find_by_index() {}
+    pub fn test_pop_from_end() {
+        let mut list: LinkedList<i64> = LinkedList::from(vec![8, 56, 19]);
+        assert_eq!(list.pop_from_end(), Ok(19));
+        assert_eq!(list.pop_from_end(), Ok(56));
+        assert_eq!(collect(&list), vec![8]);
+        assert!(list.tail.as_ref().unwrap().borrow().next.is_none());
+    }
 
     #[test]
-    fn test_zeroth_index_find_by_index() {}
+    pub fn test_empty_into_back() {
+        let list: LinkedList<i64> = LinkedList::new();
+        assert_eq!(list.into_back(), None);
+    }
 
     #[test]
-    fn test_last_index_find_by_index() {}
+    pub fn test_into_back() {
+        let list: LinkedList<i64> = LinkedList::from(vec![8, 56]);
+        assert_eq!(list.into_back(), Some(56));
+    }
 
     #[test]
-    fn test_inside_index_find_by_index() {}
+    pub fn test_empty_find_by_index() {
+        let list: LinkedList<i64> = LinkedList::new();
+        assert!(list.find_by_index(0).is_err());
+    }
 
     #[test]
-    fn test_outside_index_find_by_index() {}
+    pub fn test_zeroth_index_find_by_index() {
+        let list: LinkedList<i64> = LinkedList::from(vec![8, 56, 19, 80]);
+        assert_eq!(list.find_by_index(0).unwrap().borrow().data, Some(8));
+    }
 
     #[test]
-    fn find_empty_invalid_by_value() {}
+    pub fn test_last_index_find_by_index() {
+        let list: LinkedList<i64> = LinkedList::from(vec![8, 56, 19, 80]);
+        assert_eq!(list.find_by_index(3).unwrap().borrow().data, Some(80));
+    }
 
     #[test]
-    fn find_none_invalid_by_value() {}
+    pub fn test_inside_index_find_by_index() {
+        let list: LinkedList<i64> = LinkedList::from(vec![8, 56, 19, 80, 43]);
+        for (index, value) in [8, 56, 19, 80, 43].into_iter().enumerate() {
+            assert_eq!(list.find_by_index(index).unwrap().borrow().data, Some(value));
+        }
+    }
 
     #[test]
-    fn find_invalid_by_value() {}
+    pub fn test_outside_index_find_by_index() {
+        let list: LinkedList<i64> = LinkedList::from(vec![8, 56]);
+        assert!(list.find_by_index(2).is_err());
+    }
 
     #[test]
-    fn find_valid_first_by_value() {}
+    pub fn test_find_by_value() {
+        let list: LinkedList<i64> = LinkedList::from(vec![43, 17, 99]);
+        assert_eq!(list.find_by_value(&17).unwrap().borrow().data, Some(17));
+        assert!(list.find_by_value(&105).is_err());
+    }
 
     #[test]
-    fn find_valid_last_by_value() {}
+    pub fn test_empty_is_empty() {
+        let list: LinkedList<i64> = LinkedList::new();
+        assert!(list.is_empty());
+    }
 
     #[test]
-    fn find_valid_by_value() {}
+    pub fn test_some_is_empty() {
+        let list: LinkedList<i64> = LinkedList::from(vec![8]);
+        assert!(!list.is_empty());
+    }
 
     #[test]
-    fn test_empty_is_empty() {}
+    pub fn test_empty_add_before() {
+        let mut list: LinkedList<i64> = LinkedList::new();
+        assert_eq!(list.add_before(0, 8), Err(Error));
+    }
 
     #[test]
-    fn test_some_is_empty() {}
+    pub fn test_one_add_before() {
+        let mut list: LinkedList<i64> = LinkedList::from(vec![56]);
+        assert_eq!(list.add_before(0, 8), Ok(()));
+        assert_eq!(collect(&list), vec![8, 56]);
+        assert_eq!(collect_backwards(&list), vec![56, 8]);
+    }
 
     #[test]
-    pub fn test_empty_add_before() {}
+    pub fn test_first_add_before() {
+        let mut list: LinkedList<i64> = LinkedList::from(vec![56, 19]);
+        assert_eq!(list.add_before(0, 8), Ok(()));
+        assert_eq!(list.into_front(), Some(8));
+        assert_eq!(collect_backwards(&list), vec![19, 56, 8]);
+    }
 
     #[test]
-    pub fn test_one_add_before() {}
+    pub fn test_last_add_before() {
+        let mut list: LinkedList<i64> = LinkedList::from(vec![8, 80]);
+        assert_eq!(list.add_before(1, 19), Ok(()));
+        assert_eq!(collect(&list), vec![8, 19, 80]);
+        assert_eq!(list.into_back(), Some(80));
+    }
 
     #[test]
-    pub fn test_first_add_before() {}
+    pub fn test_add_before() {
+        let mut list: LinkedList<i64> = LinkedList::from(vec![8, 56, 80]);
+        assert_eq!(list.add_before(2, 19), Ok(()));
+        assert_eq!(list.add_before(4, 0), Err(Error));
+        assert_eq!(collect(&list), vec![8, 56, 19, 80]);
+        assert_eq!(collect_backwards(&list), vec![80, 19, 56, 8]);
+    }
 
     #[test]
-    pub fn test_last_add_before() {}
+    pub fn test_empty_add_after() {
+        let mut list: LinkedList<i64> = LinkedList::new();
+        assert_eq!(list.add_after(0, 8), Err(Error));
+    }
 
     #[test]
-    pub fn test_add_before() {}
+    pub fn test_one_add_after() {
+        let mut list: LinkedList<i64> = LinkedList::from(vec![8]);
+        assert_eq!(list.add_after(0, 56), Ok(()));
+        assert_eq!(collect(&list), vec![8, 56]);
+        assert_eq!(list.into_back(), Some(56));
+    }
 
     #[test]
-    pub fn test_empty_add_after() {}
+    pub fn test_first_add_after() {
+        let mut list: LinkedList<i64> = LinkedList::from(vec![8, 19]);
+        assert_eq!(list.add_after(0, 56), Ok(()));
+        assert_eq!(collect(&list), vec![8, 56, 19]);
+        assert_eq!(collect_backwards(&list), vec![19, 56, 8]);
+    }
 
     #[test]
-    pub fn test_one_add_after() {}
-
+    pub fn test_last_add_after() {
+        let mut list: LinkedList<i64> = LinkedList::from(vec![8, 56]);
+        assert_eq!(list.add_after(1, 19), Ok(()));
+        assert_eq!(list.into_back(), Some(19));
+        assert_eq!(collect_backwards(&list), vec![19, 56, 8]);
+    }
+
     #[test]
-    pub fn test_first_add_after() {}
+    pub fn test_add_after() {
+        let mut list: LinkedList<i64> = LinkedList::from(vec![8, 56, 80]);
+        assert_eq!(list.add_after(1, 19), Ok(()));
+        assert_eq!(list.add_after(4, 0), Err(Error));
+        assert_eq!(collect(&list), vec![8, 56, 19, 80]);
+        assert_eq!(collect_backwards(&list), vec![80, 19, 56, 8]);
+    }
 
     #[test]
-    pub fn test_last_add_after() {}
+    pub fn test_remove() {
+        let mut list: LinkedList<i64> = LinkedList::from(vec![0, 1, 2, 3, 4]);
+        assert_eq!(list.remove(3), Ok(3));
+        assert_eq!(list.remove(0), Ok(0));
+        assert_eq!(list.remove(3), Err(Error));
+        assert_eq!(collect(&list), vec![1, 2, 4]);
+        assert_eq!(collect_backwards(&list), vec![4, 2, 1]);
+    }
 
-    #[test]
-    pub fn test_add_after() {}
+    #[test]
+    pub fn test_split_off_and_append() {
+        let mut list: LinkedList<i64> = LinkedList::from(vec![0, 1, 2, 3, 4]);
+        let mut back: LinkedList<i64> = list.split_off(2).unwrap();
+        assert_eq!(collect(&list), vec![0, 1]);
+        assert_eq!(collect_backwards(&back), vec![4, 3, 2]);
+        assert_eq!((list.length, back.length), (2, 3));
+        assert!(list.split_off(3).is_err());
+        assert!(list.split_off(2).unwrap().is_empty());
+
+        list.append(&mut back);
+        assert!(back.is_empty() && back.head.is_none());
+        assert_eq!(collect(&list), vec![0, 1, 2, 3, 4]);
+        assert_eq!(collect_backwards(&list), vec![4, 3, 2, 1, 0]);
+
+        let mut all: LinkedList<i64> = list.split_off(0).unwrap();
+        assert!(list.is_empty());
+        list.append(&mut all);
+        assert_eq!(list.length, 5);
+    }
+
+    #[test]
+    pub fn test_long_list_drop() {
+        let list: LinkedList<i64> = LinkedList::from((0..200_000).collect());
+        assert_eq!(list.length, 200_000);
+    }
+
+    #[test]
+    pub fn test_unlink_twice_is_rejected() {
+        let mut list: LinkedList<i64> = LinkedList::from(vec![1, 2, 3]);
+        let node: Rc<RefCell<Node<i64>>> = list.find_by_index(1).unwrap();
+        assert_eq!(list.unlink(&node), Ok(2));
+        assert_eq!(list.unlink(&node), Err(Error));
+        assert_eq!(list.length, 2);
+        assert_eq!(collect(&list), vec![1, 3]);
+        assert_eq!(collect_backwards(&list), vec![3, 1]);
+    }
+
+    #[test]
+    pub fn test_long_list_eq() {
+        let list: LinkedList<i64> = LinkedList::from((0..300_000).collect());
+        let mut other: LinkedList<i64> = LinkedList::from((0..300_000).collect());
+        assert_eq!(list, other);
+        assert!(*list.head.as_ref().unwrap().borrow() == *other.head.as_ref().unwrap().borrow());
+
+        other.push_to_end(0);
+        assert_ne!(list, other);
+    }
 }
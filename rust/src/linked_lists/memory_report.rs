@@ -0,0 +1,63 @@
+use std::cell::RefCell;
+use std::mem::size_of;
+
+use super::sllwt_rc_refcell;
+
+/// How many heap bytes a list uses for its nodes compared with the bytes
+/// taken up by the elements themselves. Allocator bookkeeping and rounding
+/// are not included.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct MemoryReport {
+    pub elements: usize,
+    pub element_bytes: usize,
+    pub allocated_bytes: usize,
+}
+
+impl MemoryReport {
+    pub fn overhead_bytes(&self) -> usize {
+        return self.allocated_bytes - self.elements * self.element_bytes;
+    }
+
+    pub fn overhead_per_element(&self) -> f64 {
+        if self.elements == 0 {
+            return 0.0;
+        }
+
+        return self.overhead_bytes() as f64 / self.elements as f64;
+    }
+}
+
+/// The report for a `sllwt_rc_refcell::LinkedList` of `length` elements, to
+/// compare the other backends against. Each node is an `Rc` allocation, which
+/// holds the strong and weak counts next to the `RefCell<Node<T>>`.
+pub fn rc_refcell_report<T>(length: usize) -> MemoryReport {
+    let node_bytes: usize = 2 * size_of::<usize>() + size_of::<RefCell<sllwt_rc_refcell::Node<T>>>();
+    MemoryReport { elements: length, element_bytes: size_of::<T>(), allocated_bytes: length * node_bytes }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_overhead_per_element() {
+        let report: MemoryReport = MemoryReport { elements: 0, element_bytes: 8, allocated_bytes: 0 };
+        assert_eq!(report.overhead_per_element(), 0.0);
+    }
+
+    #[test]
+    fn test_overhead_per_element() {
+        let report: MemoryReport = MemoryReport { elements: 4, element_bytes: 8, allocated_bytes: 64 };
+        assert_eq!(report.overhead_bytes(), 32);
+        assert_eq!(report.overhead_per_element(), 8.0);
+    }
+
+    #[test]
+    fn test_rc_refcell_report() {
+        let report: MemoryReport = rc_refcell_report::<u64>(10);
+        assert_eq!(report.elements, 10);
+        assert!(report.overhead_per_element() >= (3 * size_of::<usize>()) as f64);
+    }
+}
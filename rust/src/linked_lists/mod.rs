@@ -1,7 +1,23 @@
+// The lists write `return x;` and give each collection an inherent `new`
+// without a `Default` impl, as the original ones already did.
+#![allow(clippy::needless_return, clippy::new_without_default)]
+
 // pub mod sllnt_rc_refcell;
+// The original list compares against `None` and literal bools.
+#[allow(clippy::partialeq_to_none, clippy::bool_comparison, clippy::unnecessary_unwrap, clippy::bool_assert_comparison)]
 pub mod sllwt_rc_refcell;
+pub mod csll_rc_refcell;
+mod psll;
+pub mod psll_rc;
+pub mod psll_arc;
+pub mod sosll_rc_refcell;
+pub mod xdllwt_raw;
+pub mod usllnt_box;
+pub mod skiplist_rc_refcell;
+pub mod olsm_rc_refcell;
+pub mod memory_report;
 // pub mod dllnt_rc_refcell;
-// pub mod dllwt_rc_refcell;
+pub mod dllwt_rc_refcell;
 
 // pub mod dllwt_box;
 // pub mod dllwt_arc_mutex;
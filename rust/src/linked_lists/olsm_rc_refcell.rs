@@ -0,0 +1,328 @@
+use std::cell::{RefCell, RefMut};
+use std::fmt::Error;
+use std::rc::Rc;
+
+use crate::arrays::sparse_matrix::{CooMatrix, Scalar};
+
+type Link<T> = Option<Rc<RefCell<Node<T>>>>;
+
+/// One non-zero entry. It sits in two lists at once: `right` is the next
+/// entry in its row and `down` the next entry in its column.
+#[derive(Debug)]
+pub struct Node<T> {
+    pub row: usize,
+    pub column: usize,
+    pub data: T,
+    pub right: Link<T>,
+    pub down: Link<T>,
+}
+
+fn right<T>(node: &mut Node<T>) -> &mut Link<T> {
+    &mut node.right
+}
+
+fn down<T>(node: &mut Node<T>) -> &mut Link<T> {
+    &mut node.down
+}
+
+/// Walks one row or column list to the first node whose `key` is at least
+/// `target`. Returns that node and the one before it.
+fn seek<T>(head: &Link<T>, target: usize, key: fn(&Node<T>) -> usize, next: fn(&mut Node<T>) -> &mut Link<T>) -> (Link<T>, Link<T>) {
+    let mut previous: Link<T> = None;
+    let mut current: Link<T> = head.clone();
+
+    while let Some(node) = current.clone() {
+        if key(&node.borrow()) >= target {
+            break;
+        }
+        current = next(&mut node.borrow_mut()).clone();
+        previous = Some(node);
+    }
+
+    return (previous, current);
+}
+
+/// Points the link after `previous` (or `head`) at `node`, and `node` at
+/// whatever followed.
+fn link_after<T>(head: &mut Link<T>, previous: &Link<T>, node: &Rc<RefCell<Node<T>>>, next: fn(&mut Node<T>) -> &mut Link<T>) {
+    let slot_owner: Option<Rc<RefCell<Node<T>>>> = previous.clone();
+    match slot_owner {
+        Some(previous) => {
+            let following: Link<T> = next(&mut previous.borrow_mut()).replace(node.clone());
+            *next(&mut node.borrow_mut()) = following;
+        }
+        None => {
+            let following: Link<T> = head.replace(node.clone());
+            *next(&mut node.borrow_mut()) = following;
+        }
+    }
+}
+
+/// Removes the node after `previous` (or the head) from one list.
+fn unlink_after<T>(head: &mut Link<T>, previous: &Link<T>, next: fn(&mut Node<T>) -> &mut Link<T>) {
+    let removed: Link<T> = match previous {
+        Some(previous) => next(&mut previous.borrow_mut()).take(),
+        None => head.take(),
+    };
+    let removed: Rc<RefCell<Node<T>>> = removed.expect("the node being unlinked");
+    let following: Link<T> = next(&mut removed.borrow_mut()).take();
+
+    match previous {
+        Some(previous) => *next(&mut previous.borrow_mut()) = following,
+        None => *head = following,
+    }
+}
+
+/// A sparse matrix as an orthogonal list: every row and every column is a
+/// sorted singly linked list of the non-zero nodes in it, so each node is
+/// shared by exactly two lists.
+///
+/// It is slower than the compressed forms in `arrays::sparse_matrix` but
+/// inserts and removes in place, which makes the trade-off easy to show.
+pub struct OrthogonalMatrix<T> {
+    rows: usize,
+    columns: usize,
+    row_heads: Vec<Link<T>>,
+    column_heads: Vec<Link<T>>,
+    length: usize,
+}
+
+impl<T: Scalar> OrthogonalMatrix<T> {
+    pub fn new(rows: usize, columns: usize) -> Self {
+        OrthogonalMatrix { rows, columns, row_heads: vec![None; rows], column_heads: vec![None; columns], length: 0 }
+    }
+
+    pub fn from(matrix: &CooMatrix<T>) -> Self {
+        let (rows, columns): (usize, usize) = matrix.shape();
+        let mut orthogonal: OrthogonalMatrix<T> = OrthogonalMatrix::new(rows, columns);
+
+        for (row, column, data) in matrix.to_csr().iter() {
+            let _ = orthogonal.set(row, column, data);
+        }
+
+        return orthogonal;
+    }
+
+    pub fn shape(&self) -> (usize, usize) {
+        return (self.rows, self.columns);
+    }
+
+    /// The number of stored entries.
+    pub fn len(&self) -> usize {
+        return self.length;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.length == 0;
+    }
+
+    /// Stores `data` at `(row, column)` and returns what was there before.
+    /// Storing zero removes the entry.
+    pub fn set(&mut self, row: usize, column: usize, data: T) -> Result<Option<T>, Error> {
+        if row >= self.rows || column >= self.columns {
+            return Err(Error);
+        }
+        if data == T::default() {
+            return Ok(self.remove(row, column));
+        }
+
+        let (row_previous, row_current): (Link<T>, Link<T>) = seek(&self.row_heads[row], column, |node| node.column, right);
+        if let Some(node) = &row_current {
+            if node.borrow().column == column {
+                return Ok(Some(std::mem::replace(&mut node.borrow_mut().data, data)));
+            }
+        }
+
+        let node: Rc<RefCell<Node<T>>> = Rc::new(RefCell::new(Node { row, column, data, right: None, down: None }));
+        link_after(&mut self.row_heads[row], &row_previous, &node, right);
+
+        let (column_previous, _): (Link<T>, Link<T>) = seek(&self.column_heads[column], row, |node| node.row, down);
+        link_after(&mut self.column_heads[column], &column_previous, &node, down);
+
+        self.length += 1;
+        return Ok(None);
+    }
+
+    pub fn get(&self, row: usize, column: usize) -> Result<T, Error> {
+        if row >= self.rows || column >= self.columns {
+            return Err(Error);
+        }
+
+        let (_, current): (Link<T>, Link<T>) = seek(&self.row_heads[row], column, |node| node.column, right);
+        match current {
+            Some(node) if node.borrow().column == column => Ok(node.borrow().data),
+            _ => Ok(T::default()),
+        }
+    }
+
+    pub fn remove(&mut self, row: usize, column: usize) -> Option<T> {
+        if row >= self.rows || column >= self.columns {
+            return None;
+        }
+
+        let (row_previous, row_current): (Link<T>, Link<T>) = seek(&self.row_heads[row], column, |node| node.column, right);
+        let node: Rc<RefCell<Node<T>>> = match row_current {
+            Some(node) if node.borrow().column == column => node,
+            _ => return None,
+        };
+        let (column_previous, _): (Link<T>, Link<T>) = seek(&self.column_heads[column], row, |node| node.row, down);
+
+        unlink_after(&mut self.row_heads[row], &row_previous, right);
+        unlink_after(&mut self.column_heads[column], &column_previous, down);
+        self.length -= 1;
+
+        let data: T = node.borrow().data;
+        return Some(data);
+    }
+
+    /// The non-zeros of one row as `(column, data)`, left to right.
+    pub fn row(&self, row: usize) -> Result<Vec<(usize, T)>, Error> {
+        if row >= self.rows {
+            return Err(Error);
+        }
+        return Ok(self.row_entries(row));
+    }
+
+    fn row_entries(&self, row: usize) -> Vec<(usize, T)> {
+        let mut entries: Vec<(usize, T)> = Vec::new();
+        let mut current: Link<T> = self.row_heads[row].clone();
+
+        while let Some(node) = current {
+            entries.push((node.borrow().column, node.borrow().data));
+            current = node.borrow().right.clone();
+        }
+
+        return entries;
+    }
+
+    /// The non-zeros of one column as `(row, data)`, top to bottom.
+    pub fn column(&self, column: usize) -> Result<Vec<(usize, T)>, Error> {
+        if column >= self.columns {
+            return Err(Error);
+        }
+
+        let mut entries: Vec<(usize, T)> = Vec::new();
+        let mut current: Link<T> = self.column_heads[column].clone();
+
+        while let Some(node) = current {
+            entries.push((node.borrow().row, node.borrow().data));
+            current = node.borrow().down.clone();
+        }
+
+        return Ok(entries);
+    }
+
+    pub fn multiply(&self, vector: &[T]) -> Result<Vec<T>, Error> {
+        if vector.len() != self.columns {
+            return Err(Error);
+        }
+
+        let result: Vec<T> = (0..self.rows)
+            .map(|row| self.row_entries(row).into_iter().fold(T::default(), |sum, (column, data)| sum + data * vector[column]))
+            .collect();
+        return Ok(result);
+    }
+
+    pub fn to_coo(&self) -> CooMatrix<T> {
+        let mut matrix: CooMatrix<T> = CooMatrix::new(self.rows, self.columns);
+
+        for row in 0..self.rows {
+            for (column, data) in self.row_entries(row) {
+                let _ = matrix.push(row, column, data);
+            }
+        }
+
+        return matrix;
+    }
+}
+
+impl<T> Drop for OrthogonalMatrix<T> {
+    /// Cuts every link along the rows so no node's drop recurses into the
+    /// next one; the column heads then hold the last references.
+    fn drop(&mut self) {
+        for head in self.row_heads.iter_mut() {
+            let mut current: Link<T> = head.take();
+            while let Some(node) = current {
+                let mut node: RefMut<'_, Node<T>> = node.borrow_mut();
+                node.down = None;
+                current = node.right.take();
+            }
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arrays::sparse_matrix::CsrMatrix;
+
+    #[test]
+    fn test_orthogonal_matrix_new() {
+        let matrix: OrthogonalMatrix<i64> = OrthogonalMatrix::new(3, 4);
+        assert!(matrix.is_empty());
+        assert_eq!(matrix.shape(), (3, 4));
+        assert_eq!(matrix.get(2, 3), Ok(0));
+        assert!(matrix.get(3, 0).is_err());
+    }
+
+    #[test]
+    fn test_set_keeps_rows_and_columns_sorted() {
+        let mut matrix: OrthogonalMatrix<i64> = OrthogonalMatrix::new(3, 3);
+        assert_eq!(matrix.set(1, 2, 6), Ok(None));
+        assert_eq!(matrix.set(1, 0, 4), Ok(None));
+        assert_eq!(matrix.set(0, 2, 3), Ok(None));
+        assert_eq!(matrix.set(2, 2, 9), Ok(None));
+        assert_eq!(matrix.set(1, 1, 5), Ok(None));
+        assert_eq!(matrix.len(), 5);
+
+        assert_eq!(matrix.row(1), Ok(vec![(0, 4), (1, 5), (2, 6)]));
+        assert_eq!(matrix.column(2), Ok(vec![(0, 3), (1, 6), (2, 9)]));
+        assert!(matrix.row(3).is_err());
+        assert!(matrix.column(3).is_err());
+        assert_eq!(matrix.set(1, 1, 50), Ok(Some(5)));
+        assert_eq!(matrix.get(1, 1), Ok(50));
+        assert!(matrix.set(3, 0, 1).is_err());
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut matrix: OrthogonalMatrix<i64> = OrthogonalMatrix::new(2, 2);
+        let _ = matrix.set(0, 0, 1);
+        let _ = matrix.set(0, 1, 2);
+        let _ = matrix.set(1, 1, 3);
+
+        assert_eq!(matrix.remove(0, 1), Some(2));
+        assert_eq!(matrix.remove(0, 1), None);
+        assert_eq!(matrix.row(0), Ok(vec![(0, 1)]));
+        assert_eq!(matrix.column(1), Ok(vec![(1, 3)]));
+
+        assert_eq!(matrix.set(0, 0, 0), Ok(Some(1)));
+        assert_eq!(matrix.len(), 1);
+        assert_eq!(matrix.column(0), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_matches_compressed_forms() {
+        let coo: CooMatrix<i64> = CooMatrix::from(3, 4, vec![(0, 0, 1), (0, 2, 2), (2, 1, 3), (2, 3, 4), (0, 2, 5)]).unwrap();
+        let matrix: OrthogonalMatrix<i64> = OrthogonalMatrix::from(&coo);
+        let csr: CsrMatrix<i64> = coo.to_csr();
+
+        assert_eq!(matrix.len(), csr.non_zeros());
+        assert_eq!(matrix.get(0, 2), Ok(7));
+        assert_eq!(matrix.to_coo().to_csr(), csr);
+        assert_eq!(matrix.multiply(&[1, 2, 3, 4]), csr.multiply(&[1, 2, 3, 4]));
+        assert!(matrix.multiply(&[1]).is_err());
+    }
+
+    #[test]
+    fn test_drop_long_row() {
+        let mut matrix: OrthogonalMatrix<i64> = OrthogonalMatrix::new(1, 200_000);
+        for column in (0..200_000).rev() {
+            let _ = matrix.set(0, column, 1);
+        }
+        assert_eq!(matrix.len(), 200_000);
+        drop(matrix);
+    }
+}
@@ -0,0 +1,242 @@
+/// Defines `Node`, `PersistentList` and `Iter` over the shared pointer
+/// `$pointer`, which must be `Rc` or `Arc` and in scope at the call site.
+/// Any attributes before it, such as doc comments, go on `PersistentList`.
+/// Both variants are otherwise identical, so `psll_rc` and `psll_arc` only
+/// differ in which pointer they pass and in their own extra tests.
+macro_rules! persistent_list {
+    ($(#[$attribute:meta])* $pointer:ident) => {
+        pub struct Node<T> {
+            pub data: T,
+            pub next: Option<$pointer<Node<T>>>,
+        }
+
+        $(#[$attribute])*
+        pub struct PersistentList<T> {
+            head: Option<$pointer<Node<T>>>,
+            length: usize,
+        }
+
+        impl<T> PersistentList<T> {
+            pub fn new() -> Self {
+                PersistentList { head: None, length: 0 }
+            }
+
+            pub fn from(data_vec: Vec<T>) -> Self {
+                let mut list: PersistentList<T> = PersistentList::new();
+
+                for data in data_vec.into_iter().rev() {
+                    list = list.push_front(data);
+                }
+
+                return list;
+            }
+
+            pub fn push_front(&self, data: T) -> Self {
+                let node: Node<T> = Node { data, next: self.head.clone() };
+                PersistentList { head: Some($pointer::new(node)), length: self.length + 1 }
+            }
+
+            pub fn front(&self) -> Option<&T> {
+                self.head.as_ref().map(|node| &node.data)
+            }
+
+            pub fn tail(&self) -> Result<Self, Error> {
+                match &self.head {
+                    Some(node) => Ok(PersistentList { head: node.next.clone(), length: self.length - 1 }),
+                    None => Err(Error),
+                }
+            }
+
+            pub fn iter(&self) -> Iter<'_, T> {
+                Iter { next: self.head.as_deref() }
+            }
+
+            pub fn reverse(&self) -> Self
+            where
+                T: Clone,
+            {
+                let mut list: PersistentList<T> = PersistentList::new();
+
+                for data in self.iter() {
+                    list = list.push_front(data.clone());
+                }
+
+                return list;
+            }
+
+            /// Copies the nodes of `self` once and points the last copy at
+            /// `other`, so the result shares all of `other`.
+            pub fn append(&self, other: &Self) -> Self
+            where
+                T: Clone,
+            {
+                let front: Vec<&T> = self.iter().collect();
+                let mut list: PersistentList<T> = other.clone();
+
+                for data in front.into_iter().rev() {
+                    list = list.push_front(data.clone());
+                }
+
+                return list;
+            }
+
+            pub fn len(&self) -> usize {
+                return self.length;
+            }
+
+            pub fn is_empty(&self) -> bool {
+                return self.length == 0;
+            }
+        }
+
+        impl<T> Clone for PersistentList<T> {
+            fn clone(&self) -> Self {
+                PersistentList { head: self.head.clone(), length: self.length }
+            }
+        }
+
+        impl<T> Drop for PersistentList<T> {
+            fn drop(&mut self) {
+                // Only free nodes that no other version still points at, and
+                // do it in a loop rather than through the recursive drop.
+                // `into_inner` hands the node to exactly one of several
+                // threads dropping their last references at once, where
+                // `try_unwrap` could fail for all of them and leave the tail
+                // to the recursive drop.
+                let mut current_node: Option<$pointer<Node<T>>> = self.head.take();
+
+                while let Some(node) = current_node {
+                    match $pointer::into_inner(node) {
+                        Some(mut node) => current_node = node.next.take(),
+                        None => break,
+                    }
+                }
+            }
+        }
+
+        impl<T: Debug> Debug for PersistentList<T> {
+            fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+                f.debug_list().entries(self.iter()).finish()
+            }
+        }
+
+        impl<T: PartialEq> PartialEq for PersistentList<T> {
+            fn eq(&self, other: &Self) -> bool {
+                return self.length == other.length && self.iter().eq(other.iter());
+            }
+        }
+
+        pub struct Iter<'a, T> {
+            next: Option<&'a Node<T>>,
+        }
+
+        impl<'a, T> Iterator for Iter<'a, T> {
+            type Item = &'a T;
+
+            fn next(&mut self) -> Option<&'a T> {
+                let node: &Node<T> = self.next?;
+                self.next = node.next.as_deref();
+                return Some(&node.data);
+            }
+        }
+
+        #[cfg(test)]
+        mod shared_tests {
+            use super::*;
+
+            fn collect(list: &PersistentList<i64>) -> Vec<i64> {
+                list.iter().cloned().collect()
+            }
+
+            #[test]
+            fn test_persistent_list_new() {
+                let list: PersistentList<i64> = PersistentList::new();
+                assert!(list.head.is_none());
+                assert!(list.is_empty());
+                assert_eq!(list.len(), 0);
+            }
+
+            #[test]
+            fn test_persistent_list_from() {
+                let list: PersistentList<i64> = PersistentList::from(vec![8, 56, 19]);
+                assert_eq!(collect(&list), vec![8, 56, 19]);
+                assert_eq!(list.len(), 3);
+                assert_eq!(list.front(), Some(&8));
+            }
+
+            #[test]
+            fn test_push_front_keeps_old_version() {
+                let list: PersistentList<i64> = PersistentList::from(vec![2, 3]);
+                let pushed: PersistentList<i64> = list.push_front(1);
+
+                assert_eq!(collect(&list), vec![2, 3]);
+                assert_eq!(collect(&pushed), vec![1, 2, 3]);
+                assert!($pointer::ptr_eq(list.head.as_ref().unwrap(), pushed.head.as_ref().unwrap().next.as_ref().unwrap()));
+            }
+
+            #[test]
+            fn test_empty_tail() {
+                let list: PersistentList<i64> = PersistentList::new();
+                assert!(list.tail().is_err());
+            }
+
+            #[test]
+            fn test_tail() {
+                let list: PersistentList<i64> = PersistentList::from(vec![1, 2, 3]);
+                let tail: PersistentList<i64> = list.tail().unwrap();
+
+                assert_eq!(collect(&tail), vec![2, 3]);
+                assert_eq!(tail.len(), 2);
+                assert_eq!(collect(&list), vec![1, 2, 3]);
+            }
+
+            #[test]
+            fn test_tail_down_to_empty() {
+                let mut list: PersistentList<i64> = PersistentList::from(vec![1, 2]);
+                while let Ok(tail) = list.tail() {
+                    list = tail;
+                }
+                assert!(list.is_empty());
+                assert_eq!(list.front(), None);
+            }
+
+            #[test]
+            fn test_reverse() {
+                let list: PersistentList<i64> = PersistentList::from(vec![1, 2, 3]);
+                assert_eq!(collect(&list.reverse()), vec![3, 2, 1]);
+                assert_eq!(collect(&PersistentList::new().reverse()), Vec::<i64>::new());
+            }
+
+            #[test]
+            fn test_append_shares_right_list() {
+                let left: PersistentList<i64> = PersistentList::from(vec![1, 2]);
+                let right: PersistentList<i64> = PersistentList::from(vec![3, 4]);
+                let joined: PersistentList<i64> = left.append(&right);
+
+                assert_eq!(collect(&joined), vec![1, 2, 3, 4]);
+                assert_eq!(joined.len(), 4);
+                assert_eq!(collect(&left), vec![1, 2]);
+
+                let shared: PersistentList<i64> = joined.tail().unwrap().tail().unwrap();
+                assert!($pointer::ptr_eq(shared.head.as_ref().unwrap(), right.head.as_ref().unwrap()));
+                assert_eq!(collect(&PersistentList::new().append(&right)), vec![3, 4]);
+            }
+
+            #[test]
+            fn test_drop_keeps_shared_nodes() {
+                let list: PersistentList<i64> = PersistentList::from(vec![1, 2, 3]);
+                let pushed: PersistentList<i64> = list.push_front(0);
+                drop(pushed);
+                assert_eq!(collect(&list), vec![1, 2, 3]);
+            }
+
+            #[test]
+            fn test_long_drop() {
+                let list: PersistentList<i64> = PersistentList::from((0..200_000).collect());
+                drop(list);
+            }
+        }
+    };
+}
+
+pub(crate) use persistent_list;
@@ -0,0 +1,51 @@
+use std::fmt::{Debug, Error, Formatter};
+use std::sync::Arc;
+
+use super::psll::persistent_list;
+
+persistent_list!(
+    /// The `Arc` version of `psll_rc::PersistentList`, so versions can be
+    /// handed to other threads while still sharing their nodes.
+    Arc
+);
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect(list: &PersistentList<i64>) -> Vec<i64> {
+        list.iter().cloned().collect()
+    }
+
+    #[test]
+    fn test_share_between_threads() {
+        let list: PersistentList<i64> = PersistentList::from(vec![1, 2, 3]);
+        let snapshot: PersistentList<i64> = list.clone();
+        let handle = std::thread::spawn(move || snapshot.push_front(0).iter().sum::<i64>());
+
+        assert_eq!(handle.join().unwrap(), 6);
+        assert_eq!(collect(&list), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_concurrent_drops_of_shared_tail() {
+        for _ in 0..20 {
+            let shared: PersistentList<i64> = PersistentList::from((0..100_000).collect());
+            let versions: Vec<PersistentList<i64>> = (0..2).map(|data| shared.push_front(data)).collect();
+            drop(shared);
+
+            let barrier: std::sync::Barrier = std::sync::Barrier::new(2);
+            std::thread::scope(|scope| {
+                for version in versions {
+                    let barrier: &std::sync::Barrier = &barrier;
+                    scope.spawn(move || {
+                        barrier.wait();
+                        drop(version);
+                    });
+                }
+            });
+        }
+    }
+}
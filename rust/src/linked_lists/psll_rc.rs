@@ -0,0 +1,11 @@
+use std::fmt::{Debug, Error, Formatter};
+use std::rc::Rc;
+
+use super::psll::persistent_list;
+
+persistent_list!(
+    /// An immutable singly linked list. Nodes are never mutated once built,
+    /// so every version of the list can share its tail with the versions it
+    /// was made from and older versions stay valid as snapshots.
+    Rc
+);
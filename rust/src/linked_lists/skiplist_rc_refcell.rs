@@ -0,0 +1,513 @@
+use std::cmp::Ordering;
+use std::fmt::{Debug, Error, Formatter};
+use std::ops::{Bound, RangeBounds};
+use std::rc::Rc;
+use std::cell::RefCell;
+
+pub const MAX_LEVEL: usize = 32;
+
+/// A small xorshift generator for node levels. Each extra level is added
+/// with probability 1/2, and the same seed always gives the same levels, so
+/// a list's shape can be reproduced exactly.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct LevelGenerator {
+    state: u64,
+}
+
+impl LevelGenerator {
+    pub fn new(seed: u64) -> Self {
+        // xorshift never leaves the all-zero state, so nudge it away.
+        LevelGenerator { state: if seed == 0 { 0x9E37_79B9_7F4A_7C15 } else { seed } }
+    }
+
+    pub fn next_level(&mut self) -> usize {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+
+        let level: usize = self.state.trailing_ones() as usize + 1;
+        return level.min(MAX_LEVEL);
+    }
+}
+
+type Link<K, V> = Rc<RefCell<Node<K, V>>>;
+
+/// A skip list node. `forward[i]` is the next node on level `i`, and
+/// `width[i]` is how many level 0 steps that link skips over. The head of
+/// the list is a node without a key or value.
+pub struct Node<K, V> {
+    pub key: Option<K>,
+    pub value: Option<V>,
+    pub forward: Vec<Option<Link<K, V>>>,
+    pub width: Vec<usize>,
+}
+
+impl<K, V> Node<K, V> {
+    pub fn new() -> Node<K, V> {
+        Node { key: None, value: None, forward: vec![None; MAX_LEVEL], width: vec![0; MAX_LEVEL] }
+    }
+
+    pub fn from(key: K, value: V, level: usize) -> Node<K, V> {
+        Node { key: Some(key), value: Some(value), forward: vec![None; level], width: vec![0; level] }
+    }
+}
+
+/// An ordered map kept as a skip list, giving expected O(log n) search,
+/// insert and remove, plus rank and select through the link widths.
+pub struct SkipList<K, V> {
+    pub head: Rc<RefCell<Node<K, V>>>,
+    pub level: usize,
+    pub length: usize,
+    pub generator: LevelGenerator,
+}
+
+impl<K, V> SkipList<K, V>
+where
+    K: Ord + Clone,
+    V: Clone,
+{
+    pub fn new() -> Self {
+        SkipList::with_seed(0)
+    }
+
+    pub fn with_seed(seed: u64) -> Self {
+        SkipList {
+            head: Rc::new(RefCell::new(Node::new())),
+            level: 1,
+            length: 0,
+            generator: LevelGenerator::new(seed),
+        }
+    }
+
+    pub fn from(data_vec: Vec<(K, V)>) -> Self {
+        let mut list: SkipList<K, V> = SkipList::new();
+
+        for (key, value) in data_vec {
+            list.insert(key, value);
+        }
+
+        return list;
+    }
+
+    /// For each level, the last node whose key is below `key`, along with
+    /// that node's rank (the head has rank 0, the first element rank 1).
+    fn find_path(&self, key: &K) -> (Vec<Link<K, V>>, Vec<usize>) {
+        let mut update: Vec<Link<K, V>> = vec![self.head.clone(); MAX_LEVEL];
+        let mut rank: Vec<usize> = vec![0; MAX_LEVEL];
+        let mut current_node: Link<K, V> = self.head.clone();
+        let mut traversed: usize = 0;
+
+        for level in (0..self.level).rev() {
+            loop {
+                let next: Option<Link<K, V>> = current_node.borrow().forward[level].clone();
+                match next {
+                    Some(node) if node.borrow().key.as_ref().unwrap() < key => {
+                        traversed += current_node.borrow().width[level];
+                        current_node = node;
+                    }
+                    _ => break,
+                }
+            }
+            update[level] = current_node.clone();
+            rank[level] = traversed;
+        }
+
+        return (update, rank);
+    }
+
+    fn next_matching(update: &[Link<K, V>], key: &K) -> Option<Link<K, V>> {
+        let next: Option<Link<K, V>> = update[0].borrow().forward[0].clone();
+        match next {
+            Some(node) if node.borrow().key.as_ref() == Some(key) => Some(node),
+            _ => None,
+        }
+    }
+
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let (update, rank): (Vec<Link<K, V>>, Vec<usize>) = self.find_path(&key);
+
+        if let Some(node) = SkipList::next_matching(&update, &key) {
+            return node.borrow_mut().value.replace(value);
+        }
+
+        let level: usize = self.generator.next_level();
+        if level > self.level {
+            for index in self.level..level {
+                self.head.borrow_mut().width[index] = self.length;
+            }
+            self.level = level;
+        }
+
+        let new_node: Link<K, V> = Rc::new(RefCell::new(Node::from(key, value, level)));
+        for index in 0..level {
+            let mut previous = update[index].borrow_mut();
+            let mut node = new_node.borrow_mut();
+            let skipped: usize = rank[0] - rank[index];
+
+            node.forward[index] = previous.forward[index].take();
+            node.width[index] = previous.width[index] - skipped;
+            previous.forward[index] = Some(new_node.clone());
+            previous.width[index] = skipped + 1;
+        }
+        for (index, previous) in update.iter().enumerate().take(self.level).skip(level) {
+            previous.borrow_mut().width[index] += 1;
+        }
+
+        self.length += 1;
+        return None;
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let (update, _): (Vec<Link<K, V>>, Vec<usize>) = self.find_path(key);
+        let target: Link<K, V> = SkipList::next_matching(&update, key)?;
+
+        for (index, previous) in update.iter().enumerate().take(self.level) {
+            let mut previous = previous.borrow_mut();
+            let links_to_target: bool = match &previous.forward[index] {
+                Some(node) => Rc::ptr_eq(node, &target),
+                None => false,
+            };
+
+            if links_to_target {
+                let mut node = target.borrow_mut();
+                previous.width[index] += node.width[index];
+                previous.width[index] -= 1;
+                previous.forward[index] = node.forward[index].take();
+            } else {
+                previous.width[index] -= 1;
+            }
+        }
+
+        while self.level > 1 && self.head.borrow().forward[self.level - 1].is_none() {
+            self.level -= 1;
+        }
+
+        self.length -= 1;
+        let value: Option<V> = target.borrow_mut().value.take();
+        return value;
+    }
+
+    pub fn get(&self, key: &K) -> Option<V> {
+        let (update, _): (Vec<Link<K, V>>, Vec<usize>) = self.find_path(key);
+        let node: Link<K, V> = SkipList::next_matching(&update, key)?;
+        let value: Option<V> = node.borrow().value.clone();
+        return value;
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        let (update, _): (Vec<Link<K, V>>, Vec<usize>) = self.find_path(key);
+        return SkipList::next_matching(&update, key).is_some();
+    }
+
+    /// The zero-based position of `key` in sorted order.
+    pub fn rank(&self, key: &K) -> Option<usize> {
+        let (update, rank): (Vec<Link<K, V>>, Vec<usize>) = self.find_path(key);
+        SkipList::next_matching(&update, key)?;
+        return Some(rank[0]);
+    }
+
+    /// The entry at zero-based position `index` in sorted order.
+    pub fn select(&self, index: usize) -> Option<(K, V)> {
+        if index >= self.length {
+            return None;
+        }
+
+        let target: usize = index + 1;
+        let mut current_node: Link<K, V> = self.head.clone();
+        let mut traversed: usize = 0;
+
+        for level in (0..self.level).rev() {
+            loop {
+                let next: Option<Link<K, V>> = current_node.borrow().forward[level].clone();
+                let width: usize = current_node.borrow().width[level];
+                match next {
+                    Some(node) if traversed + width <= target => {
+                        traversed += width;
+                        current_node = node;
+                    }
+                    _ => break,
+                }
+            }
+            if traversed == target {
+                break;
+            }
+        }
+
+        return SkipList::entry(&current_node);
+    }
+
+    pub fn first(&self) -> Option<(K, V)> {
+        let next: Option<Link<K, V>> = self.head.borrow().forward[0].clone();
+        return SkipList::entry(&next?);
+    }
+
+    pub fn last(&self) -> Option<(K, V)> {
+        if self.length == 0 {
+            return None;
+        }
+
+        return self.select(self.length - 1);
+    }
+
+    pub fn iter(&self) -> Iter<K, V> {
+        self.range(..)
+    }
+
+    pub fn range<R: RangeBounds<K>>(&self, range: R) -> Iter<K, V> {
+        let start: Option<Link<K, V>> = match range.start_bound() {
+            Bound::Unbounded => self.head.borrow().forward[0].clone(),
+            Bound::Included(key) => {
+                let (update, _): (Vec<Link<K, V>>, Vec<usize>) = self.find_path(key);
+                let next: Option<Link<K, V>> = update[0].borrow().forward[0].clone();
+                next
+            }
+            Bound::Excluded(key) => {
+                let (update, _): (Vec<Link<K, V>>, Vec<usize>) = self.find_path(key);
+                let mut next: Option<Link<K, V>> = update[0].borrow().forward[0].clone();
+                if let Some(node) = next.clone() {
+                    if node.borrow().key.as_ref() == Some(key) {
+                        next = node.borrow().forward[0].clone();
+                    }
+                }
+                next
+            }
+        };
+
+        let end: Bound<K> = match range.end_bound() {
+            Bound::Included(key) => Bound::Included(key.clone()),
+            Bound::Excluded(key) => Bound::Excluded(key.clone()),
+            Bound::Unbounded => Bound::Unbounded,
+        };
+
+        Iter { current: start, end }
+    }
+
+    fn entry(node: &Link<K, V>) -> Option<(K, V)> {
+        let node = node.borrow();
+        Some((node.key.clone()?, node.value.clone()?))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.length == 0;
+    }
+}
+
+impl<K, V> Drop for SkipList<K, V> {
+    fn drop(&mut self) {
+        // Clear every node's links while walking level 0, so nodes are freed
+        // one at a time rather than by a chain of recursive drops.
+        let mut current_node: Option<Link<K, V>> = self.head.borrow_mut().forward[0].take();
+        self.head.borrow_mut().forward.clear();
+
+        while let Some(node) = current_node {
+            let mut node = node.borrow_mut();
+            current_node = node.forward[0].take();
+            node.forward.clear();
+        }
+    }
+}
+
+impl<K, V> Debug for SkipList<K, V>
+where
+    K: Ord + Clone + Debug,
+    V: Clone + Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// Walks level 0 in key order, yielding copies of each entry.
+pub struct Iter<K, V> {
+    current: Option<Link<K, V>>,
+    end: Bound<K>,
+}
+
+impl<K, V> Iterator for Iter<K, V>
+where
+    K: Ord + Clone,
+    V: Clone,
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<(K, V)> {
+        let node: Link<K, V> = self.current.take()?;
+        let (key, value): (K, V) = SkipList::entry(&node)?;
+
+        let in_range: bool = match &self.end {
+            Bound::Included(end) => key.cmp(end) != Ordering::Greater,
+            Bound::Excluded(end) => key < *end,
+            Bound::Unbounded => true,
+        };
+        if !in_range {
+            return None;
+        }
+
+        self.current = node.borrow().forward[0].clone();
+        return Some((key, value));
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(list: &SkipList<i64, String>) -> Vec<i64> {
+        list.iter().map(|(key, _)| key).collect()
+    }
+
+    /// Checks that every link's width matches the number of level 0 steps.
+    fn assert_widths(list: &SkipList<i64, String>) {
+        let positions: Vec<i64> = keys(list);
+        let rank_of = |node: &Link<i64, String>| -> usize {
+            match node.borrow().key {
+                Some(key) => positions.iter().position(|k| *k == key).unwrap() + 1,
+                None => 0,
+            }
+        };
+
+        let mut nodes: Vec<Link<i64, String>> = vec![list.head.clone()];
+        let mut current_node: Option<Link<i64, String>> = list.head.borrow().forward[0].clone();
+        while let Some(node) = current_node {
+            current_node = node.borrow().forward[0].clone();
+            nodes.push(node);
+        }
+
+        for node in &nodes {
+            let levels: usize = node.borrow().forward.len().min(list.level);
+            for level in 0..levels {
+                if let Some(next) = node.borrow().forward[level].clone() {
+                    assert_eq!(node.borrow().width[level], rank_of(&next) - rank_of(node));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_level_generator_is_deterministic() {
+        let mut generator: LevelGenerator = LevelGenerator::new(42);
+        let mut same: LevelGenerator = LevelGenerator::new(42);
+        let levels: Vec<usize> = (0..100).map(|_| generator.next_level()).collect();
+        assert_eq!(levels, (0..100).map(|_| same.next_level()).collect::<Vec<usize>>());
+        assert!(levels.iter().all(|level| (1..=MAX_LEVEL).contains(level)));
+        assert!(levels.iter().any(|level| *level > 1));
+    }
+
+    #[test]
+    fn test_skip_list_new() {
+        let list: SkipList<i64, String> = SkipList::new();
+        assert!(list.is_empty());
+        assert_eq!(list.first(), None);
+        assert_eq!(list.last(), None);
+        assert_eq!(list.get(&1), None);
+    }
+
+    #[test]
+    fn test_insert_keeps_keys_sorted() {
+        let list: SkipList<i64, String> = SkipList::from(
+            vec![(5, "five".to_string()), (1, "one".to_string()), (3, "three".to_string())]
+        );
+        assert_eq!(keys(&list), vec![1, 3, 5]);
+        assert_eq!(list.get(&3), Some("three".to_string()));
+        assert_eq!(list.length, 3);
+    }
+
+    #[test]
+    fn test_insert_replaces_value() {
+        let mut list: SkipList<i64, String> = SkipList::new();
+        assert_eq!(list.insert(1, "a".to_string()), None);
+        assert_eq!(list.insert(1, "b".to_string()), Some("a".to_string()));
+        assert_eq!(list.get(&1), Some("b".to_string()));
+        assert_eq!(list.length, 1);
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut list: SkipList<i64, String> = SkipList::with_seed(7);
+        for key in 0..50 {
+            list.insert(key, key.to_string());
+        }
+
+        assert_eq!(list.remove(&10), Some("10".to_string()));
+        assert_eq!(list.remove(&10), None);
+        assert_eq!(list.remove(&100), None);
+        assert!(!list.contains_key(&10));
+        assert_eq!(list.length, 49);
+        assert_widths(&list);
+    }
+
+    #[test]
+    fn test_rank_and_select() {
+        let mut list: SkipList<i64, String> = SkipList::with_seed(3);
+        for key in (0..200).rev() {
+            list.insert(key * 2, key.to_string());
+        }
+        assert_widths(&list);
+
+        for index in 0..200 {
+            let key: i64 = index as i64 * 2;
+            assert_eq!(list.rank(&key), Some(index));
+            assert_eq!(list.select(index).map(|(key, _)| key), Some(key));
+        }
+        assert_eq!(list.rank(&3), None);
+        assert_eq!(list.select(200), None);
+    }
+
+    #[test]
+    fn test_first_and_last() {
+        let list: SkipList<i64, String> = SkipList::from(
+            vec![(9, "nine".to_string()), (-4, "minus four".to_string()), (2, "two".to_string())]
+        );
+        assert_eq!(list.first(), Some((-4, "minus four".to_string())));
+        assert_eq!(list.last(), Some((9, "nine".to_string())));
+    }
+
+    #[test]
+    fn test_range() {
+        let mut list: SkipList<i64, String> = SkipList::new();
+        for key in 0..10 {
+            list.insert(key, key.to_string());
+        }
+
+        let range_keys = |range: (Bound<i64>, Bound<i64>)| -> Vec<i64> {
+            list.range(range).map(|(key, _)| key).collect()
+        };
+        assert_eq!(range_keys((Bound::Included(3), Bound::Excluded(6))), vec![3, 4, 5]);
+        assert_eq!(range_keys((Bound::Excluded(3), Bound::Included(6))), vec![4, 5, 6]);
+        assert_eq!(range_keys((Bound::Included(8), Bound::Unbounded)), vec![8, 9]);
+        assert_eq!(range_keys((Bound::Included(20), Bound::Unbounded)), Vec::<i64>::new());
+        assert_eq!(list.range(..2).count(), 2);
+    }
+
+    #[test]
+    fn test_matches_btree_map() {
+        use std::collections::BTreeMap;
+
+        let mut list: SkipList<i64, String> = SkipList::with_seed(11);
+        let mut expected: BTreeMap<i64, String> = BTreeMap::new();
+        let mut generator: LevelGenerator = LevelGenerator::new(99);
+
+        for step in 0..1000 {
+            let key: i64 = (generator.next_level() as i64 * 37 + step * 13) % 97;
+            if step % 3 == 0 {
+                assert_eq!(list.remove(&key), expected.remove(&key));
+            } else {
+                assert_eq!(list.insert(key, step.to_string()), expected.insert(key, step.to_string()));
+            }
+        }
+
+        assert_eq!(list.iter().collect::<Vec<(i64, String)>>(), expected.into_iter().collect::<Vec<(i64, String)>>());
+        assert_widths(&list);
+    }
+
+    #[test]
+    fn test_long_drop() {
+        let mut list: SkipList<i64, String> = SkipList::new();
+        for key in 0..100_000 {
+            list.insert(key, String::new());
+        }
+        drop(list);
+    }
+}
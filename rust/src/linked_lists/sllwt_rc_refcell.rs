@@ -100,14 +100,16 @@ where
         self.ordered = false;
     }
 
-    pub fn pop_from_front(&mut self) -> Result<(), Error> {
+    pub fn pop_from_front(&mut self) -> Result<T, Error> {
         self.ordered = false;
 
         if self.head == None {
             return Err(Error);
         }
 
-        self.head = self.head.clone().unwrap().borrow().clone().next;
+        let head: Rc<RefCell<Node<T>>> = self.head.clone().unwrap();
+        let data: Option<T> = head.borrow().data.clone();
+        self.head = head.borrow().clone().next;
         if self.head == None {
             self.ordered = true;
             self.tail = None;
@@ -116,17 +118,19 @@ where
         self.length -= 1;
         self.update_indices();
 
-        return Ok(());
+        return data.ok_or(Error);
     }
 
-    pub fn unordered_pop_from_front(&mut self) -> Result<(), Error> {
+    pub fn unordered_pop_from_front(&mut self) -> Result<T, Error> {
         self.ordered = false;
         
         if self.head == None {
             return Err(Error);
         }
 
-        self.head = self.head.clone().unwrap().borrow().clone().next;
+        let head: Rc<RefCell<Node<T>>> = self.head.clone().unwrap();
+        let data: Option<T> = head.borrow().data.clone();
+        self.head = head.borrow().clone().next;
         if self.head == None {
             self.ordered = true;
             self.tail = None;
@@ -134,7 +138,7 @@ where
 
         self.length -= 1;
 
-        return Ok(());
+        return data.ok_or(Error);
     }
 
     pub fn into_front(self) -> Option<T> {
@@ -584,8 +588,9 @@ mod tests {
 
         let data_vec: Vec<i64> = vec![value_4, value, value_1, value_2, value_3];
         let mut function_list: LinkedList<i64> = LinkedList::from(data_vec);
-        let _ = function_list.pop_from_front();
+        let res = function_list.pop_from_front();
 
+        assert_eq!(res, Ok(value_4));
         assert_eq!(manual_list, function_list);
     }
 
@@ -602,7 +607,7 @@ mod tests {
         
         let data_vec: Vec<i64> = vec![2];
         let mut function_list: LinkedList<i64> = LinkedList::from(data_vec);
-        let _ = function_list.unordered_pop_from_front();
+        assert_eq!(function_list.unordered_pop_from_front(), Ok(2));
         assert_eq!(manual_list, function_list)
     }
 
@@ -643,7 +648,7 @@ mod tests {
 
         let data_vec: Vec<i64> = vec![value_4, value, value_1, value_2, value_3];
         let mut function_list: LinkedList<i64> = LinkedList::from(data_vec);
-        let _ = function_list.unordered_pop_from_front();
+        assert_eq!(function_list.unordered_pop_from_front(), Ok(value_4));
         assert_ne!(manual_list, function_list);
 
         function_list.update_indices();
@@ -0,0 +1,332 @@
+use std::fmt::Error;
+use std::rc::Rc;
+use std::cell::RefCell;
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Node<T> {
+    pub data: T,
+    pub count: usize,
+    pub next: Option<Rc<RefCell<Node<T>>>>,
+}
+
+impl<T> Node<T> {
+    pub fn from(data: T) -> Node<T> {
+        Node { data, count: 0, next: None }
+    }
+}
+
+/// How the list reorders itself after a successful lookup.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Strategy {
+    /// Move the found node to the head.
+    MoveToFront,
+    /// Swap the found node with the node before it.
+    Transpose,
+    /// Keep the nodes sorted by how often they have been found.
+    FrequencyCount,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct SelfOrganizingList<T> {
+    pub head: Option<Rc<RefCell<Node<T>>>>,
+    pub tail: Option<Rc<RefCell<Node<T>>>>,
+    pub length: usize,
+    pub strategy: Strategy,
+    pub comparisons: usize,
+}
+
+impl<T> SelfOrganizingList<T>
+where
+    T: Clone + std::cmp::PartialEq + std::fmt::Debug
+{
+    pub fn new(strategy: Strategy) -> Self {
+        SelfOrganizingList { head: None, tail: None, length: 0, strategy, comparisons: 0 }
+    }
+
+    pub fn from(strategy: Strategy, data_vec: Vec<T>) -> Self {
+        let mut list: SelfOrganizingList<T> = SelfOrganizingList::new(strategy);
+
+        for data in data_vec {
+            list.push_to_end(data);
+        }
+
+        return list;
+    }
+
+    pub fn push_to_front(&mut self, data: T) {
+        let mut node: Node<T> = Node::from(data);
+        node.next = self.head.take();
+        self.head = Some(Rc::new(RefCell::new(node)));
+        if self.tail.is_none() {
+            self.tail = self.head.clone();
+        }
+
+        self.length += 1;
+    }
+
+    pub fn push_to_end(&mut self, data: T) {
+        let new_node: Rc<RefCell<Node<T>>> = Rc::new(RefCell::new(Node::from(data)));
+
+        match self.tail.take() {
+            Some(tail) => tail.borrow_mut().next = Some(new_node.clone()),
+            None => self.head = Some(new_node.clone()),
+        }
+
+        self.tail = Some(new_node);
+        self.length += 1;
+    }
+
+    pub fn pop_from_front(&mut self) -> Result<T, Error> {
+        let head: Rc<RefCell<Node<T>>> = match self.head.take() {
+            Some(head) => head,
+            None => return Err(Error),
+        };
+
+        self.head = head.borrow_mut().next.take();
+        if self.head.is_none() {
+            self.tail = None;
+        }
+
+        self.length -= 1;
+        let data: T = head.borrow().data.clone();
+        return Ok(data);
+    }
+
+    /// Finds the first node holding `data` and then reorganises the list
+    /// according to `self.strategy`. Every value comparison is added to
+    /// `self.comparisons`.
+    pub fn find_node_by_value(&mut self, data: T) -> Result<Rc<RefCell<Node<T>>>, Error> {
+        let mut before_previous: Option<Rc<RefCell<Node<T>>>> = None;
+        let mut previous: Option<Rc<RefCell<Node<T>>>> = None;
+        let mut current_node: Option<Rc<RefCell<Node<T>>>> = self.head.clone();
+
+        while let Some(node) = current_node {
+            self.comparisons += 1;
+            if node.borrow().data == data {
+                node.borrow_mut().count += 1;
+                match self.strategy {
+                    Strategy::MoveToFront => self.move_to_front(previous, &node),
+                    Strategy::Transpose => self.transpose(before_previous, previous, &node),
+                    Strategy::FrequencyCount => self.move_by_count(previous, &node),
+                }
+                return Ok(node);
+            }
+
+            let next: Option<Rc<RefCell<Node<T>>>> = node.borrow().next.clone();
+            before_previous = previous;
+            previous = Some(node);
+            current_node = next;
+        }
+
+        return Err(Error);
+    }
+
+    pub fn contains(&mut self, data: T) -> bool {
+        return self.find_node_by_value(data).is_ok();
+    }
+
+    pub fn reset_comparisons(&mut self) {
+        self.comparisons = 0;
+    }
+
+    pub fn to_vec(&self) -> Vec<T> {
+        let mut data_vec: Vec<T> = Vec::with_capacity(self.length);
+        let mut current_node: Option<Rc<RefCell<Node<T>>>> = self.head.clone();
+
+        while let Some(node) = current_node {
+            data_vec.push(node.borrow().data.clone());
+            current_node = node.borrow().next.clone();
+        }
+
+        return data_vec;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.length == 0;
+    }
+
+    fn unlink(&mut self, previous: &Rc<RefCell<Node<T>>>, node: &Rc<RefCell<Node<T>>>) {
+        previous.borrow_mut().next = node.borrow_mut().next.take();
+        if previous.borrow().next.is_none() {
+            self.tail = Some(previous.clone());
+        }
+    }
+
+    fn move_to_front(&mut self, previous: Option<Rc<RefCell<Node<T>>>>, node: &Rc<RefCell<Node<T>>>) {
+        let previous: Rc<RefCell<Node<T>>> = match previous {
+            Some(previous) => previous,
+            None => return,
+        };
+
+        self.unlink(&previous, node);
+        node.borrow_mut().next = self.head.take();
+        self.head = Some(node.clone());
+    }
+
+    fn transpose(
+        &mut self,
+        before_previous: Option<Rc<RefCell<Node<T>>>>,
+        previous: Option<Rc<RefCell<Node<T>>>>,
+        node: &Rc<RefCell<Node<T>>>,
+    ) {
+        let previous: Rc<RefCell<Node<T>>> = match previous {
+            Some(previous) => previous,
+            None => return,
+        };
+
+        self.unlink(&previous, node);
+        node.borrow_mut().next = Some(previous);
+        match before_previous {
+            Some(before_previous) => before_previous.borrow_mut().next = Some(node.clone()),
+            None => self.head = Some(node.clone()),
+        }
+    }
+
+    fn move_by_count(&mut self, previous: Option<Rc<RefCell<Node<T>>>>, node: &Rc<RefCell<Node<T>>>) {
+        let previous: Rc<RefCell<Node<T>>> = match previous {
+            Some(previous) => previous,
+            None => return,
+        };
+        if previous.borrow().count >= node.borrow().count {
+            return;
+        }
+
+        self.unlink(&previous, node);
+        let count: usize = node.borrow().count;
+
+        // Place the node after every node that has been found at least as
+        // often, so ties keep their current order.
+        let head: Rc<RefCell<Node<T>>> = self.head.clone().unwrap();
+        if head.borrow().count < count {
+            node.borrow_mut().next = self.head.take();
+            self.head = Some(node.clone());
+            return;
+        }
+
+        let mut current_node: Rc<RefCell<Node<T>>> = head;
+        loop {
+            let next: Option<Rc<RefCell<Node<T>>>> = current_node.borrow().next.clone();
+            match next {
+                Some(next) if next.borrow().count >= count => current_node = next,
+                _ => break,
+            }
+        }
+
+        node.borrow_mut().next = current_node.borrow_mut().next.take();
+        current_node.borrow_mut().next = Some(node.clone());
+    }
+}
+
+impl<T> Drop for SelfOrganizingList<T> {
+    fn drop(&mut self) {
+        self.tail = None;
+        let mut current_node: Option<Rc<RefCell<Node<T>>>> = self.head.take();
+
+        while let Some(node) = current_node {
+            current_node = node.borrow_mut().next.take();
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_self_organizing_list_from() {
+        let list: SelfOrganizingList<i64> = SelfOrganizingList::from(Strategy::MoveToFront, vec![1, 2, 3]);
+        assert_eq!(list.to_vec(), vec![1, 2, 3]);
+        assert_eq!(list.length, 3);
+        assert_eq!(list.tail.clone().unwrap().borrow().data, 3);
+    }
+
+    #[test]
+    fn test_empty_find_node_by_value() {
+        let mut list: SelfOrganizingList<i64> = SelfOrganizingList::new(Strategy::Transpose);
+        assert!(list.find_node_by_value(4).is_err());
+        assert_eq!(list.comparisons, 0);
+    }
+
+    #[test]
+    fn test_invalid_find_node_by_value() {
+        let mut list: SelfOrganizingList<i64> = SelfOrganizingList::from(Strategy::MoveToFront, vec![1, 2, 3]);
+        assert!(list.find_node_by_value(4).is_err());
+        assert_eq!(list.comparisons, 3);
+        assert_eq!(list.to_vec(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_move_to_front() {
+        let mut list: SelfOrganizingList<i64> = SelfOrganizingList::from(Strategy::MoveToFront, vec![1, 2, 3, 4]);
+
+        let node: Rc<RefCell<Node<i64>>> = list.find_node_by_value(4).unwrap();
+        assert_eq!(node.borrow().data, 4);
+        assert_eq!(list.to_vec(), vec![4, 1, 2, 3]);
+        assert_eq!(list.tail.clone().unwrap().borrow().data, 3);
+        assert_eq!(list.comparisons, 4);
+
+        let _ = list.find_node_by_value(4);
+        assert_eq!(list.comparisons, 5);
+    }
+
+    #[test]
+    fn test_transpose() {
+        let mut list: SelfOrganizingList<i64> = SelfOrganizingList::from(Strategy::Transpose, vec![1, 2, 3, 4]);
+
+        let _ = list.find_node_by_value(4);
+        assert_eq!(list.to_vec(), vec![1, 2, 4, 3]);
+        assert_eq!(list.tail.clone().unwrap().borrow().data, 3);
+
+        let _ = list.find_node_by_value(4);
+        let _ = list.find_node_by_value(4);
+        assert_eq!(list.to_vec(), vec![4, 1, 2, 3]);
+
+        let _ = list.find_node_by_value(4);
+        assert_eq!(list.to_vec(), vec![4, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_frequency_count() {
+        let mut list: SelfOrganizingList<i64> = SelfOrganizingList::from(Strategy::FrequencyCount, vec![1, 2, 3, 4]);
+
+        let _ = list.find_node_by_value(3);
+        assert_eq!(list.to_vec(), vec![3, 1, 2, 4]);
+
+        let _ = list.find_node_by_value(4);
+        assert_eq!(list.to_vec(), vec![3, 4, 1, 2]);
+
+        let _ = list.find_node_by_value(4);
+        assert_eq!(list.to_vec(), vec![4, 3, 1, 2]);
+        assert_eq!(list.tail.clone().unwrap().borrow().data, 2);
+
+        let _ = list.find_node_by_value(2);
+        assert_eq!(list.to_vec(), vec![4, 3, 2, 1]);
+        assert_eq!(list.tail.clone().unwrap().borrow().data, 1);
+    }
+
+    #[test]
+    fn test_repeated_lookups_comparisons() {
+        let data_vec: Vec<i64> = (0..50).collect();
+        let mut list: SelfOrganizingList<i64> = SelfOrganizingList::from(Strategy::MoveToFront, data_vec);
+
+        for _ in 0..100 {
+            let _ = list.find_node_by_value(49);
+        }
+        assert_eq!(list.comparisons, 50 + 99);
+
+        list.reset_comparisons();
+        assert_eq!(list.comparisons, 0);
+    }
+
+    #[test]
+    fn test_pop_from_front() {
+        let mut list: SelfOrganizingList<i64> = SelfOrganizingList::from(Strategy::MoveToFront, vec![1, 2]);
+        assert_eq!(list.pop_from_front(), Ok(1));
+        assert_eq!(list.pop_from_front(), Ok(2));
+        assert!(list.pop_from_front().is_err());
+        assert!(list.tail.is_none());
+        assert!(list.is_empty());
+    }
+}
@@ -0,0 +1,495 @@
+use std::fmt::{Debug, Error, Formatter};
+use std::mem::{size_of, MaybeUninit};
+use std::ptr;
+
+use super::memory_report::MemoryReport;
+
+/// A node of an unrolled linked list, holding up to `N` elements inline.
+/// Only `elements[..count]` are initialised.
+pub struct Node<T, const N: usize> {
+    elements: [MaybeUninit<T>; N],
+    count: usize,
+    next: Option<Box<Node<T, N>>>,
+}
+
+impl<T, const N: usize> Node<T, N> {
+    fn new() -> Box<Node<T, N>> {
+        Box::new(Node { elements: [const { MaybeUninit::uninit() }; N], count: 0, next: None })
+    }
+
+    fn get(&self, position: usize) -> &T {
+        assert!(position < self.count);
+        // SAFETY: every slot below `count` is initialised.
+        unsafe { self.elements[position].assume_init_ref() }
+    }
+
+    fn insert_at(&mut self, position: usize, data: T) {
+        assert!(self.count < N && position <= self.count);
+        // SAFETY: the slots `position..count` are shifted up by one into
+        // the spare capacity, then the gap at `position` is filled.
+        unsafe {
+            let base: *mut MaybeUninit<T> = self.elements.as_mut_ptr();
+            ptr::copy(base.add(position), base.add(position + 1), self.count - position);
+            base.add(position).write(MaybeUninit::new(data));
+        }
+        self.count += 1;
+    }
+
+    fn remove_at(&mut self, position: usize) -> T {
+        assert!(position < self.count);
+        // SAFETY: the slot is initialised; after reading it out, the slots
+        // above it are shifted down so `..count - 1` stays initialised.
+        unsafe {
+            let base: *mut MaybeUninit<T> = self.elements.as_mut_ptr();
+            let data: T = base.add(position).read().assume_init();
+            ptr::copy(base.add(position + 1), base.add(position), self.count - position - 1);
+            self.count -= 1;
+            return data;
+        }
+    }
+
+    /// Moves `other.elements[from..]` onto the end of `self`.
+    fn take_from(&mut self, other: &mut Node<T, N>, from: usize) {
+        let moved: usize = other.count - from;
+        assert!(self.count + moved <= N);
+        // SAFETY: the source slots are initialised and the destination slots
+        // are spare capacity; the two nodes are distinct allocations.
+        unsafe {
+            ptr::copy_nonoverlapping(
+                other.elements.as_ptr().add(from),
+                self.elements.as_mut_ptr().add(self.count),
+                moved,
+            );
+        }
+        self.count += moved;
+        other.count = from;
+    }
+}
+
+impl<T, const N: usize> Drop for Node<T, N> {
+    fn drop(&mut self) {
+        for slot in &mut self.elements[..self.count] {
+            // SAFETY: only the initialised prefix is dropped.
+            unsafe { slot.assume_init_drop() };
+        }
+    }
+}
+
+/// An unrolled singly linked list. Each node stores up to `N` elements, so
+/// the per-node pointer and header cost is shared between them. A full node
+/// is split in half on insert, and a node that drops below half full on
+/// remove borrows from or merges with the next node.
+pub struct LinkedList<T, const N: usize = 16> {
+    head: Option<Box<Node<T, N>>>,
+    length: usize,
+}
+
+impl<T, const N: usize> LinkedList<T, N> {
+    pub fn new() -> Self {
+        assert!(N >= 2, "an unrolled node must hold at least two elements");
+        LinkedList { head: None, length: 0 }
+    }
+
+    pub fn from(data_vec: Vec<T>) -> Self {
+        let mut list: LinkedList<T, N> = LinkedList::new();
+
+        for data in data_vec {
+            list.push_to_end(data);
+        }
+
+        return list;
+    }
+
+    pub fn push_to_front(&mut self, data: T) {
+        let _ = self.insert(0, data);
+    }
+
+    pub fn push_to_end(&mut self, data: T) {
+        let _ = self.insert(self.length, data);
+    }
+
+    pub fn pop_from_front(&mut self) -> Result<T, Error> {
+        return self.remove(0);
+    }
+
+    pub fn pop_from_end(&mut self) -> Result<T, Error> {
+        if self.length == 0 {
+            return Err(Error);
+        }
+
+        return self.remove(self.length - 1);
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        self.iter().next()
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        if self.length == 0 {
+            return None;
+        }
+
+        let (node, position): (&Node<T, N>, usize) = self.locate(self.length - 1);
+        return Some(node.get(position));
+    }
+
+    /// Returns the node holding `index` and the position inside it.
+    fn locate(&self, mut index: usize) -> (&Node<T, N>, usize) {
+        let mut current_node: &Node<T, N> = self.head.as_deref().unwrap();
+
+        while index >= current_node.count {
+            index -= current_node.count;
+            current_node = current_node.next.as_deref().unwrap();
+        }
+
+        return (current_node, index);
+    }
+
+    pub fn find_by_index(&self, index: usize) -> Result<&T, Error> {
+        if index >= self.length {
+            return Err(Error);
+        }
+
+        let (node, position): (&Node<T, N>, usize) = self.locate(index);
+        return Ok(node.get(position));
+    }
+
+    pub fn find_by_value(&self, data: &T) -> Result<usize, Error>
+    where
+        T: PartialEq,
+    {
+        match self.iter().position(|value| value == data) {
+            Some(index) => Ok(index),
+            None => Err(Error),
+        }
+    }
+
+    pub fn insert(&mut self, index: usize, data: T) -> Result<(), Error> {
+        if index > self.length {
+            return Err(Error);
+        }
+
+        if self.head.is_none() {
+            self.head = Some(Node::new());
+        }
+
+        // Appending goes into the last node, which is found by walking past
+        // every full node's worth of elements; any other index is inserted
+        // into the node that currently holds it.
+        let mut position: usize = index;
+        let mut node: &mut Node<T, N> = self.head.as_deref_mut().unwrap();
+        while position > node.count || (position == node.count && node.next.is_some()) {
+            position -= node.count;
+            node = node.next.as_deref_mut().unwrap();
+        }
+
+        if node.count == N {
+            // Appending to a full last node starts a fresh node so that a run
+            // of appends leaves every node full; otherwise split in half.
+            let mut upper: Box<Node<T, N>> = Node::new();
+            if position < N {
+                upper.take_from(node, N / 2);
+            }
+            upper.next = node.next.take();
+            node.next = Some(upper);
+
+            if position > node.count || node.count == N {
+                position -= node.count;
+                node = node.next.as_deref_mut().unwrap();
+            }
+        }
+
+        node.insert_at(position, data);
+        self.length += 1;
+        return Ok(());
+    }
+
+    pub fn remove(&mut self, index: usize) -> Result<T, Error> {
+        if index >= self.length {
+            return Err(Error);
+        }
+
+        let mut position: usize = index;
+        let mut link: &mut Option<Box<Node<T, N>>> = &mut self.head;
+        while position >= link.as_ref().unwrap().count {
+            position -= link.as_ref().unwrap().count;
+            link = &mut link.as_mut().unwrap().next;
+        }
+
+        let node: &mut Node<T, N> = link.as_deref_mut().unwrap();
+        let data: T = node.remove_at(position);
+        self.length -= 1;
+
+        if node.count < N / 2 {
+            if let Some(mut next) = node.next.take() {
+                if node.count + next.count <= N {
+                    node.take_from(&mut next, 0);
+                    node.next = next.next.take();
+                } else {
+                    let borrowed: T = next.remove_at(0);
+                    node.insert_at(node.count, borrowed);
+                    node.next = Some(next);
+                }
+            }
+        }
+
+        // A node can only be left empty when it is the last one, as any
+        // other node would have borrowed from its successor above.
+        if node.count == 0 {
+            *link = None;
+        }
+
+        return Ok(data);
+    }
+
+    pub fn iter(&self) -> Iter<'_, T, N> {
+        Iter { node: self.head.as_deref(), position: 0 }
+    }
+
+    pub fn node_count(&self) -> usize {
+        let mut count: usize = 0;
+        let mut current_node: Option<&Node<T, N>> = self.head.as_deref();
+
+        while let Some(node) = current_node {
+            count += 1;
+            current_node = node.next.as_deref();
+        }
+
+        return count;
+    }
+
+    pub fn memory_report(&self) -> MemoryReport {
+        MemoryReport {
+            elements: self.length,
+            element_bytes: size_of::<T>(),
+            allocated_bytes: self.node_count() * size_of::<Node<T, N>>(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        return self.length;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.length == 0;
+    }
+}
+
+impl<T, const N: usize> Drop for LinkedList<T, N> {
+    fn drop(&mut self) {
+        let mut current_node: Option<Box<Node<T, N>>> = self.head.take();
+
+        while let Some(mut node) = current_node {
+            current_node = node.next.take();
+        }
+    }
+}
+
+impl<T: Debug, const N: usize> Debug for LinkedList<T, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq, const N: usize> PartialEq for LinkedList<T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        return self.length == other.length && self.iter().eq(other.iter());
+    }
+}
+
+pub struct Iter<'a, T, const N: usize> {
+    node: Option<&'a Node<T, N>>,
+    position: usize,
+}
+
+impl<'a, T, const N: usize> Iterator for Iter<'a, T, N> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let mut node: &'a Node<T, N> = self.node?;
+
+        while self.position >= node.count {
+            node = node.next.as_deref()?;
+            self.node = Some(node);
+            self.position = 0;
+        }
+
+        self.position += 1;
+        return Some(node.get(self.position - 1));
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linked_lists::memory_report::rc_refcell_report;
+    use std::rc::Rc;
+
+    fn collect<const N: usize>(list: &LinkedList<i64, N>) -> Vec<i64> {
+        list.iter().cloned().collect()
+    }
+
+    fn node_counts<const N: usize>(list: &LinkedList<i64, N>) -> Vec<usize> {
+        let mut counts: Vec<usize> = vec![];
+        let mut current_node: Option<&Node<i64, N>> = list.head.as_deref();
+        while let Some(node) = current_node {
+            counts.push(node.count);
+            current_node = node.next.as_deref();
+        }
+        return counts;
+    }
+
+    #[test]
+    fn test_linked_list_new() {
+        let list: LinkedList<i64> = LinkedList::new();
+        assert!(list.is_empty());
+        assert_eq!(list.front(), None);
+        assert_eq!(list.back(), None);
+    }
+
+    #[test]
+    fn test_linked_list_from_fills_nodes() {
+        let list: LinkedList<i64, 4> = LinkedList::from((0..10).collect());
+        assert_eq!(collect(&list), (0..10).collect::<Vec<i64>>());
+        assert_eq!(node_counts(&list), vec![4, 4, 2]);
+        assert_eq!(list.back(), Some(&9));
+    }
+
+    #[test]
+    fn test_insert_splits_full_node() {
+        let mut list: LinkedList<i64, 4> = LinkedList::from(vec![0, 1, 2, 3]);
+        assert_eq!(list.insert(1, 10), Ok(()));
+        assert_eq!(collect(&list), vec![0, 10, 1, 2, 3]);
+        assert_eq!(node_counts(&list), vec![3, 2]);
+        assert_eq!(list.back(), Some(&3));
+
+        assert_eq!(list.insert(4, 11), Ok(()));
+        assert_eq!(collect(&list), vec![0, 10, 1, 2, 11, 3]);
+        assert!(list.insert(7, 12).is_err());
+    }
+
+    #[test]
+    fn test_push_to_front() {
+        let mut list: LinkedList<i64, 4> = LinkedList::new();
+        for value in (0..9).rev() {
+            list.push_to_front(value);
+        }
+        assert_eq!(collect(&list), (0..9).collect::<Vec<i64>>());
+        assert_eq!(list.back(), Some(&8));
+    }
+
+    #[test]
+    fn test_remove_borrows_and_merges() {
+        let mut list: LinkedList<i64, 4> = LinkedList::from((0..8).collect());
+        assert_eq!(list.remove(0), Ok(0));
+        assert_eq!(list.remove(0), Ok(1));
+        assert_eq!(node_counts(&list), vec![2, 4]);
+
+        assert_eq!(list.remove(0), Ok(2));
+        assert_eq!(node_counts(&list), vec![2, 3]);
+
+        assert_eq!(list.remove(0), Ok(3));
+        assert_eq!(node_counts(&list), vec![4]);
+        assert_eq!(collect(&list), vec![4, 5, 6, 7]);
+        assert_eq!(list.back(), Some(&7));
+    }
+
+    #[test]
+    fn test_pop_from_both_ends() {
+        let mut list: LinkedList<i64, 4> = LinkedList::from((0..6).collect());
+        assert_eq!(list.pop_from_end(), Ok(5));
+        assert_eq!(list.pop_from_end(), Ok(4));
+        assert_eq!(list.pop_from_front(), Ok(0));
+        assert_eq!(list.back(), Some(&3));
+        assert_eq!(collect(&list), vec![1, 2, 3]);
+
+        while list.pop_from_end().is_ok() {}
+        assert!(list.is_empty());
+        assert!(list.head.is_none());
+        assert!(list.pop_from_front().is_err());
+
+        list.push_to_end(9);
+        assert_eq!(collect(&list), vec![9]);
+    }
+
+    #[test]
+    fn test_find_by_index() {
+        let list: LinkedList<i64, 3> = LinkedList::from((10..20).collect());
+        for index in 0..10 {
+            assert_eq!(list.find_by_index(index), Ok(&(10 + index as i64)));
+        }
+        assert!(list.find_by_index(10).is_err());
+    }
+
+    #[test]
+    fn test_find_by_value() {
+        let list: LinkedList<i64, 2> = LinkedList::from(vec![43, 17, 99]);
+        assert_eq!(list.find_by_value(&99), Ok(2));
+        assert!(list.find_by_value(&105).is_err());
+    }
+
+    #[test]
+    fn test_matches_vec_under_mixed_operations() {
+        let mut list: LinkedList<i64, 4> = LinkedList::new();
+        let mut expected: Vec<i64> = vec![];
+        let mut seed: u64 = 7;
+
+        for step in 0..500 {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            let choice: u64 = seed >> 33;
+            if expected.is_empty() || !choice.is_multiple_of(3) {
+                let index: usize = (choice as usize) % (expected.len() + 1);
+                let _ = list.insert(index, step);
+                expected.insert(index, step);
+            } else {
+                let index: usize = (choice as usize) % expected.len();
+                assert_eq!(list.remove(index), Ok(expected.remove(index)));
+            }
+            assert_eq!(list.back(), expected.last());
+        }
+
+        assert_eq!(collect(&list), expected);
+    }
+
+    #[test]
+    fn test_len_tracks_public_operations() {
+        let mut list: LinkedList<i64, 4> = LinkedList::from((0..10).collect());
+        assert_eq!((list.len(), list.node_count()), (10, 3));
+
+        assert_eq!(list.insert(3, 30), Ok(()));
+        assert_eq!(list.remove(0), Ok(0));
+        assert_eq!(list.pop_from_end(), Ok(9));
+        assert_eq!(list.len(), 9);
+        assert_eq!(list.iter().count(), list.len());
+        assert_eq!(list.find_by_index(list.len() - 1), Ok(&8));
+        assert!(list.find_by_index(list.len()).is_err());
+
+        while list.pop_from_front().is_ok() {}
+        assert_eq!((list.len(), list.node_count()), (0, 0));
+    }
+
+    #[test]
+    fn test_drop_releases_every_element() {
+        let counter: Rc<()> = Rc::new(());
+        let mut list: LinkedList<Rc<()>, 4> = LinkedList::new();
+        for _ in 0..10 {
+            list.push_to_end(counter.clone());
+        }
+        let _ = list.remove(4);
+        assert_eq!(Rc::strong_count(&counter), 10);
+
+        drop(list);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn test_memory_report() {
+        let list: LinkedList<u64, 16> = LinkedList::from((0..64).collect());
+        let report: MemoryReport = list.memory_report();
+        assert_eq!(report.elements, 64);
+        assert_eq!(report.overhead_per_element(), (2 * size_of::<usize>()) as f64 / 16.0);
+        assert!(report.overhead_per_element() < rc_refcell_report::<u64>(64).overhead_per_element());
+    }
+}
@@ -0,0 +1,545 @@
+use std::fmt::{Debug, Error, Formatter};
+use std::marker::PhantomData;
+use std::mem::size_of;
+use std::ptr;
+
+use super::memory_report::MemoryReport;
+
+/// A node of an XOR linked list. Instead of separate `prev` and `next`
+/// pointers it stores `prev ^ next` as one address, with a null neighbour
+/// counting as zero. Walking the list means remembering the address of the
+/// node you came from and XOR-ing it out of `link`.
+pub struct Node<T> {
+    pub data: T,
+    pub link: usize,
+}
+
+/// A doubly linked list with a tail, where every node only has a single
+/// link field.
+///
+/// Safety invariants that every method relies on:
+/// - `head` and `tail` are either both null (empty list) or both point to
+///   nodes that were created with `Box::into_raw` and are owned by this list.
+/// - For every node, `link == prev as usize ^ next as usize`, where the
+///   missing neighbour of `head`/`tail` is null.
+/// - `length` is the number of nodes reachable from `head`.
+///
+/// Nodes are only freed by `pop_from_front`, `pop_from_end`, `remove` and
+/// `Drop`, each of which first repairs the links of the neighbours.
+pub struct LinkedList<T> {
+    head: *mut Node<T>,
+    tail: *mut Node<T>,
+    length: usize,
+    marker: PhantomData<Box<Node<T>>>,
+}
+
+// SAFETY: the raw pointers only ever point at nodes this list allocated and
+// owns; no other list, iterator or node handle outlives a `&mut` borrow of it.
+// Moving the list moves sole ownership of every `T`, so it is `Send` when `T`
+// is. Through `&LinkedList` the only access is reading the nodes (`iter`,
+// `front`, `find_by_index`, ...), with no interior mutability, so it is
+// `Sync` when `&T` may be shared. This leans on every field that the safety
+// invariants above mention being private.
+unsafe impl<T: Send> Send for LinkedList<T> {}
+unsafe impl<T: Sync> Sync for LinkedList<T> {}
+
+impl<T> LinkedList<T> {
+    pub fn new() -> Self {
+        LinkedList { head: ptr::null_mut(), tail: ptr::null_mut(), length: 0, marker: PhantomData }
+    }
+
+    pub fn from(data_vec: Vec<T>) -> Self {
+        let mut list: LinkedList<T> = LinkedList::new();
+
+        for data in data_vec {
+            list.push_to_end(data);
+        }
+
+        return list;
+    }
+
+    fn allocate(data: T, link: usize) -> *mut Node<T> {
+        Box::into_raw(Box::new(Node { data, link }))
+    }
+
+    pub fn push_to_front(&mut self, data: T) {
+        let node: *mut Node<T> = LinkedList::allocate(data, self.head as usize);
+
+        if self.head.is_null() {
+            self.tail = node;
+        } else {
+            // SAFETY: `head` is a live node owned by the list, and its
+            // previous neighbour was null, so XOR-ing in `node` sets it.
+            unsafe { (*self.head).link ^= node as usize };
+        }
+
+        self.head = node;
+        self.length += 1;
+    }
+
+    pub fn push_to_end(&mut self, data: T) {
+        let node: *mut Node<T> = LinkedList::allocate(data, self.tail as usize);
+
+        if self.tail.is_null() {
+            self.head = node;
+        } else {
+            // SAFETY: as in `push_to_front`, mirrored for the tail.
+            unsafe { (*self.tail).link ^= node as usize };
+        }
+
+        self.tail = node;
+        self.length += 1;
+    }
+
+    pub fn pop_from_front(&mut self) -> Result<T, Error> {
+        if self.head.is_null() {
+            return Err(Error);
+        }
+
+        // SAFETY: `head` is non-null, so it came from `Box::into_raw` and no
+        // other node will be handed out after it is unlinked below.
+        let node: Box<Node<T>> = unsafe { Box::from_raw(self.head) };
+        let next: *mut Node<T> = node.link as *mut Node<T>;
+
+        if next.is_null() {
+            self.tail = ptr::null_mut();
+        } else {
+            // SAFETY: `next` is a live node whose previous neighbour was the
+            // old head; XOR-ing that address out leaves null as its prev.
+            unsafe { (*next).link ^= self.head as usize };
+        }
+
+        self.head = next;
+        self.length -= 1;
+        return Ok(node.data);
+    }
+
+    pub fn pop_from_end(&mut self) -> Result<T, Error> {
+        if self.tail.is_null() {
+            return Err(Error);
+        }
+
+        // SAFETY: as in `pop_from_front`, mirrored for the tail.
+        let node: Box<Node<T>> = unsafe { Box::from_raw(self.tail) };
+        let previous: *mut Node<T> = node.link as *mut Node<T>;
+
+        if previous.is_null() {
+            self.head = ptr::null_mut();
+        } else {
+            unsafe { (*previous).link ^= self.tail as usize };
+        }
+
+        self.tail = previous;
+        self.length -= 1;
+        return Ok(node.data);
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        self.iter().next()
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        self.iter().next_back()
+    }
+
+    /// Returns the neighbouring pair `(previous, current)` where `current`
+    /// is the node at `index`, walking in from whichever end is closer.
+    fn find_pair(&self, index: usize) -> (*mut Node<T>, *mut Node<T>) {
+        let mut previous: *mut Node<T> = ptr::null_mut();
+        let mut current: *mut Node<T>;
+
+        if index < self.length / 2 {
+            current = self.head;
+            for _ in 0..index {
+                // SAFETY: `index < length`, so we never step past the tail.
+                let next: *mut Node<T> = unsafe { ((*current).link ^ previous as usize) as *mut Node<T> };
+                previous = current;
+                current = next;
+            }
+            return (previous, current);
+        }
+
+        current = self.tail;
+        for _ in 0..(self.length - 1 - index) {
+            // SAFETY: walking backwards from the tail, `next` plays the role
+            // of `previous`, and we stop at `index` before reaching null.
+            let before: *mut Node<T> = unsafe { ((*current).link ^ previous as usize) as *mut Node<T> };
+            previous = current;
+            current = before;
+        }
+
+        // SAFETY: `current` is live, `previous` is its next neighbour.
+        let before: *mut Node<T> = unsafe { ((*current).link ^ previous as usize) as *mut Node<T> };
+        return (before, current);
+    }
+
+    pub fn find_by_index(&self, index: usize) -> Result<&T, Error> {
+        if index >= self.length {
+            return Err(Error);
+        }
+
+        let (_, node): (*mut Node<T>, *mut Node<T>) = self.find_pair(index);
+        // SAFETY: `node` is a live node and the borrow is tied to `&self`.
+        return Ok(unsafe { &(*node).data });
+    }
+
+    pub fn find_by_value(&self, data: &T) -> Result<usize, Error>
+    where
+        T: PartialEq,
+    {
+        match self.iter().position(|value| value == data) {
+            Some(index) => Ok(index),
+            None => Err(Error),
+        }
+    }
+
+    pub fn insert(&mut self, index: usize, data: T) -> Result<(), Error> {
+        if index > self.length {
+            return Err(Error);
+        }
+        if index == 0 {
+            self.push_to_front(data);
+            return Ok(());
+        }
+        if index == self.length {
+            self.push_to_end(data);
+            return Ok(());
+        }
+
+        let (previous, current): (*mut Node<T>, *mut Node<T>) = self.find_pair(index);
+        let node: *mut Node<T> = LinkedList::allocate(data, previous as usize ^ current as usize);
+
+        // SAFETY: `0 < index < length`, so both neighbours are live. Each of
+        // them swaps the other's address for `node` in its link.
+        unsafe {
+            (*previous).link ^= current as usize ^ node as usize;
+            (*current).link ^= previous as usize ^ node as usize;
+        }
+
+        self.length += 1;
+        return Ok(());
+    }
+
+    pub fn remove(&mut self, index: usize) -> Result<T, Error> {
+        if index >= self.length {
+            return Err(Error);
+        }
+        if index == 0 {
+            return self.pop_from_front();
+        }
+        if index == self.length - 1 {
+            return self.pop_from_end();
+        }
+
+        let (previous, current): (*mut Node<T>, *mut Node<T>) = self.find_pair(index);
+
+        // SAFETY: `current` is an interior node, so both neighbours are live.
+        // They are linked to each other before `current` is freed.
+        unsafe {
+            let next: *mut Node<T> = ((*current).link ^ previous as usize) as *mut Node<T>;
+            (*previous).link ^= current as usize ^ next as usize;
+            (*next).link ^= current as usize ^ previous as usize;
+        }
+
+        self.length -= 1;
+        let node: Box<Node<T>> = unsafe { Box::from_raw(current) };
+        return Ok(node.data);
+    }
+
+    /// Splits the list in two at `at` by cutting one pair of links, so no
+    /// node is moved or reallocated. The list keeps the first `at` elements
+    /// and the rest are returned. Finding the cut walks in from the nearer
+    /// end.
+    pub fn split_off(&mut self, at: usize) -> Result<LinkedList<T>, Error> {
+        if at > self.length {
+            return Err(Error);
+        }
+        if at == 0 {
+            return Ok(std::mem::replace(self, LinkedList::new()));
+        }
+        if at == self.length {
+            return Ok(LinkedList::new());
+        }
+
+        let (previous, current): (*mut Node<T>, *mut Node<T>) = self.find_pair(at);
+        let back: LinkedList<T> = LinkedList { head: current, tail: self.tail, length: self.length - at, marker: PhantomData };
+
+        // SAFETY: `0 < at < length`, so both nodes are live. Each drops the
+        // other from its link, which leaves null as `previous`'s next and as
+        // `current`'s prev.
+        unsafe {
+            (*previous).link ^= current as usize;
+            (*current).link ^= previous as usize;
+        }
+
+        self.tail = previous;
+        self.length = at;
+        return Ok(back);
+    }
+
+    /// Moves every node of `other` onto the end of this list in O(1),
+    /// leaving `other` empty.
+    pub fn append(&mut self, other: &mut LinkedList<T>) {
+        if other.head.is_null() {
+            return;
+        }
+        if self.head.is_null() {
+            std::mem::swap(self, other);
+            return;
+        }
+
+        // SAFETY: both ends are live nodes whose missing neighbour was null,
+        // so XOR-ing in the other list's end links them.
+        unsafe {
+            (*self.tail).link ^= other.head as usize;
+            (*other.head).link ^= self.tail as usize;
+        }
+
+        self.tail = other.tail;
+        self.length += other.length;
+        other.head = ptr::null_mut();
+        other.tail = ptr::null_mut();
+        other.length = 0;
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            front: (ptr::null_mut(), self.head),
+            back: (ptr::null_mut(), self.tail),
+            remaining: self.length,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn memory_report(&self) -> MemoryReport {
+        MemoryReport {
+            elements: self.length,
+            element_bytes: size_of::<T>(),
+            allocated_bytes: self.length * size_of::<Node<T>>(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.length == 0;
+    }
+}
+
+impl<T> Drop for LinkedList<T> {
+    fn drop(&mut self) {
+        while self.pop_from_front().is_ok() {}
+    }
+}
+
+impl<T: Debug> Debug for LinkedList<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for LinkedList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        return self.length == other.length && self.iter().eq(other.iter());
+    }
+}
+
+/// Walks the list from both ends. Each end keeps the pair of the node it
+/// last came from and the node it will yield next.
+pub struct Iter<'a, T> {
+    front: (*mut Node<T>, *mut Node<T>),
+    back: (*mut Node<T>, *mut Node<T>),
+    remaining: usize,
+    marker: PhantomData<&'a Node<T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let (previous, current): (*mut Node<T>, *mut Node<T>) = self.front;
+        // SAFETY: `remaining > 0`, so `current` is a live node, and the list
+        // cannot change while it is borrowed by this iterator.
+        let node: &'a Node<T> = unsafe { &*current };
+        self.front = (current, (node.link ^ previous as usize) as *mut Node<T>);
+        self.remaining -= 1;
+        return Some(&node.data);
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let (next, current): (*mut Node<T>, *mut Node<T>) = self.back;
+        // SAFETY: as in `next`, walking from the tail.
+        let node: &'a Node<T> = unsafe { &*current };
+        self.back = (current, (node.link ^ next as usize) as *mut Node<T>);
+        self.remaining -= 1;
+        return Some(&node.data);
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linked_lists::memory_report::rc_refcell_report;
+    use std::rc::Rc;
+
+    fn collect(list: &LinkedList<i64>) -> Vec<i64> {
+        list.iter().cloned().collect()
+    }
+
+    #[test]
+    fn test_linked_list_new() {
+        let list: LinkedList<i64> = LinkedList::new();
+        assert!(list.is_empty());
+        assert_eq!(list.front(), None);
+        assert_eq!(list.back(), None);
+    }
+
+    #[test]
+    fn test_linked_list_from() {
+        let list: LinkedList<i64> = LinkedList::from(vec![8, 56, 19, 80]);
+        assert_eq!(collect(&list), vec![8, 56, 19, 80]);
+        assert_eq!(list.iter().rev().cloned().collect::<Vec<i64>>(), vec![80, 19, 56, 8]);
+        assert_eq!(list.len(), 4);
+    }
+
+    #[test]
+    fn test_push_to_front() {
+        let mut list: LinkedList<i64> = LinkedList::new();
+        list.push_to_front(3);
+        list.push_to_front(2);
+        list.push_to_front(1);
+        assert_eq!(collect(&list), vec![1, 2, 3]);
+        assert_eq!(list.back(), Some(&3));
+    }
+
+    #[test]
+    fn test_empty_pop_from_front() {
+        let mut list: LinkedList<i64> = LinkedList::new();
+        assert!(list.pop_from_front().is_err());
+        assert!(list.pop_from_end().is_err());
+    }
+
+    #[test]
+    fn test_pop_from_both_ends() {
+        let mut list: LinkedList<i64> = LinkedList::from(vec![1, 2, 3, 4]);
+        assert_eq!(list.pop_from_front(), Ok(1));
+        assert_eq!(list.pop_from_end(), Ok(4));
+        assert_eq!(collect(&list), vec![2, 3]);
+        assert_eq!(list.pop_from_end(), Ok(3));
+        assert_eq!(list.pop_from_end(), Ok(2));
+        assert!(list.is_empty());
+        assert!(list.head.is_null() && list.tail.is_null());
+    }
+
+    #[test]
+    fn test_find_by_index() {
+        let list: LinkedList<i64> = LinkedList::from(vec![10, 11, 12, 13, 14]);
+        for index in 0..5 {
+            assert_eq!(list.find_by_index(index), Ok(&(10 + index as i64)));
+        }
+        assert!(list.find_by_index(5).is_err());
+    }
+
+    #[test]
+    fn test_find_by_value() {
+        let list: LinkedList<i64> = LinkedList::from(vec![43, 17, 99]);
+        assert_eq!(list.find_by_value(&99), Ok(2));
+        assert!(list.find_by_value(&105).is_err());
+    }
+
+    #[test]
+    fn test_insert() {
+        let mut list: LinkedList<i64> = LinkedList::from(vec![1, 4]);
+        assert_eq!(list.insert(1, 2), Ok(()));
+        assert_eq!(list.insert(2, 3), Ok(()));
+        assert_eq!(list.insert(0, 0), Ok(()));
+        assert_eq!(list.insert(5, 5), Ok(()));
+        assert!(list.insert(7, 7).is_err());
+        assert_eq!(collect(&list), vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(list.iter().rev().cloned().collect::<Vec<i64>>(), vec![5, 4, 3, 2, 1, 0]);
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut list: LinkedList<i64> = LinkedList::from(vec![0, 1, 2, 3, 4]);
+        assert_eq!(list.remove(3), Ok(3));
+        assert_eq!(list.remove(1), Ok(1));
+        assert!(list.remove(3).is_err());
+        assert_eq!(collect(&list), vec![0, 2, 4]);
+        assert_eq!(list.iter().rev().cloned().collect::<Vec<i64>>(), vec![4, 2, 0]);
+    }
+
+    #[test]
+    fn test_split_off_and_append() {
+        let mut list: LinkedList<i64> = LinkedList::from(vec![0, 1, 2, 3, 4]);
+        let mut back: LinkedList<i64> = list.split_off(2).unwrap();
+        assert_eq!(collect(&list), vec![0, 1]);
+        assert_eq!(back.iter().rev().cloned().collect::<Vec<i64>>(), vec![4, 3, 2]);
+        assert_eq!((list.len(), back.len()), (2, 3));
+        assert!(list.split_off(3).is_err());
+        assert!(list.split_off(2).unwrap().is_empty());
+
+        list.append(&mut back);
+        assert!(back.is_empty() && back.front().is_none());
+        assert_eq!(collect(&list), vec![0, 1, 2, 3, 4]);
+        assert_eq!(list.iter().rev().cloned().collect::<Vec<i64>>(), vec![4, 3, 2, 1, 0]);
+
+        let mut all: LinkedList<i64> = list.split_off(0).unwrap();
+        assert!(list.is_empty());
+        list.append(&mut all);
+        list.push_to_end(5);
+        assert_eq!(collect(&list), vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(list.pop_from_end(), Ok(5));
+        assert_eq!(list.len(), 5);
+    }
+
+    #[test]
+    fn test_iter_meets_in_the_middle() {
+        let list: LinkedList<i64> = LinkedList::from(vec![1, 2, 3]);
+        let mut iter: Iter<i64> = list.iter();
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next_back(), Some(&3));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    fn test_drop_releases_every_element() {
+        let counter: Rc<()> = Rc::new(());
+        let mut list: LinkedList<Rc<()>> = LinkedList::new();
+        for _ in 0..10 {
+            list.push_to_end(counter.clone());
+        }
+        let _ = list.remove(4);
+        assert_eq!(Rc::strong_count(&counter), 10);
+
+        drop(list);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn test_memory_report() {
+        let list: LinkedList<u64> = LinkedList::from(vec![1, 2, 3]);
+        let report: MemoryReport = list.memory_report();
+        assert_eq!(report.overhead_per_element(), size_of::<usize>() as f64);
+        assert!(report.overhead_per_element() < rc_refcell_report::<u64>(3).overhead_per_element());
+    }
+}
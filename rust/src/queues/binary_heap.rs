@@ -0,0 +1,439 @@
+use std::fmt::Error;
+
+/// Decides which of two elements leaves a heap first. Closures taking two
+/// references work directly, alongside `MinFirst` and `MaxFirst`.
+pub trait Priority<T> {
+    /// Whether `first` must come out before `second`. Equal elements return
+    /// false either way.
+    fn before(&self, first: &T, second: &T) -> bool;
+}
+
+impl<T, F: Fn(&T, &T) -> bool> Priority<T> for F {
+    fn before(&self, first: &T, second: &T) -> bool {
+        self(first, second)
+    }
+}
+
+/// Smallest element first.
+#[derive(Clone, Copy, Debug)]
+pub struct MinFirst;
+
+/// Largest element first.
+#[derive(Clone, Copy, Debug)]
+pub struct MaxFirst;
+
+impl<T: Ord> Priority<T> for MinFirst {
+    fn before(&self, first: &T, second: &T) -> bool {
+        first < second
+    }
+}
+
+impl<T: Ord> Priority<T> for MaxFirst {
+    fn before(&self, first: &T, second: &T) -> bool {
+        first > second
+    }
+}
+
+/// A priority queue stored as an implicit binary tree in a `Vec`: the
+/// children of index `i` are `2i + 1` and `2i + 2`, and no element comes out
+/// before its parent.
+pub struct BinaryHeap<T, P> {
+    elements: Vec<T>,
+    priority: P,
+}
+
+impl<T, P: Priority<T>> BinaryHeap<T, P> {
+    pub fn new(priority: P) -> Self {
+        BinaryHeap { elements: Vec::new(), priority }
+    }
+
+    /// Builds the heap in O(n) by sifting down every parent, last one first.
+    pub fn from(priority: P, data_vec: Vec<T>) -> Self {
+        let mut heap: BinaryHeap<T, P> = BinaryHeap { elements: data_vec, priority };
+        heap.heapify();
+        return heap;
+    }
+
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    pub fn push(&mut self, data: T) {
+        self.elements.push(data);
+        self.sift_up(self.elements.len() - 1);
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        if self.elements.is_empty() {
+            return None;
+        }
+
+        let data: T = self.elements.swap_remove(0);
+        self.sift_down(0, self.elements.len());
+        return Some(data);
+    }
+
+    /// The element `pop` would return.
+    pub fn peek(&self) -> Option<&T> {
+        self.elements.first()
+    }
+
+    /// Moves every element of `other` into this heap. A small `other` is
+    /// pushed one element at a time; once it is at least half this heap's
+    /// size, rebuilding the whole heap in O(n + m) is cheaper.
+    pub fn merge(&mut self, other: BinaryHeap<T, P>) {
+        let start: usize = self.elements.len();
+        self.elements.extend(other.elements);
+
+        if self.elements.len() - start >= start / 2 {
+            self.heapify();
+            return;
+        }
+
+        for index in start..self.elements.len() {
+            self.sift_up(index);
+        }
+    }
+
+    /// The elements in the order `pop` would return them, sorted in place.
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        let mut end: usize = self.elements.len();
+
+        while end > 1 {
+            end -= 1;
+            self.elements.swap(0, end);
+            self.sift_down(0, end);
+        }
+
+        self.elements.reverse();
+        return self.elements;
+    }
+
+    /// The elements in heap order.
+    pub fn into_vec(self) -> Vec<T> {
+        self.elements
+    }
+
+    fn heapify(&mut self) {
+        let length: usize = self.elements.len();
+
+        for index in (0..length / 2).rev() {
+            self.sift_down(index, length);
+        }
+    }
+
+    fn sift_up(&mut self, mut index: usize) {
+        while index > 0 {
+            let parent: usize = (index - 1) / 2;
+            if !self.priority.before(&self.elements[index], &self.elements[parent]) {
+                break;
+            }
+            self.elements.swap(index, parent);
+            index = parent;
+        }
+    }
+
+    /// Sifts within `elements[..end]`, which lets `into_sorted_vec` keep the
+    /// finished tail out of the heap.
+    fn sift_down(&mut self, mut index: usize, end: usize) {
+        loop {
+            let mut first: usize = index;
+            for child in [2 * index + 1, 2 * index + 2] {
+                if child < end && self.priority.before(&self.elements[child], &self.elements[first]) {
+                    first = child;
+                }
+            }
+            if first == index {
+                return;
+            }
+            self.elements.swap(index, first);
+            index = first;
+        }
+    }
+}
+
+/// Names an element of an `IndexedHeap`. Handles are never reused, so one
+/// whose element has left the heap stays invalid.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Handle(usize);
+
+/// A binary heap that also remembers where each element sits, so an element
+/// can be found by its `Handle` in O(1) and re-prioritised or removed in
+/// O(log n). This is the decrease-key operation Dijkstra's and Prim's
+/// algorithms need.
+pub struct IndexedHeap<T, P> {
+    elements: Vec<(Handle, T)>,
+    /// The index in `elements` of every handle ever issued, or `None` once
+    /// its element has left.
+    positions: Vec<Option<usize>>,
+    priority: P,
+}
+
+impl<T, P: Priority<T>> IndexedHeap<T, P> {
+    pub fn new(priority: P) -> Self {
+        IndexedHeap { elements: Vec::new(), positions: Vec::new(), priority }
+    }
+
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    pub fn contains(&self, handle: Handle) -> bool {
+        self.position(handle).is_ok()
+    }
+
+    pub fn push(&mut self, data: T) -> Handle {
+        let handle: Handle = Handle(self.positions.len());
+        self.positions.push(Some(self.elements.len()));
+        self.elements.push((handle, data));
+        self.sift_up(self.elements.len() - 1);
+        return handle;
+    }
+
+    pub fn pop(&mut self) -> Option<(Handle, T)> {
+        if self.elements.is_empty() {
+            return None;
+        }
+
+        return Some(self.take(0));
+    }
+
+    pub fn peek(&self) -> Option<(Handle, &T)> {
+        self.elements.first().map(|(handle, data)| (*handle, data))
+    }
+
+    pub fn get(&self, handle: Handle) -> Option<&T> {
+        let index: usize = self.position(handle).ok()?;
+        return Some(&self.elements[index].1);
+    }
+
+    /// Replaces the element with one that comes out no later, for example a
+    /// shorter distance in a `MinFirst` heap. Fails on a stale handle or a
+    /// value that would move the element away from the top.
+    pub fn decrease_key(&mut self, handle: Handle, data: T) -> Result<(), Error> {
+        let index: usize = self.position(handle)?;
+        if self.priority.before(&self.elements[index].1, &data) {
+            return Err(Error);
+        }
+
+        self.elements[index].1 = data;
+        self.sift_up(index);
+        return Ok(());
+    }
+
+    /// The opposite of `decrease_key`: the new element must come out no
+    /// earlier than the one it replaces.
+    pub fn increase_key(&mut self, handle: Handle, data: T) -> Result<(), Error> {
+        let index: usize = self.position(handle)?;
+        if self.priority.before(&data, &self.elements[index].1) {
+            return Err(Error);
+        }
+
+        self.elements[index].1 = data;
+        self.sift_down(index);
+        return Ok(());
+    }
+
+    pub fn remove(&mut self, handle: Handle) -> Result<T, Error> {
+        let index: usize = self.position(handle)?;
+        return Ok(self.take(index).1);
+    }
+
+    fn position(&self, handle: Handle) -> Result<usize, Error> {
+        self.positions.get(handle.0).copied().flatten().ok_or(Error)
+    }
+
+    /// Removes the element at `index` by moving the last element into its
+    /// place, which then has to be sifted whichever way it belongs.
+    fn take(&mut self, index: usize) -> (Handle, T) {
+        let (handle, data): (Handle, T) = self.elements.swap_remove(index);
+        self.positions[handle.0] = None;
+
+        if index < self.elements.len() {
+            self.positions[self.elements[index].0 .0] = Some(index);
+            self.sift_up(index);
+            self.sift_down(index);
+        }
+
+        return (handle, data);
+    }
+
+    fn swap(&mut self, first: usize, second: usize) {
+        self.elements.swap(first, second);
+        self.positions[self.elements[first].0 .0] = Some(first);
+        self.positions[self.elements[second].0 .0] = Some(second);
+    }
+
+    fn sift_up(&mut self, mut index: usize) {
+        while index > 0 {
+            let parent: usize = (index - 1) / 2;
+            if !self.priority.before(&self.elements[index].1, &self.elements[parent].1) {
+                break;
+            }
+            self.swap(index, parent);
+            index = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut index: usize) {
+        let end: usize = self.elements.len();
+
+        loop {
+            let mut first: usize = index;
+            for child in [2 * index + 1, 2 * index + 2] {
+                if child < end && self.priority.before(&self.elements[child].1, &self.elements[first].1) {
+                    first = child;
+                }
+            }
+            if first == index {
+                return;
+            }
+            self.swap(index, first);
+            index = first;
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drain<T, P: Priority<T>>(mut heap: BinaryHeap<T, P>) -> Vec<T> {
+        let mut values: Vec<T> = Vec::new();
+        while let Some(data) = heap.pop() {
+            values.push(data);
+        }
+        return values;
+    }
+
+    #[test]
+    fn test_min_and_max() {
+        let values: Vec<i64> = vec![5, 3, 8, 1, 9, 2, 7, 3];
+        let mut heap: BinaryHeap<i64, MinFirst> = BinaryHeap::new(MinFirst);
+        for value in values.clone() {
+            heap.push(value);
+        }
+        assert_eq!(heap.peek(), Some(&1));
+        assert_eq!(heap.len(), 8);
+        assert_eq!(drain(heap), vec![1, 2, 3, 3, 5, 7, 8, 9]);
+
+        let heap: BinaryHeap<i64, MaxFirst> = BinaryHeap::from(MaxFirst, values);
+        assert_eq!(drain(heap), vec![9, 8, 7, 5, 3, 3, 2, 1]);
+    }
+
+    #[test]
+    fn test_closure_priority() {
+        let by_length = |first: &&str, second: &&str| first.len() < second.len();
+        let mut heap = BinaryHeap::from(by_length, vec!["three", "a", "to"]);
+        assert_eq!(heap.pop(), Some("a"));
+        assert_eq!(heap.pop(), Some("to"));
+        assert_eq!(heap.pop(), Some("three"));
+        assert_eq!(heap.pop(), None);
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn test_heapify_keeps_heap_order() {
+        let values: Vec<i64> = (0..100).map(|i| (i * 37) % 101).collect();
+        let elements: Vec<i64> = BinaryHeap::from(MinFirst, values).into_vec();
+        for index in 1..elements.len() {
+            assert!(elements[(index - 1) / 2] <= elements[index]);
+        }
+    }
+
+    #[test]
+    fn test_into_sorted_vec() {
+        let heap: BinaryHeap<i64, MaxFirst> = BinaryHeap::from(MaxFirst, vec![4, 1, 3, 1, 5]);
+        assert_eq!(heap.into_sorted_vec(), vec![5, 4, 3, 1, 1]);
+        assert_eq!(BinaryHeap::from(MinFirst, Vec::<i64>::new()).into_sorted_vec(), vec![]);
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut heap: BinaryHeap<i64, MinFirst> = BinaryHeap::from(MinFirst, (0..20).rev().collect());
+        heap.merge(BinaryHeap::from(MinFirst, vec![-1, 25]));
+        heap.merge(BinaryHeap::from(MinFirst, (30..60).collect()));
+        heap.merge(BinaryHeap::new(MinFirst));
+        assert_eq!(heap.len(), 52);
+
+        let mut expected: Vec<i64> = (-1..20).chain([25]).chain(30..60).collect();
+        expected.sort();
+        assert_eq!(drain(heap), expected);
+    }
+
+    #[test]
+    fn test_indexed_decrease_and_increase_key() {
+        let mut heap: IndexedHeap<i64, MinFirst> = IndexedHeap::new(MinFirst);
+        let handles: Vec<Handle> = [50, 40, 30, 20, 10].into_iter().map(|value| heap.push(value)).collect();
+        assert_eq!(heap.peek(), Some((handles[4], &10)));
+
+        assert_eq!(heap.decrease_key(handles[0], 5), Ok(()));
+        assert_eq!(heap.peek(), Some((handles[0], &5)));
+        assert_eq!(heap.decrease_key(handles[1], 45), Err(Error));
+        assert_eq!(heap.increase_key(handles[0], 60), Ok(()));
+        assert_eq!(heap.increase_key(handles[2], 25), Err(Error));
+        assert_eq!(heap.get(handles[0]), Some(&60));
+
+        assert_eq!(heap.remove(handles[2]), Ok(30));
+        assert!(!heap.contains(handles[2]));
+        assert_eq!(heap.remove(handles[2]), Err(Error));
+
+        let order: Vec<(Handle, i64)> = std::iter::from_fn(|| heap.pop()).collect();
+        assert_eq!(order, vec![(handles[4], 10), (handles[3], 20), (handles[1], 40), (handles[0], 60)]);
+        assert_eq!(heap.decrease_key(handles[0], 0), Err(Error));
+    }
+
+    #[test]
+    fn test_indexed_remove_keeps_heap_order() {
+        let mut heap: IndexedHeap<i64, MaxFirst> = IndexedHeap::new(MaxFirst);
+        let handles: Vec<Handle> = (0..50).map(|i| heap.push((i * 17) % 53)).collect();
+        for handle in handles.iter().step_by(3) {
+            assert!(heap.remove(*handle).is_ok());
+        }
+
+        let mut expected: Vec<i64> = (0..50).filter(|i| i % 3 != 0).map(|i| (i * 17) % 53).collect();
+        expected.sort_by(|a, b| b.cmp(a));
+        let order: Vec<i64> = std::iter::from_fn(|| heap.pop()).map(|(_, data)| data).collect();
+        assert_eq!(order, expected);
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let edges: Vec<Vec<(usize, u64)>> = vec![
+            vec![(1, 7), (2, 9), (5, 14)],
+            vec![(0, 7), (2, 10), (3, 15)],
+            vec![(0, 9), (1, 10), (3, 11), (5, 2)],
+            vec![(1, 15), (2, 11), (4, 6)],
+            vec![(3, 6), (5, 9)],
+            vec![(0, 14), (2, 2), (4, 9)],
+        ];
+        let by_distance = |first: &(u64, usize), second: &(u64, usize)| first.0 < second.0;
+        let mut heap = IndexedHeap::new(by_distance);
+        let handles: Vec<Handle> = (0..6).map(|vertex| heap.push((if vertex == 0 { 0 } else { u64::MAX }, vertex))).collect();
+        let mut distances: Vec<u64> = vec![u64::MAX; 6];
+
+        while let Some((_, (distance, vertex))) = heap.pop() {
+            distances[vertex] = distance;
+            for &(next, weight) in &edges[vertex] {
+                let Some(&(known, _)) = heap.get(handles[next]) else {
+                    continue;
+                };
+                if distance + weight < known {
+                    assert_eq!(heap.decrease_key(handles[next], (distance + weight, next)), Ok(()));
+                }
+            }
+        }
+
+        assert_eq!(distances, vec![0, 7, 9, 20, 20, 11]);
+    }
+}
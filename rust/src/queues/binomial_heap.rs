@@ -0,0 +1,202 @@
+use std::mem;
+
+use super::meldable_heap::MeldableHeap;
+
+/// A binomial tree node in child/sibling form: `child` is the child with the
+/// highest degree and `sibling` the next lower one. At the top level the
+/// `sibling` links chain the roots together instead, lowest degree first.
+pub struct Node<T> {
+    data: T,
+    degree: usize,
+    child: Option<Box<Node<T>>>,
+    sibling: Option<Box<Node<T>>>,
+}
+
+/// A list of heap-ordered binomial trees with at most one tree of each
+/// degree, like the set bits of `length` in binary. Melding adds two such
+/// lists the way binary numbers are added, carrying a linked tree into the
+/// next degree, so it costs O(log n).
+pub struct BinomialHeap<T> {
+    roots: Option<Box<Node<T>>>,
+    length: usize,
+}
+
+impl<T: Ord> BinomialHeap<T> {
+    pub fn new() -> Self {
+        BinomialHeap { roots: None, length: 0 }
+    }
+
+    pub fn from(data_vec: Vec<T>) -> Self {
+        let mut heap: BinomialHeap<T> = BinomialHeap::new();
+
+        for data in data_vec {
+            heap.insert(data);
+        }
+
+        return heap;
+    }
+
+    /// Adds every tree to `slots`, where slot `d` holds the tree of degree
+    /// `d`, then relinks the slots into the root list.
+    fn rebuild(&mut self, trees: Vec<Box<Node<T>>>) {
+        let mut slots: Vec<Option<Box<Node<T>>>> = Vec::new();
+
+        for mut tree in trees {
+            loop {
+                let degree: usize = tree.degree;
+                if degree >= slots.len() {
+                    slots.resize_with(degree + 1, || None);
+                }
+                match slots[degree].take() {
+                    Some(other) => tree = link(tree, other),
+                    None => {
+                        slots[degree] = Some(tree);
+                        break;
+                    }
+                }
+            }
+        }
+
+        let mut roots: Option<Box<Node<T>>> = None;
+        for mut tree in slots.into_iter().rev().flatten() {
+            tree.sibling = roots;
+            roots = Some(tree);
+        }
+        self.roots = roots;
+    }
+}
+
+/// Makes the root with the larger element the first child of the other.
+/// Both trees must have the same degree.
+fn link<T: Ord>(first: Box<Node<T>>, second: Box<Node<T>>) -> Box<Node<T>> {
+    let (mut parent, mut child): (Box<Node<T>>, Box<Node<T>>) = if second.data < first.data { (second, first) } else { (first, second) };
+
+    child.sibling = parent.child.take();
+    parent.child = Some(child);
+    parent.degree += 1;
+    return parent;
+}
+
+/// Unlinks every node of a sibling list, first to last.
+fn detach_siblings<T>(mut next: Option<Box<Node<T>>>) -> Vec<Box<Node<T>>> {
+    let mut nodes: Vec<Box<Node<T>>> = Vec::new();
+
+    while let Some(mut node) = next {
+        next = node.sibling.take();
+        nodes.push(node);
+    }
+
+    return nodes;
+}
+
+impl<T: Ord> MeldableHeap<T> for BinomialHeap<T> {
+    fn insert(&mut self, data: T) {
+        let mut trees: Vec<Box<Node<T>>> = detach_siblings(self.roots.take());
+        trees.insert(0, Box::new(Node { data, degree: 0, child: None, sibling: None }));
+        self.rebuild(trees);
+        self.length += 1;
+    }
+
+    fn pop_min(&mut self) -> Option<T> {
+        let mut trees: Vec<Box<Node<T>>> = detach_siblings(self.roots.take());
+        let min_index: usize = (0..trees.len()).min_by(|&a, &b| trees[a].data.cmp(&trees[b].data))?;
+
+        let mut min: Box<Node<T>> = trees.remove(min_index);
+        let mut children: Vec<Box<Node<T>>> = detach_siblings(min.child.take());
+        children.reverse();
+        trees.extend(children);
+
+        self.rebuild(trees);
+        self.length -= 1;
+        return Some(min.data);
+    }
+
+    /// Scans the O(log n) roots.
+    fn peek_min(&self) -> Option<&T> {
+        let mut min: Option<&T> = None;
+        let mut current_node: Option<&Node<T>> = self.roots.as_deref();
+
+        while let Some(node) = current_node {
+            if min.is_none_or(|min| node.data < *min) {
+                min = Some(&node.data);
+            }
+            current_node = node.sibling.as_deref();
+        }
+
+        return min;
+    }
+
+    fn meld(mut self, mut other: Self) -> Self {
+        let mut trees: Vec<Box<Node<T>>> = detach_siblings(self.roots.take());
+        trees.extend(detach_siblings(other.roots.take()));
+
+        self.rebuild(trees);
+        self.length += mem::take(&mut other.length);
+        return self;
+    }
+
+    fn len(&self) -> usize {
+        self.length
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &T> + '_> {
+        Box::new(Iter { stack: self.roots.as_deref().into_iter().collect() })
+    }
+}
+
+struct Iter<'a, T> {
+    stack: Vec<&'a Node<T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let node: &'a Node<T> = self.stack.pop()?;
+        self.stack.extend(node.child.as_deref());
+        self.stack.extend(node.sibling.as_deref());
+        return Some(&node.data);
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::queues::meldable_heap::behaviour;
+
+    #[test]
+    fn test_binomial_heap_behaviour() {
+        behaviour::starts_empty(BinomialHeap::new());
+        behaviour::pops_in_sorted_order(BinomialHeap::new());
+        behaviour::melds_two_heaps(BinomialHeap::new(), BinomialHeap::new());
+        behaviour::melds_with_empty(BinomialHeap::new(), BinomialHeap::new(), BinomialHeap::new());
+        behaviour::iterates_every_element(BinomialHeap::new());
+        behaviour::interleaves_insert_and_pop(BinomialHeap::new());
+        behaviour::survives_sorted_runs(BinomialHeap::new(), BinomialHeap::new());
+    }
+
+    fn degrees(heap: &BinomialHeap<i64>) -> Vec<usize> {
+        let mut degrees: Vec<usize> = Vec::new();
+        let mut current_node: Option<&Node<i64>> = heap.roots.as_deref();
+        while let Some(node) = current_node {
+            degrees.push(node.degree);
+            current_node = node.sibling.as_deref();
+        }
+        return degrees;
+    }
+
+    #[test]
+    fn test_roots_follow_binary_length() {
+        let mut heap: BinomialHeap<i64> = BinomialHeap::from((0..13).rev().collect());
+        assert_eq!(degrees(&heap), vec![0, 2, 3]);
+
+        heap = heap.meld(BinomialHeap::from(vec![20, 21, 22]));
+        assert_eq!(heap.len(), 16);
+        assert_eq!(degrees(&heap), vec![4]);
+
+        assert_eq!(heap.pop_min(), Some(0));
+        assert_eq!(degrees(&heap), vec![0, 1, 2, 3]);
+    }
+}
@@ -0,0 +1,330 @@
+use std::fmt::{Display, Error, Formatter};
+use std::sync::{Condvar, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
+use crate::arrays::ring_buffer::{FullPolicy, RingBuffer};
+
+/// Why a push failed. Each variant hands the element back.
+#[derive(PartialEq, Debug)]
+pub enum PushError<T> {
+    /// The queue was full and the push was not allowed to wait.
+    Full(T),
+    /// The queue stayed full for the whole timeout.
+    Timeout(T),
+    /// The queue has been closed.
+    Closed(T),
+}
+
+impl<T> PushError<T> {
+    pub fn into_inner(self) -> T {
+        match self {
+            PushError::Full(data) | PushError::Timeout(data) | PushError::Closed(data) => data,
+        }
+    }
+}
+
+impl<T> Display for PushError<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match self {
+            PushError::Full(_) => write!(f, "the queue is full"),
+            PushError::Timeout(_) => write!(f, "timed out waiting for room in the queue"),
+            PushError::Closed(_) => write!(f, "the queue is closed"),
+        }
+    }
+}
+
+/// Why a pop failed.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PopError {
+    /// The queue was empty and the pop was not allowed to wait.
+    Empty,
+    /// The queue stayed empty for the whole timeout.
+    Timeout,
+    /// The queue has been closed and everything in it has been popped.
+    Closed,
+}
+
+impl Display for PopError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match self {
+            PopError::Empty => write!(f, "the queue is empty"),
+            PopError::Timeout => write!(f, "timed out waiting for an element"),
+            PopError::Closed => write!(f, "the queue is closed and empty"),
+        }
+    }
+}
+
+/// How long a push or pop may wait for the queue to change.
+#[derive(Clone, Copy)]
+enum Wait {
+    Never,
+    Forever,
+    For(Duration),
+}
+
+struct State<T> {
+    buffer: RingBuffer<T>,
+    closed: bool,
+}
+
+/// A fixed-capacity queue for any number of producer and consumer threads.
+/// The elements sit in a `RingBuffer` behind one `Mutex`; producers wait on
+/// `not_full` and consumers on `not_empty`, so a slow stage holds back the
+/// stage feeding it.
+///
+/// Closing the queue refuses further pushes and wakes every waiter. The
+/// elements already queued can still be popped, and only then do pops
+/// report `Closed`.
+pub struct BoundedQueue<T> {
+    state: Mutex<State<T>>,
+    not_empty: Condvar,
+    not_full: Condvar,
+    capacity: usize,
+}
+
+impl<T> BoundedQueue<T> {
+    /// A capacity of zero is raised to one, since nothing could ever pass
+    /// through an empty queue.
+    pub fn new(capacity: usize) -> Self {
+        let capacity: usize = capacity.max(1);
+        BoundedQueue {
+            state: Mutex::new(State { buffer: RingBuffer::new(capacity, FullPolicy::Reject), closed: false }),
+            not_empty: Condvar::new(),
+            not_full: Condvar::new(),
+            capacity,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().buffer.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len() >= self.capacity
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.lock().closed
+    }
+
+    /// Refuses every later push and wakes all blocked threads. Closing twice
+    /// does nothing more.
+    pub fn close(&self) {
+        self.lock().closed = true;
+        self.not_empty.notify_all();
+        self.not_full.notify_all();
+    }
+
+    /// Waits as long as it takes for room.
+    pub fn push(&self, data: T) -> Result<(), PushError<T>> {
+        self.push_waiting(data, Wait::Forever)
+    }
+
+    pub fn try_push(&self, data: T) -> Result<(), PushError<T>> {
+        self.push_waiting(data, Wait::Never)
+    }
+
+    pub fn push_timeout(&self, data: T, timeout: Duration) -> Result<(), PushError<T>> {
+        self.push_waiting(data, Wait::For(timeout))
+    }
+
+    /// Waits as long as it takes for an element.
+    pub fn pop(&self) -> Result<T, PopError> {
+        self.pop_waiting(Wait::Forever)
+    }
+
+    pub fn try_pop(&self) -> Result<T, PopError> {
+        self.pop_waiting(Wait::Never)
+    }
+
+    pub fn pop_timeout(&self, timeout: Duration) -> Result<T, PopError> {
+        self.pop_waiting(Wait::For(timeout))
+    }
+
+    /// The state stays consistent even if a thread panicked while holding
+    /// the lock, since no method panics between its reads and writes.
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn push_waiting(&self, data: T, wait: Wait) -> Result<(), PushError<T>> {
+        let full = |state: &mut State<T>| !state.closed && state.buffer.len() >= self.capacity;
+        let mut state: MutexGuard<'_, State<T>> = self.lock();
+
+        state = match wait {
+            Wait::Never => state,
+            Wait::Forever => self.not_full.wait_while(state, full).unwrap_or_else(PoisonError::into_inner),
+            Wait::For(timeout) => self.not_full.wait_timeout_while(state, timeout, full).unwrap_or_else(PoisonError::into_inner).0,
+        };
+
+        if state.closed {
+            return Err(PushError::Closed(data));
+        }
+        if state.buffer.len() >= self.capacity {
+            return Err(match wait {
+                Wait::Never => PushError::Full(data),
+                _ => PushError::Timeout(data),
+            });
+        }
+
+        let _ = state.buffer.push_back(data);
+        drop(state);
+        self.not_empty.notify_one();
+        return Ok(());
+    }
+
+    fn pop_waiting(&self, wait: Wait) -> Result<T, PopError> {
+        let empty = |state: &mut State<T>| !state.closed && state.buffer.is_empty();
+        let mut state: MutexGuard<'_, State<T>> = self.lock();
+
+        state = match wait {
+            Wait::Never => state,
+            Wait::Forever => self.not_empty.wait_while(state, empty).unwrap_or_else(PoisonError::into_inner),
+            Wait::For(timeout) => self.not_empty.wait_timeout_while(state, timeout, empty).unwrap_or_else(PoisonError::into_inner).0,
+        };
+
+        if let Some(data) = state.buffer.pop_front() {
+            drop(state);
+            self.not_full.notify_one();
+            return Ok(data);
+        }
+        if state.closed {
+            return Err(PopError::Closed);
+        }
+
+        return Err(match wait {
+            Wait::Never => PopError::Empty,
+            _ => PopError::Timeout,
+        });
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+    use std::time::Instant;
+
+    #[test]
+    fn test_try_push_and_pop() {
+        let queue: BoundedQueue<i64> = BoundedQueue::new(3);
+        assert_eq!(queue.capacity(), 3);
+        assert_eq!(queue.try_pop(), Err(PopError::Empty));
+
+        for data in 1..=3 {
+            assert_eq!(queue.try_push(data), Ok(()));
+        }
+        assert!(queue.is_full());
+        assert_eq!(queue.try_push(4), Err(PushError::Full(4)));
+
+        assert_eq!(queue.try_pop(), Ok(1));
+        assert_eq!(queue.try_push(4), Ok(()));
+        assert_eq!(queue.len(), 3);
+        for data in 2..=4 {
+            assert_eq!(queue.pop(), Ok(data));
+        }
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_timeouts() {
+        let queue: BoundedQueue<i64> = BoundedQueue::new(1);
+        let timeout: Duration = Duration::from_millis(20);
+
+        let start: Instant = Instant::now();
+        assert_eq!(queue.pop_timeout(timeout), Err(PopError::Timeout));
+        assert!(start.elapsed() >= timeout);
+
+        assert_eq!(queue.push_timeout(1, timeout), Ok(()));
+        let start: Instant = Instant::now();
+        assert_eq!(queue.push_timeout(2, timeout), Err(PushError::Timeout(2)));
+        assert!(start.elapsed() >= timeout);
+        assert_eq!(queue.pop_timeout(timeout), Ok(1));
+    }
+
+    #[test]
+    fn test_timeout_ends_early_on_pop() {
+        let queue: BoundedQueue<i64> = BoundedQueue::new(1);
+        queue.push(1).unwrap();
+
+        thread::scope(|scope| {
+            scope.spawn(|| {
+                thread::sleep(Duration::from_millis(10));
+                assert_eq!(queue.pop(), Ok(1));
+            });
+            assert_eq!(queue.push_timeout(2, Duration::from_secs(10)), Ok(()));
+        });
+        assert_eq!(queue.try_pop(), Ok(2));
+    }
+
+    #[test]
+    fn test_close_wakes_waiters() {
+        let empty: BoundedQueue<i64> = BoundedQueue::new(2);
+        let full: BoundedQueue<i64> = BoundedQueue::new(1);
+        full.push(0).unwrap();
+
+        thread::scope(|scope| {
+            let popper = scope.spawn(|| empty.pop());
+            let pusher = scope.spawn(|| full.push(1));
+            thread::sleep(Duration::from_millis(20));
+            empty.close();
+            full.close();
+            assert_eq!(popper.join().unwrap(), Err(PopError::Closed));
+            assert_eq!(pusher.join().unwrap(), Err(PushError::Closed(1)));
+        });
+
+        assert!(full.is_closed());
+        assert_eq!(full.try_push(2).map_err(PushError::into_inner), Err(2));
+        assert_eq!(full.pop(), Ok(0));
+        assert_eq!(full.pop(), Err(PopError::Closed));
+        assert_eq!(full.pop_timeout(Duration::from_secs(10)), Err(PopError::Closed));
+    }
+
+    #[test]
+    fn test_many_producers_and_consumers() {
+        let queue: BoundedQueue<usize> = BoundedQueue::new(8);
+        let received: AtomicUsize = AtomicUsize::new(0);
+        let total: AtomicUsize = AtomicUsize::new(0);
+
+        thread::scope(|scope| {
+            let producers: Vec<_> = (0..4)
+                .map(|producer| {
+                    let queue: &BoundedQueue<usize> = &queue;
+                    scope.spawn(move || {
+                        for data in 0..1000 {
+                            queue.push(producer * 1000 + data).unwrap();
+                        }
+                    })
+                })
+                .collect();
+            for _ in 0..3 {
+                scope.spawn(|| {
+                    while let Ok(data) = queue.pop() {
+                        assert!(queue.len() <= queue.capacity());
+                        received.fetch_add(1, Ordering::Relaxed);
+                        total.fetch_add(data, Ordering::Relaxed);
+                    }
+                });
+            }
+
+            for producer in producers {
+                producer.join().unwrap();
+            }
+            queue.close();
+        });
+
+        assert_eq!(received.into_inner(), 4000);
+        assert_eq!(total.into_inner(), (0..4000).sum::<usize>());
+    }
+}
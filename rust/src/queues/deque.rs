@@ -0,0 +1,303 @@
+use std::cell::RefCell;
+use std::fmt::Error;
+use std::ops::Range;
+use std::rc::{Rc, Weak};
+
+use super::queue::Queue;
+use crate::linked_lists::dllwt_rc_refcell::{LinkedList, Node};
+
+/// A double-ended queue on the crate's doubly linked list. Both ends are
+/// held directly, so every push and pop is O(1), unlike `pop_from_end` on
+/// the singly linked list, which has to walk to the node before the tail.
+#[derive(PartialEq, Debug)]
+pub struct Deque<T> {
+    list: LinkedList<T>,
+}
+
+impl<T> Deque<T> {
+    pub fn new() -> Self {
+        Deque { list: LinkedList::new() }
+    }
+
+    /// Pushes the elements to the back in order, so the first one is at the
+    /// front.
+    pub fn from(data_vec: Vec<T>) -> Self {
+        Deque { list: LinkedList::from(data_vec) }
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.length
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    pub fn push_front(&mut self, data: T) {
+        self.list.push_to_front(data);
+    }
+
+    pub fn push_back(&mut self, data: T) {
+        self.list.push_to_end(data);
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.list.pop_from_front().ok()
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.list.pop_from_end().ok()
+    }
+
+    /// Removes the elements in `range` and hands them back in order. The
+    /// range is cut out of the list as a whole when this is called, so the
+    /// elements are gone even if the iterator is dropped unused. Costs
+    /// O(distance from the nearer end to each bound).
+    pub fn drain(&mut self, range: Range<usize>) -> Result<Drain<T>, Error> {
+        if range.start > range.end || range.end > self.list.length {
+            return Err(Error);
+        }
+
+        let mut drained: LinkedList<T> = self.list.split_off(range.start)?;
+        let mut rest: LinkedList<T> = drained.split_off(range.end - range.start)?;
+        self.list.append(&mut rest);
+
+        return Ok(Drain { list: drained });
+    }
+
+    /// Keeps only the elements for which `keep` returns true, visiting them
+    /// front to back. Each rejected node is unlinked where it stands, so the
+    /// whole pass is O(n).
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut keep: F) {
+        let mut current_node: Option<Rc<RefCell<Node<T>>>> = self.list.head.clone();
+
+        while let Some(node) = current_node {
+            current_node = node.borrow().next.clone();
+            let kept: bool = node.borrow().data.as_ref().is_some_and(&mut keep);
+            if !kept {
+                let _ = self.list.unlink(&node);
+            }
+        }
+    }
+
+    /// Hands back the underlying list, front of the deque first.
+    pub fn into_list(self) -> LinkedList<T> {
+        self.list
+    }
+}
+
+impl<T: Clone> Deque<T> {
+    pub fn front(&self) -> Option<T> {
+        self.list.into_front()
+    }
+
+    pub fn back(&self) -> Option<T> {
+        self.list.into_back()
+    }
+
+    /// Clones the elements front to back; `.rev()` walks back to front.
+    pub fn iter(&self) -> Iter<T> {
+        Iter { front: self.list.head.clone(), back: self.list.tail.clone(), remaining: self.list.length }
+    }
+}
+
+impl<T: Clone> Queue<T> for Deque<T> {
+    fn enqueue(&mut self, data: T) {
+        self.push_back(data);
+    }
+
+    fn dequeue(&mut self) -> Option<T> {
+        self.pop_front()
+    }
+
+    fn front(&self) -> Option<T> {
+        Deque::front(self)
+    }
+
+    fn len(&self) -> usize {
+        Deque::len(self)
+    }
+}
+
+/// Walks the deque from both ends, stopping once they meet.
+pub struct Iter<T> {
+    front: Option<Rc<RefCell<Node<T>>>>,
+    back: Option<Rc<RefCell<Node<T>>>>,
+    remaining: usize,
+}
+
+impl<T: Clone> Iterator for Iter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let node: Rc<RefCell<Node<T>>> = self.front.take()?;
+        self.front = node.borrow().next.clone();
+        self.remaining -= 1;
+        let data: Option<T> = node.borrow().data.clone();
+        data
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T: Clone> DoubleEndedIterator for Iter<T> {
+    fn next_back(&mut self) -> Option<T> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let node: Rc<RefCell<Node<T>>> = self.back.take()?;
+        self.back = node.borrow().prev.as_ref().and_then(Weak::upgrade);
+        self.remaining -= 1;
+        let data: Option<T> = node.borrow().data.clone();
+        data
+    }
+}
+
+impl<T: Clone> ExactSizeIterator for Iter<T> {}
+
+/// The elements taken out by `Deque::drain`, in their original order.
+pub struct Drain<T> {
+    list: LinkedList<T>,
+}
+
+impl<T> Iterator for Drain<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.list.pop_from_front().ok()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.length, Some(self.list.length))
+    }
+}
+
+impl<T> DoubleEndedIterator for Drain<T> {
+    fn next_back(&mut self) -> Option<T> {
+        self.list.pop_from_end().ok()
+    }
+}
+
+impl<T> ExactSizeIterator for Drain<T> {}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::queues::queue::behaviour;
+
+    #[test]
+    fn test_deque_queue_behaviour() {
+        behaviour::starts_empty(Deque::new());
+        behaviour::dequeues_in_order(Deque::new());
+        behaviour::front_does_not_remove(Deque::new());
+        behaviour::keeps_order_when_interleaved(Deque::new());
+        behaviour::survives_empty_dequeues(Deque::new());
+    }
+
+    #[test]
+    fn test_both_ends() {
+        let mut deque: Deque<i64> = Deque::new();
+        deque.push_back(2);
+        deque.push_front(1);
+        deque.push_back(3);
+        assert_eq!((deque.front(), deque.back()), (Some(1), Some(3)));
+        assert_eq!(deque.pop_back(), Some(3));
+        assert_eq!(deque.pop_back(), Some(2));
+        assert_eq!(deque.pop_back(), Some(1));
+        assert_eq!(deque.pop_back(), None);
+        assert_eq!(deque.pop_front(), None);
+        assert_eq!((deque.front(), deque.back()), (None, None));
+
+        deque.push_front(4);
+        assert_eq!(deque.pop_back(), Some(4));
+        assert!(deque.is_empty());
+    }
+
+    #[test]
+    fn test_iter_both_ways() {
+        let deque: Deque<i64> = Deque::from(vec![1, 2, 3, 4, 5]);
+        assert_eq!(deque.iter().collect::<Vec<i64>>(), vec![1, 2, 3, 4, 5]);
+        assert_eq!(deque.iter().rev().collect::<Vec<i64>>(), vec![5, 4, 3, 2, 1]);
+        assert_eq!(deque.iter().len(), 5);
+
+        let mut iter: Iter<i64> = deque.iter();
+        assert_eq!(iter.next(), Some(1));
+        assert_eq!(iter.next_back(), Some(5));
+        assert_eq!(iter.next_back(), Some(4));
+        assert_eq!(iter.next(), Some(2));
+        assert_eq!(iter.next(), Some(3));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    fn test_drain() {
+        let mut deque: Deque<i64> = Deque::from((0..10).collect());
+        assert_eq!(deque.drain(3..6).unwrap().collect::<Vec<i64>>(), vec![3, 4, 5]);
+        assert_eq!(deque.iter().collect::<Vec<i64>>(), vec![0, 1, 2, 6, 7, 8, 9]);
+        assert_eq!(deque.iter().rev().collect::<Vec<i64>>(), vec![9, 8, 7, 6, 2, 1, 0]);
+
+        assert_eq!(deque.drain(5..7).unwrap().rev().collect::<Vec<i64>>(), vec![9, 8]);
+        assert_eq!(deque.back(), Some(7));
+        assert_eq!(deque.drain(0..1).unwrap().len(), 1);
+        assert_eq!(deque.front(), Some(1));
+        assert_eq!(deque.drain(2..2).unwrap().count(), 0);
+        assert!(deque.drain(2..5).is_err());
+        assert_eq!(deque.len(), 4);
+
+        let drained: Drain<i64> = deque.drain(0..4).unwrap();
+        drop(drained);
+        assert!(deque.is_empty());
+        assert_eq!(deque.back(), None);
+    }
+
+    #[test]
+    fn test_retain() {
+        let mut deque: Deque<i64> = Deque::from((0..10).collect());
+        deque.retain(|data| data % 3 != 0);
+        assert_eq!(deque.iter().collect::<Vec<i64>>(), vec![1, 2, 4, 5, 7, 8]);
+        assert_eq!(deque.iter().rev().collect::<Vec<i64>>(), vec![8, 7, 5, 4, 2, 1]);
+
+        let mut seen: Vec<i64> = Vec::new();
+        deque.retain(|data| {
+            seen.push(*data);
+            *data > 4
+        });
+        assert_eq!(seen, vec![1, 2, 4, 5, 7, 8]);
+        assert_eq!((deque.front(), deque.back()), (Some(5), Some(8)));
+
+        deque.retain(|_| false);
+        assert!(deque.is_empty());
+        deque.push_back(1);
+        assert_eq!(deque.into_list(), LinkedList::from(vec![1]));
+    }
+
+    #[test]
+    fn test_work_queue_churn() {
+        let mut deque: Deque<usize> = Deque::new();
+        for round in 0..100_000 {
+            deque.push_back(round);
+            if round % 3 == 0 {
+                assert_eq!(deque.pop_back(), Some(round));
+            }
+        }
+        assert_eq!(deque.len(), 66_666);
+        assert_eq!(deque.front(), Some(1));
+    }
+
+    #[test]
+    fn test_long_deques_compare() {
+        let deque: Deque<usize> = Deque::from((0..300_000).collect());
+        assert_eq!(deque, Deque::from((0..300_000).collect()));
+        assert_ne!(deque, Deque::from((1..300_001).collect()));
+    }
+}
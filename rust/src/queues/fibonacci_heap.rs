@@ -0,0 +1,389 @@
+use std::cell::RefCell;
+use std::fmt::Error;
+use std::rc::{Rc, Weak};
+
+/// A node of a Fibonacci heap. Siblings form a doubly linked list through
+/// `next` and `prev`, the same way as in `linked_lists::dllwt_rc_refcell`,
+/// and `child` is the first node of the children's list.
+pub struct Node<T> {
+    data: T,
+    parent: Option<Weak<RefCell<Node<T>>>>,
+    child: Option<Rc<RefCell<Node<T>>>>,
+    next: Option<Rc<RefCell<Node<T>>>>,
+    prev: Option<Weak<RefCell<Node<T>>>>,
+    degree: usize,
+    /// Set when the node has lost a child since it last became a child
+    /// itself. Losing a second one cuts it from its parent as well.
+    marked: bool,
+}
+
+/// Names an element of a `FibonacciHeap`. It goes stale once its element
+/// is deleted, and stays valid when its heap is melded into another.
+pub struct Handle<T>(Weak<RefCell<Node<T>>>);
+
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        Handle(self.0.clone())
+    }
+}
+
+/// A min-heap kept as a list of heap-ordered trees. Insert and meld only add
+/// to the root list and decrease-key only cuts, all in O(1) amortised. The
+/// trees are merged by degree only when the minimum is deleted, which costs
+/// O(log n) amortised.
+pub struct FibonacciHeap<T> {
+    roots: Option<Rc<RefCell<Node<T>>>>,
+    last_root: Option<Weak<RefCell<Node<T>>>>,
+    min: Option<Rc<RefCell<Node<T>>>>,
+    length: usize,
+}
+
+impl<T: Ord> FibonacciHeap<T> {
+    pub fn new() -> Self {
+        FibonacciHeap { roots: None, last_root: None, min: None, length: 0 }
+    }
+
+    pub fn from(data_vec: Vec<T>) -> Self {
+        let mut heap: FibonacciHeap<T> = FibonacciHeap::new();
+
+        for data in data_vec {
+            heap.insert(data);
+        }
+
+        return heap;
+    }
+
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    pub fn insert(&mut self, data: T) -> Handle<T> {
+        let node: Rc<RefCell<Node<T>>> = Rc::new(RefCell::new(Node {
+            data,
+            parent: None,
+            child: None,
+            next: None,
+            prev: None,
+            degree: 0,
+            marked: false,
+        }));
+        let handle: Handle<T> = Handle(Rc::downgrade(&node));
+
+        self.add_root(node.clone());
+        self.update_min(node);
+        self.length += 1;
+        return handle;
+    }
+
+    pub fn delete_min(&mut self) -> Option<T> {
+        let min: Rc<RefCell<Node<T>>> = self.min.take()?;
+
+        let mut trees: Vec<Rc<RefCell<Node<T>>>> = detach_siblings(self.roots.take());
+        self.last_root = None;
+        trees.retain(|tree| !Rc::ptr_eq(tree, &min));
+
+        let children: Vec<Rc<RefCell<Node<T>>>> = detach_siblings(min.borrow_mut().child.take());
+        for child in children {
+            child.borrow_mut().parent = None;
+            trees.push(child);
+        }
+
+        self.consolidate(trees);
+        self.length -= 1;
+        return Rc::try_unwrap(min).ok().map(|node| node.into_inner().data);
+    }
+
+    /// Replaces the element behind `handle` with a smaller or equal one. If
+    /// that breaks heap order, the node is cut into the root list, and so is
+    /// every marked ancestor above it. The handle must come from this heap
+    /// or one melded into it.
+    pub fn decrease_key(&mut self, handle: &Handle<T>, data: T) -> Result<(), Error> {
+        let node: Rc<RefCell<Node<T>>> = handle.0.upgrade().ok_or(Error)?;
+        if data > node.borrow().data {
+            return Err(Error);
+        }
+        node.borrow_mut().data = data;
+
+        let parent: Option<Rc<RefCell<Node<T>>>> = node.borrow().parent.as_ref().and_then(Weak::upgrade);
+        if let Some(parent) = parent {
+            if node.borrow().data < parent.borrow().data {
+                self.cut(&node, &parent);
+                self.cascading_cut(parent);
+            }
+        }
+
+        self.update_min(node);
+        return Ok(());
+    }
+
+    /// Moves every element of `other` into this heap in O(1) by joining the
+    /// two root lists. Handles into `other` keep working on this heap.
+    pub fn meld(&mut self, mut other: FibonacciHeap<T>) {
+        let Some(other_roots) = other.roots.take() else {
+            return;
+        };
+
+        match self.last_root.as_ref().and_then(Weak::upgrade) {
+            Some(last_root) => {
+                other_roots.borrow_mut().prev = Some(Rc::downgrade(&last_root));
+                last_root.borrow_mut().next = Some(other_roots);
+            }
+            None => self.roots = Some(other_roots),
+        }
+
+        self.last_root = other.last_root.take();
+        self.length += std::mem::take(&mut other.length);
+        if let Some(other_min) = other.min.take() {
+            self.update_min(other_min);
+        }
+    }
+
+    fn update_min(&mut self, node: Rc<RefCell<Node<T>>>) {
+        let smaller: bool = match &self.min {
+            Some(min) => node.borrow().data < min.borrow().data,
+            None => true,
+        };
+        if smaller {
+            self.min = Some(node);
+        }
+    }
+
+    fn add_root(&mut self, node: Rc<RefCell<Node<T>>>) {
+        let old_roots: Option<Rc<RefCell<Node<T>>>> = self.roots.take();
+        match &old_roots {
+            Some(old_roots) => old_roots.borrow_mut().prev = Some(Rc::downgrade(&node)),
+            None => self.last_root = Some(Rc::downgrade(&node)),
+        }
+        node.borrow_mut().next = old_roots;
+        self.roots = Some(node);
+    }
+
+    /// Links trees of equal degree until no two roots share one, then
+    /// rebuilds the root list and finds the new minimum.
+    fn consolidate(&mut self, trees: Vec<Rc<RefCell<Node<T>>>>) {
+        let mut by_degree: Vec<Option<Rc<RefCell<Node<T>>>>> = Vec::new();
+
+        for mut tree in trees {
+            loop {
+                let degree: usize = tree.borrow().degree;
+                if degree >= by_degree.len() {
+                    by_degree.resize(degree + 1, None);
+                }
+                match by_degree[degree].take() {
+                    Some(other) => tree = link(tree, other),
+                    None => {
+                        by_degree[degree] = Some(tree);
+                        break;
+                    }
+                }
+            }
+        }
+
+        for tree in by_degree.into_iter().flatten() {
+            self.add_root(tree.clone());
+            self.update_min(tree);
+        }
+    }
+
+    /// Moves `node` from `parent`'s children to the root list.
+    fn cut(&mut self, node: &Rc<RefCell<Node<T>>>, parent: &Rc<RefCell<Node<T>>>) {
+        let prev: Option<Rc<RefCell<Node<T>>>> = node.borrow_mut().prev.take().and_then(|prev| prev.upgrade());
+        let next: Option<Rc<RefCell<Node<T>>>> = node.borrow_mut().next.take();
+
+        if let Some(next) = &next {
+            next.borrow_mut().prev = prev.as_ref().map(Rc::downgrade);
+        }
+        match prev {
+            Some(prev) => prev.borrow_mut().next = next,
+            None => parent.borrow_mut().child = next,
+        }
+
+        parent.borrow_mut().degree -= 1;
+        node.borrow_mut().parent = None;
+        node.borrow_mut().marked = false;
+        self.add_root(node.clone());
+    }
+
+    fn cascading_cut(&mut self, mut node: Rc<RefCell<Node<T>>>) {
+        loop {
+            let parent: Option<Rc<RefCell<Node<T>>>> = node.borrow().parent.as_ref().and_then(Weak::upgrade);
+            let Some(parent) = parent else {
+                return;
+            };
+            if !node.borrow().marked {
+                node.borrow_mut().marked = true;
+                return;
+            }
+            self.cut(&node, &parent);
+            node = parent;
+        }
+    }
+}
+
+impl<T: Ord + Clone> FibonacciHeap<T> {
+    pub fn find_min(&self) -> Option<T> {
+        self.min.as_ref().map(|min| min.borrow().data.clone())
+    }
+
+    /// The element behind `handle`, or `None` once it has been deleted.
+    pub fn get(&self, handle: &Handle<T>) -> Option<T> {
+        handle.0.upgrade().map(|node| node.borrow().data.clone())
+    }
+}
+
+/// Unlinks every node of a sibling list, first to last.
+fn detach_siblings<T>(first: Option<Rc<RefCell<Node<T>>>>) -> Vec<Rc<RefCell<Node<T>>>> {
+    let mut nodes: Vec<Rc<RefCell<Node<T>>>> = Vec::new();
+    let mut next: Option<Rc<RefCell<Node<T>>>> = first;
+
+    while let Some(node) = next {
+        next = node.borrow_mut().next.take();
+        node.borrow_mut().prev = None;
+        nodes.push(node);
+    }
+
+    return nodes;
+}
+
+/// Makes the root with the larger element the first child of the other and
+/// returns the new root, whose degree goes up by one.
+fn link<T: Ord>(first: Rc<RefCell<Node<T>>>, second: Rc<RefCell<Node<T>>>) -> Rc<RefCell<Node<T>>> {
+    let (parent, child) = if second.borrow().data < first.borrow().data {
+        (second, first)
+    } else {
+        (first, second)
+    };
+
+    let old_child: Option<Rc<RefCell<Node<T>>>> = parent.borrow_mut().child.take();
+    if let Some(old_child) = &old_child {
+        old_child.borrow_mut().prev = Some(Rc::downgrade(&child));
+    }
+    {
+        let mut child_node = child.borrow_mut();
+        child_node.next = old_child;
+        child_node.parent = Some(Rc::downgrade(&parent));
+        child_node.marked = false;
+    }
+    parent.borrow_mut().child = Some(child);
+    parent.borrow_mut().degree += 1;
+
+    return parent;
+}
+
+/// Frees the trees with an explicit stack instead of recursing through
+/// every `child` and `next`.
+impl<T> Drop for FibonacciHeap<T> {
+    fn drop(&mut self) {
+        self.min = None;
+        let mut stack: Vec<Rc<RefCell<Node<T>>>> = self.roots.take().into_iter().collect();
+
+        while let Some(node) = stack.pop() {
+            let mut node = node.borrow_mut();
+            stack.extend(node.child.take());
+            stack.extend(node.next.take());
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drain(heap: &mut FibonacciHeap<i64>) -> Vec<i64> {
+        std::iter::from_fn(|| heap.delete_min()).collect()
+    }
+
+    #[test]
+    fn test_insert_and_delete_min() {
+        let mut heap: FibonacciHeap<i64> = FibonacciHeap::new();
+        assert_eq!(heap.find_min(), None);
+        assert_eq!(heap.delete_min(), None);
+
+        for value in [5, 3, 8, 1, 9, 2, 7, 3] {
+            heap.insert(value);
+        }
+        assert_eq!(heap.find_min(), Some(1));
+        assert_eq!(heap.len(), 8);
+        assert_eq!(drain(&mut heap), vec![1, 2, 3, 3, 5, 7, 8, 9]);
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn test_consolidate_by_degree() {
+        let mut heap: FibonacciHeap<i64> = FibonacciHeap::from((0..16).rev().collect());
+        assert_eq!(heap.delete_min(), Some(0));
+
+        let degrees: Vec<usize> = detach_siblings(heap.roots.clone()).iter().map(|root| root.borrow().degree).collect();
+        let mut sorted: Vec<usize> = degrees.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(sorted.len(), degrees.len());
+        assert_eq!(degrees.iter().map(|degree| 1 << degree).sum::<usize>(), 15);
+    }
+
+    #[test]
+    fn test_decrease_key_cascades() {
+        let mut heap: FibonacciHeap<i64> = FibonacciHeap::new();
+        let handles: Vec<Handle<i64>> = (0..33).map(|i| heap.insert(100 + i)).collect();
+        assert_eq!(heap.delete_min(), Some(100));
+        assert_eq!(heap.find_min(), Some(101));
+
+        for (i, handle) in handles.iter().enumerate().skip(20).rev() {
+            assert_eq!(heap.decrease_key(handle, 50 - i as i64), Ok(()));
+        }
+        assert_eq!(heap.decrease_key(&handles[3], 200), Err(Error));
+        assert_eq!(heap.decrease_key(&handles[0], 0), Err(Error));
+        assert_eq!(heap.get(&handles[32]), Some(18));
+
+        let values: Vec<i64> = drain(&mut heap);
+        let mut expected: Vec<i64> = (18..31).chain(101..120).collect();
+        expected.sort();
+        assert_eq!(values, expected);
+    }
+
+    #[test]
+    fn test_decrease_key_of_every_position() {
+        let mut heap: FibonacciHeap<i64> = FibonacciHeap::new();
+        let handles: Vec<Handle<i64>> = (0..64).map(|i| heap.insert(1000 + (i * 37) % 64)).collect();
+        heap.delete_min();
+        heap.delete_min();
+
+        for (i, handle) in handles.iter().enumerate() {
+            if heap.get(handle).is_some() {
+                assert_eq!(heap.decrease_key(handle, i as i64), Ok(()));
+            }
+        }
+        let values: Vec<i64> = drain(&mut heap);
+        assert_eq!(values.len(), 62);
+        assert!(values.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_meld_keeps_handles() {
+        let mut heap: FibonacciHeap<i64> = FibonacciHeap::from(vec![4, 8, 6]);
+        let mut other: FibonacciHeap<i64> = FibonacciHeap::from(vec![5, 7]);
+        let handle: Handle<i64> = other.insert(9);
+        other.delete_min();
+
+        heap.meld(other);
+        heap.meld(FibonacciHeap::new());
+        assert_eq!(heap.len(), 5);
+        assert_eq!(heap.decrease_key(&handle, 1), Ok(()));
+        heap.insert(3);
+        assert_eq!(drain(&mut heap), vec![1, 3, 4, 6, 7, 8]);
+    }
+
+    #[test]
+    fn test_meld_into_empty() {
+        let mut heap: FibonacciHeap<i64> = FibonacciHeap::new();
+        heap.meld(FibonacciHeap::from(vec![2, 1]));
+        heap.insert(0);
+        assert_eq!(drain(&mut heap), vec![0, 1, 2]);
+    }
+}
@@ -0,0 +1,166 @@
+use std::mem;
+
+use super::meldable_heap::MeldableHeap;
+
+pub struct Node<T> {
+    data: T,
+    /// The length of the shortest path down to a missing child, counting
+    /// this node.
+    rank: usize,
+    left: Option<Box<Node<T>>>,
+    right: Option<Box<Node<T>>>,
+}
+
+/// A heap-ordered binary tree where every left child's rank is at least its
+/// sibling's. The right spine is then at most log(n + 1) long, and melding
+/// only ever walks down right spines.
+pub struct LeftistHeap<T> {
+    root: Option<Box<Node<T>>>,
+    length: usize,
+}
+
+impl<T: Ord> LeftistHeap<T> {
+    pub fn new() -> Self {
+        LeftistHeap { root: None, length: 0 }
+    }
+
+    pub fn from(data_vec: Vec<T>) -> Self {
+        let mut heap: LeftistHeap<T> = LeftistHeap::new();
+
+        for data in data_vec {
+            heap.insert(data);
+        }
+
+        return heap;
+    }
+}
+
+fn rank<T>(node: &Option<Box<Node<T>>>) -> usize {
+    node.as_ref().map_or(0, |node| node.rank)
+}
+
+/// Merges the right spines top down, then walks back up the merged spine
+/// swapping children wherever the right one now has the higher rank.
+fn merge<T: Ord>(mut first: Option<Box<Node<T>>>, mut second: Option<Box<Node<T>>>) -> Option<Box<Node<T>>> {
+    let mut spine: Vec<Box<Node<T>>> = Vec::new();
+
+    let mut merged: Option<Box<Node<T>>> = loop {
+        match (first, second) {
+            (None, rest) | (rest, None) => break rest,
+            (Some(a), Some(b)) => {
+                let (mut smaller, larger): (Box<Node<T>>, Box<Node<T>>) = if b.data < a.data { (b, a) } else { (a, b) };
+                first = smaller.right.take();
+                second = Some(larger);
+                spine.push(smaller);
+            }
+        }
+    };
+
+    while let Some(mut node) = spine.pop() {
+        node.right = merged;
+        if rank(&node.left) < rank(&node.right) {
+            mem::swap(&mut node.left, &mut node.right);
+        }
+        node.rank = rank(&node.right) + 1;
+        merged = Some(node);
+    }
+
+    return merged;
+}
+
+impl<T: Ord> MeldableHeap<T> for LeftistHeap<T> {
+    fn insert(&mut self, data: T) {
+        let node: Box<Node<T>> = Box::new(Node { data, rank: 1, left: None, right: None });
+        self.root = merge(self.root.take(), Some(node));
+        self.length += 1;
+    }
+
+    fn pop_min(&mut self) -> Option<T> {
+        let mut root: Box<Node<T>> = self.root.take()?;
+        self.root = merge(root.left.take(), root.right.take());
+        self.length -= 1;
+        return Some(root.data);
+    }
+
+    fn peek_min(&self) -> Option<&T> {
+        self.root.as_ref().map(|root| &root.data)
+    }
+
+    fn meld(mut self, mut other: Self) -> Self {
+        self.root = merge(self.root.take(), other.root.take());
+        self.length += mem::take(&mut other.length);
+        return self;
+    }
+
+    fn len(&self) -> usize {
+        self.length
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &T> + '_> {
+        Box::new(Iter { stack: self.root.as_deref().into_iter().collect() })
+    }
+}
+
+struct Iter<'a, T> {
+    stack: Vec<&'a Node<T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let node: &'a Node<T> = self.stack.pop()?;
+        self.stack.extend(node.left.as_deref());
+        self.stack.extend(node.right.as_deref());
+        return Some(&node.data);
+    }
+}
+
+/// Left spines can be as long as the heap, so the nodes are freed with an
+/// explicit stack rather than by recursion.
+impl<T> Drop for LeftistHeap<T> {
+    fn drop(&mut self) {
+        let mut stack: Vec<Box<Node<T>>> = self.root.take().into_iter().collect();
+
+        while let Some(mut node) = stack.pop() {
+            stack.extend(node.left.take());
+            stack.extend(node.right.take());
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::queues::meldable_heap::behaviour;
+
+    #[test]
+    fn test_leftist_heap_behaviour() {
+        behaviour::starts_empty(LeftistHeap::new());
+        behaviour::pops_in_sorted_order(LeftistHeap::new());
+        behaviour::melds_two_heaps(LeftistHeap::new(), LeftistHeap::new());
+        behaviour::melds_with_empty(LeftistHeap::new(), LeftistHeap::new(), LeftistHeap::new());
+        behaviour::iterates_every_element(LeftistHeap::new());
+        behaviour::interleaves_insert_and_pop(LeftistHeap::new());
+        behaviour::survives_sorted_runs(LeftistHeap::new(), LeftistHeap::new());
+    }
+
+    fn check_ranks<T>(node: &Option<Box<Node<T>>>) -> usize {
+        let Some(node) = node else {
+            return 0;
+        };
+        let (left, right): (usize, usize) = (check_ranks(&node.left), check_ranks(&node.right));
+        assert!(left >= right);
+        assert_eq!(node.rank, right + 1);
+        return node.rank;
+    }
+
+    #[test]
+    fn test_right_spine_stays_short() {
+        let heap: LeftistHeap<i64> = LeftistHeap::from((0..1000).map(|i| (i * 613) % 1000).collect());
+        let root_rank: usize = check_ranks(&heap.root);
+        assert!(root_rank <= 10);
+    }
+}
@@ -0,0 +1,80 @@
+use std::fmt::Debug;
+
+use super::queue::Queue;
+use crate::linked_lists::sllwt_rc_refcell::LinkedList;
+
+/// A queue on top of the crate's singly linked list with a tail: elements
+/// join at the tail with `push_to_end` and leave from the head with
+/// `pop_from_front`.
+///
+/// `enqueue` is O(1), but `dequeue` is O(n): `pop_from_front` renumbers
+/// every remaining node through `update_indices`.
+#[derive(PartialEq, Debug)]
+pub struct ListQueue<T> {
+    list: LinkedList<T>,
+}
+
+impl<T> ListQueue<T>
+where
+    T: Clone + PartialEq + Debug,
+{
+    pub fn new() -> Self {
+        ListQueue { list: LinkedList::new() }
+    }
+
+    /// Enqueues the elements in order, so the first one is at the front.
+    pub fn from(data_vec: Vec<T>) -> Self {
+        ListQueue { list: LinkedList::from(data_vec) }
+    }
+
+    /// Hands back the underlying list, front of the queue first.
+    pub fn into_list(self) -> LinkedList<T> {
+        self.list
+    }
+}
+
+impl<T> Queue<T> for ListQueue<T>
+where
+    T: Clone + PartialEq + Debug,
+{
+    fn enqueue(&mut self, data: T) {
+        self.list.push_to_end(data);
+    }
+
+    fn dequeue(&mut self) -> Option<T> {
+        self.list.pop_from_front().ok()
+    }
+
+    fn front(&self) -> Option<T> {
+        self.list.head.as_ref().and_then(|head| head.borrow().data.clone())
+    }
+
+    fn len(&self) -> usize {
+        self.list.length
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::queues::queue::behaviour;
+
+    #[test]
+    fn test_list_queue_behaviour() {
+        behaviour::starts_empty(ListQueue::new());
+        behaviour::dequeues_in_order(ListQueue::new());
+        behaviour::front_does_not_remove(ListQueue::new());
+        behaviour::keeps_order_when_interleaved(ListQueue::new());
+        behaviour::survives_empty_dequeues(ListQueue::new());
+    }
+
+    #[test]
+    fn test_list_queue_from() {
+        let mut queue: ListQueue<i64> = ListQueue::from(vec![1, 2, 3]);
+        assert_eq!(queue.dequeue(), Some(1));
+        queue.enqueue(4);
+        assert_eq!(queue.into_list(), LinkedList::from(vec![2, 3, 4]));
+    }
+}
@@ -0,0 +1,137 @@
+/// A min-heap that two instances can be combined into in O(log n), without
+/// copying either one's elements into the other.
+pub trait MeldableHeap<T: Ord>: Sized {
+    fn insert(&mut self, data: T);
+
+    fn pop_min(&mut self) -> Option<T>;
+
+    fn peek_min(&self) -> Option<&T>;
+
+    /// Takes both heaps apart and builds one heap out of their nodes.
+    fn meld(self, other: Self) -> Self;
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Every element once, in no particular order.
+    fn iter(&self) -> Box<dyn Iterator<Item = &T> + '_>;
+}
+
+
+
+/// Behaviour every `MeldableHeap` must have. Each implementation's tests
+/// run these against fresh, empty heaps.
+#[cfg(test)]
+pub(crate) mod behaviour {
+    use super::MeldableHeap;
+
+    /// A fixed shuffle of `0..count` with every value repeated twice.
+    fn shuffled(count: i64) -> Vec<i64> {
+        (0..2 * count).map(|i| (i * 7919) % count).collect()
+    }
+
+    fn drain<H: MeldableHeap<i64>>(heap: &mut H) -> Vec<i64> {
+        let mut values: Vec<i64> = Vec::new();
+        while let Some(&min) = heap.peek_min() {
+            assert_eq!(heap.pop_min(), Some(min));
+            values.push(min);
+        }
+        return values;
+    }
+
+    pub fn starts_empty<H: MeldableHeap<i64>>(mut heap: H) {
+        assert!(heap.is_empty());
+        assert_eq!(heap.len(), 0);
+        assert_eq!(heap.peek_min(), None);
+        assert_eq!(heap.pop_min(), None);
+        assert_eq!(heap.iter().count(), 0);
+    }
+
+    pub fn pops_in_sorted_order<H: MeldableHeap<i64>>(mut heap: H) {
+        for data in shuffled(101) {
+            heap.insert(data);
+        }
+        assert_eq!(heap.len(), 202);
+
+        let mut expected: Vec<i64> = shuffled(101);
+        expected.sort();
+        assert_eq!(drain(&mut heap), expected);
+        assert!(heap.is_empty());
+    }
+
+    pub fn melds_two_heaps<H: MeldableHeap<i64>>(mut first: H, mut second: H) {
+        for data in shuffled(37) {
+            first.insert(data * 2);
+        }
+        for data in shuffled(53) {
+            second.insert(data * 2 + 1);
+        }
+
+        let mut heap: H = first.meld(second);
+        assert_eq!(heap.len(), 180);
+        assert_eq!(heap.peek_min(), Some(&0));
+
+        let mut expected: Vec<i64> = shuffled(37).into_iter().map(|data| data * 2).chain(shuffled(53).into_iter().map(|data| data * 2 + 1)).collect();
+        expected.sort();
+        assert_eq!(drain(&mut heap), expected);
+    }
+
+    pub fn melds_with_empty<H: MeldableHeap<i64>>(mut first: H, second: H, third: H) {
+        first.insert(2);
+        first.insert(1);
+
+        let heap: H = first.meld(second);
+        let mut heap: H = third.meld(heap);
+        assert_eq!(heap.len(), 2);
+        assert_eq!(drain(&mut heap), vec![1, 2]);
+    }
+
+    pub fn iterates_every_element<H: MeldableHeap<i64>>(mut heap: H) {
+        for data in shuffled(64) {
+            heap.insert(data);
+        }
+        heap.pop_min();
+
+        let mut values: Vec<i64> = heap.iter().copied().collect();
+        values.sort();
+        let mut expected: Vec<i64> = shuffled(64);
+        expected.sort();
+        assert_eq!(values, expected[1..]);
+    }
+
+    /// Mixes inserts and pops unevenly and checks the heap against a sorted
+    /// `Vec` model.
+    pub fn interleaves_insert_and_pop<H: MeldableHeap<i64>>(mut heap: H) {
+        let mut model: Vec<i64> = Vec::new();
+
+        for round in 0..300 {
+            for offset in 0..(round % 7) {
+                let data: i64 = (round * 31 + offset * 17) % 97;
+                heap.insert(data);
+                model.push(data);
+            }
+            model.sort_by(|a, b| b.cmp(a));
+            for _ in 0..(round % 5) {
+                assert_eq!(heap.pop_min(), model.pop());
+            }
+            assert_eq!(heap.len(), model.len());
+            assert_eq!(heap.peek_min(), model.last());
+        }
+    }
+
+    /// Sorted input builds the most lopsided shapes; dropping what is left
+    /// must not overflow the stack.
+    pub fn survives_sorted_runs<H: MeldableHeap<i64>>(mut ascending: H, mut descending: H) {
+        for data in 0..100_000 {
+            ascending.insert(data);
+            descending.insert(-data);
+        }
+        assert_eq!(ascending.pop_min(), Some(0));
+        assert_eq!(descending.pop_min(), Some(-99_999));
+        assert_eq!(ascending.peek_min(), Some(&1));
+        assert_eq!(descending.peek_min(), Some(&-99_998));
+    }
+}
@@ -0,0 +1,388 @@
+use std::cmp::Ordering;
+use std::slice;
+
+/// A double-ended priority queue in one `Vec`. The tree is laid out like a
+/// binary heap, but levels alternate: an element on an even level (the root
+/// is level 0) is the smallest in its subtree, and one on an odd level the
+/// largest. The minimum is then the root and the maximum one of its two
+/// children.
+///
+/// A bounded heap keeps only the `capacity` largest elements it has seen,
+/// evicting its minimum to make room, so the top k can be read from the max
+/// end while the cut-off sits at the min end.
+pub struct MinMaxHeap<T> {
+    elements: Vec<T>,
+    capacity: Option<usize>,
+}
+
+impl<T: Ord> MinMaxHeap<T> {
+    pub fn new() -> Self {
+        MinMaxHeap { elements: Vec::new(), capacity: None }
+    }
+
+    pub fn bounded(capacity: usize) -> Self {
+        MinMaxHeap { elements: Vec::with_capacity(capacity), capacity: Some(capacity) }
+    }
+
+    /// Builds an unbounded heap in O(n), trickling every parent down, last
+    /// one first.
+    pub fn from(data_vec: Vec<T>) -> Self {
+        let mut heap: MinMaxHeap<T> = MinMaxHeap { elements: data_vec, capacity: None };
+
+        for index in (0..heap.elements.len() / 2).rev() {
+            heap.trickle_down(index);
+        }
+
+        return heap;
+    }
+
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    pub fn capacity(&self) -> Option<usize> {
+        self.capacity
+    }
+
+    /// Adds `data`. A full bounded heap hands back the element it evicted,
+    /// which is `data` itself when it is no larger than the minimum.
+    pub fn push(&mut self, data: T) -> Option<T> {
+        if let Some(capacity) = self.capacity {
+            if self.elements.len() >= capacity {
+                if capacity == 0 {
+                    return Some(data);
+                }
+                return Some(self.push_pop_min(data));
+            }
+        }
+
+        self.elements.push(data);
+        self.bubble_up(self.elements.len() - 1);
+        return None;
+    }
+
+    pub fn peek_min(&self) -> Option<&T> {
+        self.elements.first()
+    }
+
+    pub fn peek_max(&self) -> Option<&T> {
+        self.elements.get(self.max_index()?)
+    }
+
+    pub fn pop_min(&mut self) -> Option<T> {
+        if self.elements.is_empty() {
+            return None;
+        }
+
+        let data: T = self.elements.swap_remove(0);
+        if !self.elements.is_empty() {
+            self.trickle_down(0);
+        }
+        return Some(data);
+    }
+
+    pub fn pop_max(&mut self) -> Option<T> {
+        let index: usize = self.max_index()?;
+
+        let data: T = self.elements.swap_remove(index);
+        if index < self.elements.len() {
+            self.trickle_down(index);
+        }
+        return Some(data);
+    }
+
+    /// Pushes `data` and pops the minimum in one O(log n) pass, or in O(1)
+    /// when `data` would come straight back out.
+    pub fn push_pop_min(&mut self, data: T) -> T {
+        match self.elements.first() {
+            Some(min) if *min < data => {
+                let min: T = std::mem::replace(&mut self.elements[0], data);
+                self.trickle_down(0);
+                min
+            }
+            _ => data,
+        }
+    }
+
+    /// Pushes `data` and pops the maximum, like `push_pop_min`.
+    pub fn push_pop_max(&mut self, data: T) -> T {
+        match self.peek_max() {
+            Some(max) if *max > data => self.replace_at_max(data),
+            _ => data,
+        }
+    }
+
+    /// Pops the minimum and then pushes `data`, even if `data` is smaller.
+    pub fn replace_min(&mut self, data: T) -> Option<T> {
+        if self.elements.is_empty() {
+            self.push(data);
+            return None;
+        }
+
+        let min: T = std::mem::replace(&mut self.elements[0], data);
+        self.trickle_down(0);
+        return Some(min);
+    }
+
+    /// Pops the maximum and then pushes `data`, even if `data` is larger.
+    pub fn replace_max(&mut self, data: T) -> Option<T> {
+        if self.elements.is_empty() {
+            self.push(data);
+            return None;
+        }
+
+        return Some(self.replace_at_max(data));
+    }
+
+    /// The elements in no particular order.
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.elements.iter()
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.elements
+    }
+
+    fn max_index(&self) -> Option<usize> {
+        match self.elements.len() {
+            0 => None,
+            1 => Some(0),
+            2 => Some(1),
+            _ if self.elements[2] > self.elements[1] => Some(2),
+            _ => Some(1),
+        }
+    }
+
+    /// Swaps `data` in for the maximum. If `data` is smaller than the root
+    /// above it, the two trade places first, and the old root then sinks
+    /// through the max levels below.
+    fn replace_at_max(&mut self, data: T) -> T {
+        let index: usize = self.max_index().unwrap_or(0);
+        let max: T = std::mem::replace(&mut self.elements[index], data);
+
+        if index > 0 && self.elements[index] < self.elements[0] {
+            self.elements.swap(index, 0);
+        }
+        self.trickle_down(index);
+        return max;
+    }
+
+    /// The order an element on `index`'s level keeps with its descendants:
+    /// `Less` on min levels and `Greater` on max levels.
+    fn goal(index: usize) -> Ordering {
+        if (index + 1).ilog2().is_multiple_of(2) {
+            Ordering::Less
+        } else {
+            Ordering::Greater
+        }
+    }
+
+    fn bubble_up(&mut self, index: usize) {
+        if index == 0 {
+            return;
+        }
+
+        let goal: Ordering = Self::goal(index);
+        let parent: usize = (index - 1) / 2;
+        if self.elements[index].cmp(&self.elements[parent]) == goal.reverse() {
+            self.elements.swap(index, parent);
+            self.bubble_up_grandparents(parent, goal.reverse());
+        } else {
+            self.bubble_up_grandparents(index, goal);
+        }
+    }
+
+    /// Moves the element up two levels at a time while it beats its
+    /// grandparent in the direction of `goal`.
+    fn bubble_up_grandparents(&mut self, mut index: usize, goal: Ordering) {
+        while index >= 3 {
+            let grandparent: usize = ((index - 1) / 2 - 1) / 2;
+            if self.elements[index].cmp(&self.elements[grandparent]) != goal {
+                return;
+            }
+            self.elements.swap(index, grandparent);
+            index = grandparent;
+        }
+    }
+
+    /// Sinks the element at `index` to where its level allows it. It moves
+    /// to its best child or grandchild; after a move to a grandchild it may
+    /// also have to swap with the parent in between, which sits on a level of
+    /// the opposite kind.
+    fn trickle_down(&mut self, mut index: usize) {
+        let goal: Ordering = Self::goal(index);
+        let length: usize = self.elements.len();
+
+        loop {
+            let first_child: usize = 2 * index + 1;
+            if first_child >= length {
+                return;
+            }
+
+            let mut best: usize = first_child;
+            for candidate in [first_child + 1, 4 * index + 3, 4 * index + 4, 4 * index + 5, 4 * index + 6] {
+                if candidate < length && self.elements[candidate].cmp(&self.elements[best]) == goal {
+                    best = candidate;
+                }
+            }
+
+            if self.elements[best].cmp(&self.elements[index]) != goal {
+                return;
+            }
+            self.elements.swap(best, index);
+
+            if best <= first_child + 1 {
+                return;
+            }
+            let parent: usize = (best - 1) / 2;
+            if self.elements[best].cmp(&self.elements[parent]) == goal.reverse() {
+                self.elements.swap(best, parent);
+            }
+            index = best;
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_levels(heap: &MinMaxHeap<i64>) {
+        for index in 1..heap.elements.len() {
+            let mut ancestor: usize = index;
+            while ancestor > 0 {
+                ancestor = (ancestor - 1) / 2;
+                let goal: Ordering = MinMaxHeap::<i64>::goal(ancestor);
+                assert_ne!(heap.elements[index].cmp(&heap.elements[ancestor]), goal, "{} under {}", index, ancestor);
+            }
+        }
+    }
+
+    fn shuffled(count: i64) -> Vec<i64> {
+        (0..count).map(|i| (i * 7919) % count).collect()
+    }
+
+    #[test]
+    fn test_empty() {
+        let mut heap: MinMaxHeap<i64> = MinMaxHeap::new();
+        assert!(heap.is_empty());
+        assert_eq!((heap.peek_min(), heap.peek_max()), (None, None));
+        assert_eq!((heap.pop_min(), heap.pop_max()), (None, None));
+        assert_eq!(heap.push_pop_min(4), 4);
+        assert_eq!(heap.push_pop_max(4), 4);
+        assert_eq!(heap.replace_max(4), None);
+        assert_eq!(heap.len(), 1);
+    }
+
+    #[test]
+    fn test_pops_from_both_ends() {
+        let mut heap: MinMaxHeap<i64> = MinMaxHeap::new();
+        for data in shuffled(101) {
+            heap.push(data);
+            check_levels(&heap);
+        }
+
+        for step in 0..50 {
+            assert_eq!(heap.peek_min(), Some(&step));
+            assert_eq!(heap.peek_max(), Some(&(100 - step)));
+            assert_eq!(heap.pop_min(), Some(step));
+            assert_eq!(heap.pop_max(), Some(100 - step));
+            check_levels(&heap);
+        }
+        assert_eq!(heap.pop_max(), Some(50));
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn test_from_heapifies() {
+        let heap: MinMaxHeap<i64> = MinMaxHeap::from(shuffled(257));
+        check_levels(&heap);
+        assert_eq!((heap.peek_min(), heap.peek_max()), (Some(&0), Some(&256)));
+    }
+
+    #[test]
+    fn test_against_sorted_model() {
+        let mut heap: MinMaxHeap<i64> = MinMaxHeap::new();
+        let mut model: Vec<i64> = Vec::new();
+
+        for round in 0..400i64 {
+            let data: i64 = (round * 37) % 101;
+            match round % 6 {
+                0..=2 => {
+                    heap.push(data);
+                    model.push(data);
+                }
+                3 => {
+                    let expected: Option<i64> = if model.is_empty() { None } else { Some(model.remove(0)) };
+                    assert_eq!(heap.pop_min(), expected);
+                }
+                4 => assert_eq!(heap.pop_max(), model.pop()),
+                _ => {
+                    model.push(data);
+                    model.sort();
+                    let expected: i64 = model.pop().unwrap();
+                    assert_eq!(heap.push_pop_max(data), expected);
+                }
+            }
+            model.sort();
+            check_levels(&heap);
+            assert_eq!(heap.peek_min(), model.first());
+            assert_eq!(heap.peek_max(), model.last());
+        }
+    }
+
+    #[test]
+    fn test_push_pop_and_replace() {
+        let mut heap: MinMaxHeap<i64> = MinMaxHeap::from(vec![10, 20, 30, 40, 50]);
+        assert_eq!(heap.push_pop_min(5), 5);
+        assert_eq!(heap.push_pop_min(25), 10);
+        assert_eq!(heap.push_pop_max(60), 60);
+        assert_eq!(heap.push_pop_max(1), 50);
+        check_levels(&heap);
+        assert_eq!(heap.peek_min(), Some(&1));
+
+        assert_eq!(heap.replace_min(100), Some(1));
+        assert_eq!(heap.replace_max(0), Some(100));
+        check_levels(&heap);
+
+        let mut sorted: Vec<i64> = heap.into_vec();
+        sorted.sort();
+        assert_eq!(sorted, vec![0, 20, 25, 30, 40]);
+    }
+
+    #[test]
+    fn test_replace_max_below_root() {
+        let mut heap: MinMaxHeap<i64> = MinMaxHeap::from(vec![5, 9, 7, 8, 6, 9, 7]);
+        assert_eq!(heap.push_pop_max(1), 9);
+        check_levels(&heap);
+        assert_eq!(heap.pop_min(), Some(1));
+        assert_eq!(heap.pop_max(), Some(9));
+    }
+
+    #[test]
+    fn test_bounded_keeps_top_k() {
+        let mut board: MinMaxHeap<i64> = MinMaxHeap::bounded(5);
+        assert_eq!(board.capacity(), Some(5));
+
+        let mut evicted: Vec<i64> = Vec::new();
+        for score in shuffled(50) {
+            evicted.extend(board.push(score));
+            assert!(board.len() <= 5);
+        }
+        assert_eq!(evicted.len(), 45);
+        assert_eq!(board.peek_min(), Some(&45));
+
+        let top: Vec<i64> = std::iter::from_fn(|| board.pop_max()).collect();
+        assert_eq!(top, vec![49, 48, 47, 46, 45]);
+
+        let mut empty: MinMaxHeap<i64> = MinMaxHeap::bounded(0);
+        assert_eq!(empty.push(3), Some(3));
+        assert!(empty.is_empty());
+    }
+}
@@ -0,0 +1,16 @@
+#![allow(clippy::needless_return, clippy::new_without_default)]
+
+pub mod queue;
+pub mod list_queue;
+pub mod two_stack_queue;
+pub mod ring_queue;
+pub mod deque;
+pub mod binary_heap;
+pub mod pairing_heap;
+pub mod fibonacci_heap;
+pub mod meldable_heap;
+pub mod leftist_heap;
+pub mod skew_heap;
+pub mod binomial_heap;
+pub mod min_max_heap;
+pub mod bounded_queue;
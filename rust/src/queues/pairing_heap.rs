@@ -0,0 +1,271 @@
+use std::cell::RefCell;
+use std::fmt::Error;
+use std::rc::{Rc, Weak};
+
+/// A node of a pairing heap in child/sibling form: `child` is the leftmost
+/// child and `sibling` the next node to the right. `prev` points back at
+/// the parent for a leftmost child and at the left sibling otherwise.
+pub struct Node<T> {
+    data: T,
+    child: Option<Rc<RefCell<Node<T>>>>,
+    sibling: Option<Rc<RefCell<Node<T>>>>,
+    prev: Option<Weak<RefCell<Node<T>>>>,
+}
+
+/// Names an element of a `PairingHeap`. It goes stale once its element is
+/// deleted, and stays valid when its heap is melded into another.
+pub struct Handle<T>(Weak<RefCell<Node<T>>>);
+
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        Handle(self.0.clone())
+    }
+}
+
+/// A min-heap kept as a single heap-ordered tree. Insert, meld and
+/// decrease-key just link two trees in O(1); delete-min pairs up the root's
+/// children left to right and then folds the pairs right to left, which
+/// makes it O(log n) amortised.
+pub struct PairingHeap<T> {
+    root: Option<Rc<RefCell<Node<T>>>>,
+    length: usize,
+}
+
+impl<T: Ord> PairingHeap<T> {
+    pub fn new() -> Self {
+        PairingHeap { root: None, length: 0 }
+    }
+
+    pub fn from(data_vec: Vec<T>) -> Self {
+        let mut heap: PairingHeap<T> = PairingHeap::new();
+
+        for data in data_vec {
+            heap.insert(data);
+        }
+
+        return heap;
+    }
+
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    pub fn insert(&mut self, data: T) -> Handle<T> {
+        let node: Rc<RefCell<Node<T>>> = Rc::new(RefCell::new(Node { data, child: None, sibling: None, prev: None }));
+        let handle: Handle<T> = Handle(Rc::downgrade(&node));
+
+        self.root = Some(link(self.root.take(), node));
+        self.length += 1;
+        return handle;
+    }
+
+    pub fn delete_min(&mut self) -> Option<T> {
+        let root: Rc<RefCell<Node<T>>> = self.root.take()?;
+
+        let mut children: Vec<Rc<RefCell<Node<T>>>> = Vec::new();
+        let mut next: Option<Rc<RefCell<Node<T>>>> = root.borrow_mut().child.take();
+        while let Some(child) = next {
+            next = child.borrow_mut().sibling.take();
+            child.borrow_mut().prev = None;
+            children.push(child);
+        }
+
+        let mut pairs: Vec<Rc<RefCell<Node<T>>>> = Vec::with_capacity(children.len() / 2 + 1);
+        let mut children = children.into_iter();
+        while let Some(first) = children.next() {
+            pairs.push(match children.next() {
+                Some(second) => link(Some(first), second),
+                None => first,
+            });
+        }
+
+        let mut root_of_pairs: Option<Rc<RefCell<Node<T>>>> = None;
+        while let Some(pair) = pairs.pop() {
+            root_of_pairs = Some(link(root_of_pairs, pair));
+        }
+
+        self.root = root_of_pairs;
+        self.length -= 1;
+        return Rc::try_unwrap(root).ok().map(|node| node.into_inner().data);
+    }
+
+    /// Replaces the element behind `handle` with a smaller or equal one. The
+    /// node's subtree is cut from its parent and linked with the root, so
+    /// this is O(1) plus the later cost of the deferred pairing. The handle
+    /// must come from this heap or one melded into it.
+    pub fn decrease_key(&mut self, handle: &Handle<T>, data: T) -> Result<(), Error> {
+        let node: Rc<RefCell<Node<T>>> = handle.0.upgrade().ok_or(Error)?;
+        if data > node.borrow().data {
+            return Err(Error);
+        }
+        node.borrow_mut().data = data;
+
+        let Some(prev) = node.borrow_mut().prev.take().and_then(|prev| prev.upgrade()) else {
+            return Ok(());
+        };
+        let sibling: Option<Rc<RefCell<Node<T>>>> = node.borrow_mut().sibling.take();
+        if let Some(sibling) = &sibling {
+            sibling.borrow_mut().prev = Some(Rc::downgrade(&prev));
+        }
+
+        let is_leftmost: bool = prev.borrow().child.as_ref().is_some_and(|child| Rc::ptr_eq(child, &node));
+        if is_leftmost {
+            prev.borrow_mut().child = sibling;
+        } else {
+            prev.borrow_mut().sibling = sibling;
+        }
+
+        self.root = Some(link(self.root.take(), node));
+        return Ok(());
+    }
+
+    /// Moves every element of `other` into this heap in O(1). Handles into
+    /// `other` keep working on this heap.
+    pub fn meld(&mut self, mut other: PairingHeap<T>) {
+        if let Some(other_root) = other.root.take() {
+            self.root = Some(link(self.root.take(), other_root));
+            self.length += std::mem::take(&mut other.length);
+        }
+    }
+}
+
+impl<T: Ord + Clone> PairingHeap<T> {
+    pub fn find_min(&self) -> Option<T> {
+        self.root.as_ref().map(|root| root.borrow().data.clone())
+    }
+
+    /// The element behind `handle`, or `None` once it has been deleted.
+    pub fn get(&self, handle: &Handle<T>) -> Option<T> {
+        handle.0.upgrade().map(|node| node.borrow().data.clone())
+    }
+}
+
+/// Makes the root with the larger element the leftmost child of the other
+/// and returns the new root.
+fn link<T: Ord>(first: Option<Rc<RefCell<Node<T>>>>, second: Rc<RefCell<Node<T>>>) -> Rc<RefCell<Node<T>>> {
+    let Some(first) = first else {
+        return second;
+    };
+
+    let (parent, child) = if second.borrow().data < first.borrow().data {
+        (second, first)
+    } else {
+        (first, second)
+    };
+
+    let old_child: Option<Rc<RefCell<Node<T>>>> = parent.borrow_mut().child.take();
+    if let Some(old_child) = &old_child {
+        old_child.borrow_mut().prev = Some(Rc::downgrade(&child));
+    }
+    child.borrow_mut().sibling = old_child;
+    child.borrow_mut().prev = Some(Rc::downgrade(&parent));
+    parent.borrow_mut().child = Some(child);
+
+    return parent;
+}
+
+/// Frees the tree with an explicit stack, since a long chain of children
+/// or siblings would overflow the default recursive drop.
+impl<T> Drop for PairingHeap<T> {
+    fn drop(&mut self) {
+        let mut stack: Vec<Rc<RefCell<Node<T>>>> = self.root.take().into_iter().collect();
+
+        while let Some(node) = stack.pop() {
+            let mut node = node.borrow_mut();
+            stack.extend(node.child.take());
+            stack.extend(node.sibling.take());
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drain(heap: &mut PairingHeap<i64>) -> Vec<i64> {
+        std::iter::from_fn(|| heap.delete_min()).collect()
+    }
+
+    #[test]
+    fn test_insert_and_delete_min() {
+        let mut heap: PairingHeap<i64> = PairingHeap::new();
+        assert_eq!(heap.find_min(), None);
+        assert_eq!(heap.delete_min(), None);
+
+        for value in [5, 3, 8, 1, 9, 2, 7, 3] {
+            heap.insert(value);
+        }
+        assert_eq!(heap.find_min(), Some(1));
+        assert_eq!(heap.len(), 8);
+        assert_eq!(drain(&mut heap), vec![1, 2, 3, 3, 5, 7, 8, 9]);
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn test_decrease_key() {
+        let mut heap: PairingHeap<i64> = PairingHeap::new();
+        let handles: Vec<Handle<i64>> = (0..20).map(|i| heap.insert(100 + i)).collect();
+        assert_eq!(heap.delete_min(), Some(100));
+
+        assert_eq!(heap.decrease_key(&handles[7], 50), Ok(()));
+        assert_eq!(heap.decrease_key(&handles[12], 60), Ok(()));
+        assert_eq!(heap.decrease_key(&handles[12], 55), Ok(()));
+        assert_eq!(heap.decrease_key(&handles[3], 200), Err(Error));
+        assert_eq!(heap.decrease_key(&handles[0], 0), Err(Error));
+        assert_eq!(heap.get(&handles[12]), Some(55));
+        assert_eq!(heap.get(&handles[0]), None);
+
+        assert_eq!(heap.delete_min(), Some(50));
+        assert_eq!(heap.delete_min(), Some(55));
+        assert_eq!(heap.decrease_key(&handles[19], 1), Ok(()));
+        let rest: Vec<i64> = drain(&mut heap);
+        assert_eq!(rest.len(), 17);
+        assert_eq!(rest[0], 1);
+        assert!(rest.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+
+    #[test]
+    fn test_decrease_key_of_every_position() {
+        let mut heap: PairingHeap<i64> = PairingHeap::new();
+        let handles: Vec<Handle<i64>> = (0..64).map(|i| heap.insert(1000 + (i * 37) % 64)).collect();
+        heap.delete_min();
+        heap.delete_min();
+
+        for (i, handle) in handles.iter().enumerate() {
+            if heap.get(handle).is_some() {
+                assert_eq!(heap.decrease_key(handle, i as i64), Ok(()));
+            }
+        }
+        let values: Vec<i64> = drain(&mut heap);
+        assert_eq!(values.len(), 62);
+        assert!(values.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_meld_keeps_handles() {
+        let mut heap: PairingHeap<i64> = PairingHeap::from(vec![4, 8, 6]);
+        let mut other: PairingHeap<i64> = PairingHeap::from(vec![5, 7]);
+        let handle: Handle<i64> = other.insert(9);
+
+        heap.meld(other);
+        heap.meld(PairingHeap::new());
+        assert_eq!(heap.len(), 6);
+        assert_eq!(heap.decrease_key(&handle, 1), Ok(()));
+        assert_eq!(drain(&mut heap), vec![1, 4, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn test_long_chain_drop() {
+        let mut heap: PairingHeap<i64> = PairingHeap::new();
+        for value in (0..200_000).rev() {
+            heap.insert(value);
+        }
+        assert_eq!(heap.find_min(), Some(0));
+    }
+}
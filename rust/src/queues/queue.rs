@@ -0,0 +1,92 @@
+/// A first-in, first-out collection. Like `Stack`, `front` hands out a clone
+/// so list-backed queues can implement it too.
+pub trait Queue<T: Clone> {
+    fn enqueue(&mut self, data: T);
+
+    fn dequeue(&mut self) -> Option<T>;
+
+    /// The element `dequeue` would return, without removing it.
+    fn front(&self) -> Option<T>;
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+
+
+/// Behaviour every `Queue` must have. Each implementation's tests run these
+/// against a fresh, empty queue.
+#[cfg(test)]
+pub(crate) mod behaviour {
+    use super::Queue;
+
+    pub fn starts_empty<Q: Queue<i64>>(queue: Q) {
+        assert!(queue.is_empty());
+        assert_eq!(queue.len(), 0);
+        assert_eq!(queue.front(), None);
+    }
+
+    pub fn dequeues_in_order<Q: Queue<i64>>(mut queue: Q) {
+        for data in 1..=5 {
+            queue.enqueue(data);
+        }
+
+        assert_eq!(queue.len(), 5);
+        for data in 1..=5 {
+            assert_eq!(queue.front(), Some(data));
+            assert_eq!(queue.dequeue(), Some(data));
+        }
+        assert_eq!(queue.dequeue(), None);
+        assert!(queue.is_empty());
+    }
+
+    pub fn front_does_not_remove<Q: Queue<i64>>(mut queue: Q) {
+        queue.enqueue(1);
+        queue.enqueue(2);
+
+        assert_eq!(queue.front(), Some(1));
+        assert_eq!(queue.front(), Some(1));
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue.dequeue(), Some(1));
+        assert_eq!(queue.front(), Some(2));
+    }
+
+    /// Mixes enqueues and dequeues in an uneven pattern so the queue keeps
+    /// emptying and refilling, and checks it against a plain `Vec` model.
+    pub fn keeps_order_when_interleaved<Q: Queue<i64>>(mut queue: Q) {
+        let mut model: Vec<i64> = Vec::new();
+        let mut next: i64 = 0;
+
+        for round in 0..200 {
+            for _ in 0..(round % 7) {
+                queue.enqueue(next);
+                model.push(next);
+                next += 1;
+            }
+            for _ in 0..(round % 5) {
+                let expected: Option<i64> = if model.is_empty() { None } else { Some(model.remove(0)) };
+                assert_eq!(queue.dequeue(), expected);
+            }
+            assert_eq!(queue.len(), model.len());
+            assert_eq!(queue.front(), model.first().copied());
+        }
+
+        for expected in model {
+            assert_eq!(queue.dequeue(), Some(expected));
+        }
+        assert_eq!(queue.dequeue(), None);
+    }
+
+    pub fn survives_empty_dequeues<Q: Queue<i64>>(mut queue: Q) {
+        assert_eq!(queue.dequeue(), None);
+        queue.enqueue(7);
+        assert_eq!(queue.dequeue(), Some(7));
+        assert_eq!(queue.dequeue(), None);
+        queue.enqueue(8);
+        assert_eq!(queue.front(), Some(8));
+        assert_eq!(queue.len(), 1);
+    }
+}
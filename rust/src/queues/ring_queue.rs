@@ -0,0 +1,102 @@
+use super::queue::Queue;
+use crate::arrays::array_list::CapacityError;
+use crate::arrays::ring_buffer::{FullPolicy, RingBuffer};
+
+/// An unbounded queue on the crate's ring buffer. The buffer rejects pushes
+/// when full; the queue then moves everything into one twice the size, so
+/// enqueue is amortised O(1) and elements never shift while they wait.
+pub struct RingQueue<T> {
+    buffer: RingBuffer<T>,
+}
+
+impl<T: Clone> RingQueue<T> {
+    pub fn new() -> Self {
+        RingQueue::with_capacity(8)
+    }
+
+    /// A capacity of zero is raised to one, as for `RingBuffer`.
+    pub fn with_capacity(capacity: usize) -> Self {
+        RingQueue { buffer: RingBuffer::new(capacity, FullPolicy::Reject) }
+    }
+
+    pub fn from(data_vec: Vec<T>) -> Self {
+        let mut queue: RingQueue<T> = RingQueue::with_capacity(data_vec.len());
+
+        for data in data_vec {
+            queue.enqueue(data);
+        }
+
+        return queue;
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.buffer.capacity()
+    }
+
+    fn grow(&mut self) {
+        let mut buffer: RingBuffer<T> = RingBuffer::new(self.buffer.capacity() * 2, FullPolicy::Reject);
+
+        while let Some(data) = self.buffer.pop_front() {
+            let _ = buffer.push_back(data);
+        }
+
+        self.buffer = buffer;
+    }
+}
+
+impl<T: Clone> Queue<T> for RingQueue<T> {
+    fn enqueue(&mut self, data: T) {
+        if let Err(CapacityError(data)) = self.buffer.push_back(data) {
+            self.grow();
+            let _ = self.buffer.push_back(data);
+        }
+    }
+
+    fn dequeue(&mut self) -> Option<T> {
+        self.buffer.pop_front()
+    }
+
+    fn front(&self) -> Option<T> {
+        self.buffer.front().cloned()
+    }
+
+    fn len(&self) -> usize {
+        self.buffer.len()
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::queues::queue::behaviour;
+
+    #[test]
+    fn test_ring_queue_behaviour() {
+        behaviour::starts_empty(RingQueue::new());
+        behaviour::dequeues_in_order(RingQueue::with_capacity(1));
+        behaviour::front_does_not_remove(RingQueue::new());
+        behaviour::keeps_order_when_interleaved(RingQueue::with_capacity(2));
+        behaviour::survives_empty_dequeues(RingQueue::new());
+    }
+
+    #[test]
+    fn test_grows_while_wrapped() {
+        let mut queue: RingQueue<i64> = RingQueue::with_capacity(4);
+        for data in 0..3 {
+            queue.enqueue(data);
+        }
+        assert_eq!(queue.dequeue(), Some(0));
+        assert_eq!(queue.dequeue(), Some(1));
+        for data in 3..7 {
+            queue.enqueue(data);
+        }
+        assert_eq!(queue.capacity(), 8);
+        assert_eq!(queue.len(), 5);
+
+        for data in 2..7 {
+            assert_eq!(queue.dequeue(), Some(data));
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use std::mem;
+
+use super::meldable_heap::MeldableHeap;
+
+pub struct Node<T> {
+    data: T,
+    left: Option<Box<Node<T>>>,
+    right: Option<Box<Node<T>>>,
+}
+
+/// The self-adjusting cousin of `LeftistHeap`: no ranks are stored, and
+/// every node on the merged path swaps its children unconditionally. A
+/// single meld can walk a long path, but any sequence of operations costs
+/// O(log n) amortised each.
+pub struct SkewHeap<T> {
+    root: Option<Box<Node<T>>>,
+    length: usize,
+}
+
+impl<T: Ord> SkewHeap<T> {
+    pub fn new() -> Self {
+        SkewHeap { root: None, length: 0 }
+    }
+
+    pub fn from(data_vec: Vec<T>) -> Self {
+        let mut heap: SkewHeap<T> = SkewHeap::new();
+
+        for data in data_vec {
+            heap.insert(data);
+        }
+
+        return heap;
+    }
+}
+
+/// Merges the right spines top down and swaps children on the way back up.
+/// It loops instead of recursing, since the merged path isn't bounded by
+/// log n here.
+fn merge<T: Ord>(mut first: Option<Box<Node<T>>>, mut second: Option<Box<Node<T>>>) -> Option<Box<Node<T>>> {
+    let mut spine: Vec<Box<Node<T>>> = Vec::new();
+
+    let mut merged: Option<Box<Node<T>>> = loop {
+        match (first, second) {
+            (None, rest) | (rest, None) => break rest,
+            (Some(a), Some(b)) => {
+                let (mut smaller, larger): (Box<Node<T>>, Box<Node<T>>) = if b.data < a.data { (b, a) } else { (a, b) };
+                first = smaller.right.take();
+                second = Some(larger);
+                spine.push(smaller);
+            }
+        }
+    };
+
+    while let Some(mut node) = spine.pop() {
+        node.right = merged;
+        mem::swap(&mut node.left, &mut node.right);
+        merged = Some(node);
+    }
+
+    return merged;
+}
+
+impl<T: Ord> MeldableHeap<T> for SkewHeap<T> {
+    fn insert(&mut self, data: T) {
+        let node: Box<Node<T>> = Box::new(Node { data, left: None, right: None });
+        self.root = merge(self.root.take(), Some(node));
+        self.length += 1;
+    }
+
+    fn pop_min(&mut self) -> Option<T> {
+        let mut root: Box<Node<T>> = self.root.take()?;
+        self.root = merge(root.left.take(), root.right.take());
+        self.length -= 1;
+        return Some(root.data);
+    }
+
+    fn peek_min(&self) -> Option<&T> {
+        self.root.as_ref().map(|root| &root.data)
+    }
+
+    fn meld(mut self, mut other: Self) -> Self {
+        self.root = merge(self.root.take(), other.root.take());
+        self.length += mem::take(&mut other.length);
+        return self;
+    }
+
+    fn len(&self) -> usize {
+        self.length
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &T> + '_> {
+        Box::new(Iter { stack: self.root.as_deref().into_iter().collect() })
+    }
+}
+
+struct Iter<'a, T> {
+    stack: Vec<&'a Node<T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let node: &'a Node<T> = self.stack.pop()?;
+        self.stack.extend(node.left.as_deref());
+        self.stack.extend(node.right.as_deref());
+        return Some(&node.data);
+    }
+}
+
+/// Frees the nodes with an explicit stack, since either spine can be as
+/// long as the heap.
+impl<T> Drop for SkewHeap<T> {
+    fn drop(&mut self) {
+        let mut stack: Vec<Box<Node<T>>> = self.root.take().into_iter().collect();
+
+        while let Some(mut node) = stack.pop() {
+            stack.extend(node.left.take());
+            stack.extend(node.right.take());
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::queues::meldable_heap::behaviour;
+
+    #[test]
+    fn test_skew_heap_behaviour() {
+        behaviour::starts_empty(SkewHeap::new());
+        behaviour::pops_in_sorted_order(SkewHeap::new());
+        behaviour::melds_two_heaps(SkewHeap::new(), SkewHeap::new());
+        behaviour::melds_with_empty(SkewHeap::new(), SkewHeap::new(), SkewHeap::new());
+        behaviour::iterates_every_element(SkewHeap::new());
+        behaviour::interleaves_insert_and_pop(SkewHeap::new());
+        behaviour::survives_sorted_runs(SkewHeap::new(), SkewHeap::new());
+    }
+
+    #[test]
+    fn test_swaps_children_on_merge() {
+        let mut heap: SkewHeap<i64> = SkewHeap::from(vec![1, 2]);
+        heap.insert(3);
+        let root: &Node<i64> = heap.root.as_deref().unwrap();
+        assert_eq!(root.left.as_ref().map(|node| node.data), Some(3));
+        assert_eq!(root.right.as_ref().map(|node| node.data), Some(2));
+    }
+}
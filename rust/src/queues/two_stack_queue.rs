@@ -0,0 +1,94 @@
+use super::queue::Queue;
+use crate::stacks::array_stack::ArrayStack;
+use crate::stacks::stack::Stack;
+
+/// A queue made of two stacks. New elements are pushed on `inbox`; when
+/// `outbox` is empty, the whole inbox is popped onto it, which reverses it
+/// into queue order. Each element is moved at most once, so every operation
+/// is amortised O(1).
+///
+/// The inbox's bottom element is the front of the queue whenever the outbox
+/// is empty, but a stack can't see its bottom, so a clone of it is kept in
+/// `inbox_front`.
+pub struct TwoStackQueue<T> {
+    inbox: ArrayStack<T>,
+    outbox: ArrayStack<T>,
+    inbox_front: Option<T>,
+}
+
+impl<T: Clone> TwoStackQueue<T> {
+    pub fn new() -> Self {
+        TwoStackQueue { inbox: ArrayStack::new(), outbox: ArrayStack::new(), inbox_front: None }
+    }
+
+    pub fn from(data_vec: Vec<T>) -> Self {
+        let mut queue: TwoStackQueue<T> = TwoStackQueue::new();
+
+        for data in data_vec {
+            queue.enqueue(data);
+        }
+
+        return queue;
+    }
+}
+
+impl<T: Clone> Queue<T> for TwoStackQueue<T> {
+    fn enqueue(&mut self, data: T) {
+        if self.inbox.is_empty() {
+            self.inbox_front = Some(data.clone());
+        }
+        self.inbox.push(data);
+    }
+
+    fn dequeue(&mut self) -> Option<T> {
+        if self.outbox.is_empty() {
+            while let Some(data) = self.inbox.pop() {
+                self.outbox.push(data);
+            }
+            self.inbox_front = None;
+        }
+
+        self.outbox.pop()
+    }
+
+    fn front(&self) -> Option<T> {
+        match self.outbox.peek() {
+            Some(data) => Some(data),
+            None => self.inbox_front.clone(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.inbox.len() + self.outbox.len()
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::queues::queue::behaviour;
+
+    #[test]
+    fn test_two_stack_queue_behaviour() {
+        behaviour::starts_empty(TwoStackQueue::new());
+        behaviour::dequeues_in_order(TwoStackQueue::new());
+        behaviour::front_does_not_remove(TwoStackQueue::new());
+        behaviour::keeps_order_when_interleaved(TwoStackQueue::new());
+        behaviour::survives_empty_dequeues(TwoStackQueue::new());
+    }
+
+    #[test]
+    fn test_front_spans_both_stacks() {
+        let mut queue: TwoStackQueue<i64> = TwoStackQueue::from(vec![1, 2]);
+        assert_eq!(queue.dequeue(), Some(1));
+        queue.enqueue(3);
+        assert_eq!(queue.front(), Some(2));
+        assert_eq!(queue.dequeue(), Some(2));
+        assert_eq!(queue.front(), Some(3));
+        assert_eq!(queue.outbox.len(), 0);
+        assert_eq!(queue.dequeue(), Some(3));
+        assert_eq!(queue.front(), None);
+    }
+}
@@ -0,0 +1,308 @@
+use std::ops::{Add, Rem};
+
+use super::array_stack::ArrayStack;
+use super::stack::Stack;
+
+/// An associative way of folding two values into one. Closures taking two
+/// references work directly, alongside the named operations below.
+pub trait Operation<T> {
+    fn combine(&self, first: &T, second: &T) -> T;
+}
+
+impl<T, F: Fn(&T, &T) -> T> Operation<T> for F {
+    fn combine(&self, first: &T, second: &T) -> T {
+        self(first, second)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Min;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Max;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Sum;
+
+/// Greatest common divisor by Euclid's algorithm, for non-negative integers.
+#[derive(Clone, Copy, Debug)]
+pub struct Gcd;
+
+impl<T: Ord + Clone> Operation<T> for Min {
+    fn combine(&self, first: &T, second: &T) -> T {
+        first.min(second).clone()
+    }
+}
+
+impl<T: Ord + Clone> Operation<T> for Max {
+    fn combine(&self, first: &T, second: &T) -> T {
+        first.max(second).clone()
+    }
+}
+
+impl<T: Add<Output = T> + Clone> Operation<T> for Sum {
+    fn combine(&self, first: &T, second: &T) -> T {
+        first.clone() + second.clone()
+    }
+}
+
+impl<T: Rem<Output = T> + Default + PartialEq + Clone> Operation<T> for Gcd {
+    fn combine(&self, first: &T, second: &T) -> T {
+        let (mut a, mut b): (T, T) = (first.clone(), second.clone());
+        while b != T::default() {
+            let remainder: T = a % b.clone();
+            a = b;
+            b = remainder;
+        }
+        a
+    }
+}
+
+/// A stack that also knows the combination of everything on it.
+///
+/// Each entry stores its value next to the aggregate of itself and every
+/// entry below, so `aggregate` is just a look at the top entry and `pop`
+/// never has to recompute anything.
+pub struct AggregateStack<T, Op> {
+    entries: ArrayStack<(T, T)>,
+    operation: Op,
+}
+
+impl<T: Clone, Op: Operation<T>> AggregateStack<T, Op> {
+    pub fn new(operation: Op) -> Self {
+        AggregateStack { entries: ArrayStack::new(), operation }
+    }
+
+    pub fn from(operation: Op, data_vec: Vec<T>) -> Self {
+        let mut stack: AggregateStack<T, Op> = AggregateStack::new(operation);
+
+        for data in data_vec {
+            stack.push(data);
+        }
+
+        return stack;
+    }
+
+    /// The operation folded over the whole stack, bottom to top.
+    pub fn aggregate(&self) -> Option<&T> {
+        self.entries.top().map(|(_, aggregate)| aggregate)
+    }
+
+    /// Pushes `data` but folds it in before the entries below rather than
+    /// after, so the aggregate reads top to bottom instead.
+    fn push_before(&mut self, data: T) {
+        let aggregate: T = match self.entries.top() {
+            Some((_, below)) => self.operation.combine(&data, below),
+            None => data.clone(),
+        };
+        self.entries.push((data, aggregate));
+    }
+}
+
+impl<T: Clone, Op: Operation<T>> Stack<T> for AggregateStack<T, Op> {
+    fn push(&mut self, data: T) {
+        let aggregate: T = match self.entries.top() {
+            Some((_, below)) => self.operation.combine(below, &data),
+            None => data.clone(),
+        };
+        self.entries.push((data, aggregate));
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.entries.pop().map(|(data, _)| data)
+    }
+
+    fn peek(&self) -> Option<T> {
+        self.entries.top().map(|(data, _)| data.clone())
+    }
+
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = T> + '_> {
+        Box::new(self.entries.iter().map(|(data, _)| data))
+    }
+}
+
+/// A queue made of two aggregate stacks, which gives sliding-window
+/// aggregates in amortised O(1).
+///
+/// New elements go on `inbox`. When `outbox` runs dry the whole inbox is
+/// popped onto it, reversing it into queue order, so every element moves at
+/// most once. The outbox folds top to bottom, so both halves aggregate in
+/// queue order and the whole queue is the outbox's combined with the
+/// inbox's; that split only works because the operation is associative.
+pub struct AggregateQueue<T, Op> {
+    inbox: AggregateStack<T, Op>,
+    outbox: AggregateStack<T, Op>,
+}
+
+impl<T: Clone, Op: Operation<T> + Clone> AggregateQueue<T, Op> {
+    pub fn new(operation: Op) -> Self {
+        AggregateQueue { inbox: AggregateStack::new(operation.clone()), outbox: AggregateStack::new(operation) }
+    }
+
+    pub fn len(&self) -> usize {
+        return self.inbox.len() + self.outbox.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.len() == 0;
+    }
+
+    pub fn push_back(&mut self, data: T) {
+        self.inbox.push(data);
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.outbox.is_empty() {
+            while let Some(data) = self.inbox.pop() {
+                self.outbox.push_before(data);
+            }
+        }
+
+        self.outbox.pop()
+    }
+
+    /// The operation folded over the queue, front to back.
+    pub fn aggregate(&self) -> Option<T> {
+        match (self.outbox.aggregate(), self.inbox.aggregate()) {
+            (Some(front), Some(back)) => Some(self.inbox.operation.combine(front, back)),
+            (Some(only), None) | (None, Some(only)) => Some(only.clone()),
+            (None, None) => None,
+        }
+    }
+}
+
+/// The aggregate of every run of `window` consecutive values, in order.
+pub fn sliding_window<T: Clone, Op: Operation<T> + Clone>(values: &[T], window: usize, operation: Op) -> Vec<T> {
+    let mut queue: AggregateQueue<T, Op> = AggregateQueue::new(operation);
+    let mut aggregates: Vec<T> = Vec::new();
+
+    if window == 0 {
+        return aggregates;
+    }
+
+    for value in values {
+        queue.push_back(value.clone());
+        if queue.len() > window {
+            queue.pop_front();
+        }
+        if queue.len() == window {
+            aggregates.extend(queue.aggregate());
+        }
+    }
+
+    return aggregates;
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stacks::stack::behaviour;
+
+    #[test]
+    fn test_aggregate_stack_behaviour() {
+        behaviour::starts_empty(AggregateStack::new(Sum));
+        behaviour::pops_in_reverse_order(AggregateStack::new(Min));
+        behaviour::peek_does_not_remove(AggregateStack::new(Max));
+        behaviour::iterates_top_to_bottom(AggregateStack::new(Sum));
+        behaviour::interleaves_push_and_pop(AggregateStack::new(Min));
+        behaviour::survives_empty_pops(AggregateStack::new(Max));
+    }
+
+    #[test]
+    fn test_min_and_max_follow_pops() {
+        let mut min: AggregateStack<i64, Min> = AggregateStack::from(Min, vec![5, 3, 8, 1, 9]);
+        let mut max: AggregateStack<i64, Max> = AggregateStack::from(Max, vec![5, 3, 8, 1, 9]);
+        assert_eq!(min.aggregate(), Some(&1));
+        assert_eq!(max.aggregate(), Some(&9));
+
+        min.pop();
+        min.pop();
+        max.pop();
+        assert_eq!(min.aggregate(), Some(&3));
+        assert_eq!(max.aggregate(), Some(&8));
+
+        min.pop();
+        min.pop();
+        min.pop();
+        assert_eq!(min.aggregate(), None);
+    }
+
+    #[test]
+    fn test_sum_and_gcd() {
+        let sum: AggregateStack<i64, Sum> = AggregateStack::from(Sum, vec![1, 2, 3, 4]);
+        assert_eq!(sum.aggregate(), Some(&10));
+
+        let mut gcd: AggregateStack<u64, Gcd> = AggregateStack::from(Gcd, vec![84, 36, 48]);
+        assert_eq!(gcd.aggregate(), Some(&12));
+        gcd.push(10);
+        assert_eq!(gcd.aggregate(), Some(&2));
+        gcd.pop();
+        gcd.push(0);
+        assert_eq!(gcd.aggregate(), Some(&12));
+    }
+
+    #[test]
+    fn test_closure_operation() {
+        let mut stack = AggregateStack::new(|first: &String, second: &String| format!("{}{}", first, second));
+        stack.push("a".to_string());
+        stack.push("b".to_string());
+        stack.push("c".to_string());
+        assert_eq!(stack.aggregate(), Some(&"abc".to_string()));
+        assert_eq!(stack.pop(), Some("c".to_string()));
+        assert_eq!(stack.aggregate(), Some(&"ab".to_string()));
+    }
+
+    #[test]
+    fn test_aggregate_queue() {
+        let mut queue: AggregateQueue<i64, Min> = AggregateQueue::new(Min);
+        assert_eq!(queue.aggregate(), None);
+        for value in [4, 2, 7] {
+            queue.push_back(value);
+        }
+        assert_eq!(queue.aggregate(), Some(2));
+
+        assert_eq!(queue.pop_front(), Some(4));
+        assert_eq!(queue.pop_front(), Some(2));
+        assert_eq!(queue.aggregate(), Some(7));
+        queue.push_back(5);
+        assert_eq!(queue.aggregate(), Some(5));
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue.pop_front(), Some(7));
+        assert_eq!(queue.pop_front(), Some(5));
+        assert_eq!(queue.pop_front(), None);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_queue_keeps_order_for_non_commutative_operations() {
+        let mut queue = AggregateQueue::new(|first: &String, second: &String| format!("{}{}", first, second));
+        for text in ["a", "b", "c"] {
+            queue.push_back(text.to_string());
+        }
+        queue.pop_front();
+        queue.push_back("d".to_string());
+        assert_eq!(queue.aggregate(), Some("bcd".to_string()));
+    }
+
+    #[test]
+    fn test_sliding_window_matches_rescan() {
+        let values: Vec<i64> = (0..500).map(|index| (index * 7919) % 101).collect();
+
+        for window in [1, 3, 10, 64] {
+            let expected_min: Vec<i64> = values.windows(window).map(|run| *run.iter().min().unwrap()).collect();
+            let expected_max: Vec<i64> = values.windows(window).map(|run| *run.iter().max().unwrap()).collect();
+            assert_eq!(sliding_window(&values, window, Min), expected_min);
+            assert_eq!(sliding_window(&values, window, Max), expected_max);
+        }
+
+        assert_eq!(sliding_window(&[1, 2, 3], 5, Sum), Vec::<i64>::new());
+        assert_eq!(sliding_window(&[1, 2, 3], 0, Sum), Vec::<i64>::new());
+        assert_eq!(sliding_window(&[1, 2, 3, 4], 2, Sum), vec![3, 5, 7]);
+    }
+}
@@ -0,0 +1,90 @@
+use std::fmt::{Debug, Error, Formatter};
+
+use super::stack::Stack;
+
+/// A stack over one growable array. The top is the end of the `Vec`, so
+/// push and pop are amortised O(1) and never touch the other elements.
+#[derive(Clone, PartialEq)]
+pub struct ArrayStack<T> {
+    elements: Vec<T>,
+}
+
+impl<T: Clone> ArrayStack<T> {
+    pub fn new() -> Self {
+        ArrayStack { elements: Vec::new() }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        ArrayStack { elements: Vec::with_capacity(capacity) }
+    }
+
+    /// Pushes the elements in order, so the last one ends up on top.
+    pub fn from(data_vec: Vec<T>) -> Self {
+        ArrayStack { elements: data_vec }
+    }
+
+    /// A reference to the top element, which `peek` would clone.
+    pub fn top(&self) -> Option<&T> {
+        self.elements.last()
+    }
+
+    pub fn clear(&mut self) {
+        self.elements.clear();
+    }
+}
+
+impl<T: Clone> Stack<T> for ArrayStack<T> {
+    fn push(&mut self, data: T) {
+        self.elements.push(data);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.elements.pop()
+    }
+
+    fn peek(&self) -> Option<T> {
+        self.elements.last().cloned()
+    }
+
+    fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = T> + '_> {
+        Box::new(self.elements.iter().rev().cloned())
+    }
+}
+
+impl<T: Debug> Debug for ArrayStack<T> {
+    /// Lists the elements top first, like `iter`.
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        f.debug_list().entries(self.elements.iter().rev()).finish()
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stacks::stack::behaviour;
+
+    #[test]
+    fn test_array_stack_behaviour() {
+        behaviour::starts_empty(ArrayStack::new());
+        behaviour::pops_in_reverse_order(ArrayStack::new());
+        behaviour::peek_does_not_remove(ArrayStack::new());
+        behaviour::iterates_top_to_bottom(ArrayStack::new());
+        behaviour::interleaves_push_and_pop(ArrayStack::new());
+        behaviour::survives_empty_pops(ArrayStack::with_capacity(1));
+    }
+
+    #[test]
+    fn test_array_stack_from() {
+        let mut stack: ArrayStack<i64> = ArrayStack::from(vec![1, 2, 3]);
+        assert_eq!(stack.top(), Some(&3));
+        assert_eq!(format!("{:?}", stack), "[3, 2, 1]");
+        stack.clear();
+        assert!(stack.is_empty());
+    }
+}
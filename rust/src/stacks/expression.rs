@@ -0,0 +1,681 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+use super::array_stack::ArrayStack;
+use super::stack::Stack;
+
+/// Everything that can go wrong between the source text and a value. Every
+/// variant carries the char position (counting from zero) it refers to.
+#[derive(Clone, PartialEq, Debug)]
+pub enum ExpressionError {
+    UnexpectedCharacter { position: usize, character: char },
+    /// An opening bracket that is never closed.
+    UnclosedBracket { position: usize, bracket: char },
+    /// A closing bracket with nothing open.
+    UnopenedBracket { position: usize, bracket: char },
+    /// A closing bracket of the wrong kind, such as the `]` in `(1]`.
+    MismatchedBracket { open: usize, close: usize },
+    /// A token that can't appear where it does, such as the second `+` in
+    /// `1 + + 2`, or the end of input after an operator.
+    UnexpectedToken { position: usize },
+    UnknownVariable { position: usize, name: String },
+    UnknownFunction { position: usize, name: String },
+    WrongArgumentCount { position: usize, name: String, expected: usize, found: usize },
+    TypeMismatch { position: usize },
+    DivisionByZero { position: usize },
+}
+
+impl Display for ExpressionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            ExpressionError::UnexpectedCharacter { position, character } => {
+                write!(f, "unexpected character '{}' at {}", character, position)
+            }
+            ExpressionError::UnclosedBracket { position, bracket } => write!(f, "'{}' at {} is never closed", bracket, position),
+            ExpressionError::UnopenedBracket { position, bracket } => write!(f, "'{}' at {} has nothing to close", bracket, position),
+            ExpressionError::MismatchedBracket { open, close } => write!(f, "bracket at {} does not match the one at {}", close, open),
+            ExpressionError::UnexpectedToken { position } => write!(f, "unexpected token at {}", position),
+            ExpressionError::UnknownVariable { position, name } => write!(f, "unknown variable '{}' at {}", name, position),
+            ExpressionError::UnknownFunction { position, name } => write!(f, "unknown function '{}' at {}", name, position),
+            ExpressionError::WrongArgumentCount { position, name, expected, found } => {
+                write!(f, "'{}' at {} takes {} arguments, not {}", name, position, expected, found)
+            }
+            ExpressionError::TypeMismatch { position } => write!(f, "wrong operand types at {}", position),
+            ExpressionError::DivisionByZero { position } => write!(f, "division by zero at {}", position),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Remainder,
+    Power,
+    Negate,
+    Not,
+    Equal,
+    NotEqual,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    And,
+    Or,
+}
+
+impl Operator {
+    fn precedence(self) -> u8 {
+        match self {
+            Operator::Or => 1,
+            Operator::And => 2,
+            Operator::Equal | Operator::NotEqual => 3,
+            Operator::Less | Operator::LessEqual | Operator::Greater | Operator::GreaterEqual => 4,
+            Operator::Add | Operator::Subtract => 5,
+            Operator::Multiply | Operator::Divide | Operator::Remainder => 6,
+            Operator::Negate | Operator::Not => 7,
+            Operator::Power => 8,
+        }
+    }
+
+    fn is_right_associative(self) -> bool {
+        matches!(self, Operator::Power | Operator::Negate | Operator::Not)
+    }
+
+    fn is_unary(self) -> bool {
+        matches!(self, Operator::Negate | Operator::Not)
+    }
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Operator::Add => "+",
+            Operator::Subtract => "-",
+            Operator::Multiply => "*",
+            Operator::Divide => "/",
+            Operator::Remainder => "%",
+            Operator::Power => "^",
+            Operator::Negate => "neg",
+            Operator::Not => "!",
+            Operator::Equal => "==",
+            Operator::NotEqual => "!=",
+            Operator::Less => "<",
+            Operator::LessEqual => "<=",
+            Operator::Greater => ">",
+            Operator::GreaterEqual => ">=",
+            Operator::And => "&&",
+            Operator::Or => "||",
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum Token {
+    Number(f64),
+    Boolean(bool),
+    Identifier(String),
+    /// A binary operator as written; `-` and `!` become unary in
+    /// `to_postfix` when they start an operand.
+    Operator(Operator),
+    Open(char),
+    Close(char),
+    Comma,
+}
+
+/// A token and the char position it starts at.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Spanned {
+    pub token: Token,
+    pub position: usize,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum Value {
+    Number(f64),
+    Boolean(bool),
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            Value::Number(number) => write!(f, "{}", number),
+            Value::Boolean(boolean) => write!(f, "{}", boolean),
+        }
+    }
+}
+
+/// One step of a postfix program, with the position of the source token it
+/// came from for error reporting.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Instruction {
+    Push(Value, usize),
+    Load(String, usize),
+    Apply(Operator, usize),
+    Call { name: String, arity: usize, position: usize },
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            Instruction::Push(value, _) => write!(f, "{}", value),
+            Instruction::Load(name, _) => write!(f, "{}", name),
+            Instruction::Apply(operator, _) => write!(f, "{}", operator.symbol()),
+            Instruction::Call { name, arity, .. } => write!(f, "{}/{}", name, arity),
+        }
+    }
+}
+
+fn matching_close(open: char) -> char {
+    match open {
+        '(' => ')',
+        '[' => ']',
+        _ => '}',
+    }
+}
+
+/// Checks that every bracket is closed by one of the same kind, in order,
+/// using a stack of the open brackets and where they are.
+pub fn check_brackets(input: &str) -> Result<(), ExpressionError> {
+    let mut open: ArrayStack<(char, usize)> = ArrayStack::new();
+
+    for (position, character) in input.chars().enumerate() {
+        match character {
+            '(' | '[' | '{' => open.push((character, position)),
+            ')' | ']' | '}' => match open.pop() {
+                Some((bracket, _)) if matching_close(bracket) == character => {}
+                Some((_, open_position)) => {
+                    return Err(ExpressionError::MismatchedBracket { open: open_position, close: position })
+                }
+                None => return Err(ExpressionError::UnopenedBracket { position, bracket: character }),
+            },
+            _ => {}
+        }
+    }
+
+    match open.pop() {
+        Some((bracket, position)) => Err(ExpressionError::UnclosedBracket { position, bracket }),
+        None => Ok(()),
+    }
+}
+
+pub fn tokenize(input: &str) -> Result<Vec<Spanned>, ExpressionError> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens: Vec<Spanned> = Vec::new();
+    let mut position: usize = 0;
+
+    while position < chars.len() {
+        let start: usize = position;
+        let character: char = chars[position];
+        let next: Option<char> = chars.get(position + 1).copied();
+
+        let token: Token = if character.is_whitespace() {
+            position += 1;
+            continue;
+        } else if character.is_ascii_digit() || (character == '.' && next.is_some_and(|c| c.is_ascii_digit())) {
+            while position < chars.len() && (chars[position].is_ascii_digit() || chars[position] == '.') {
+                position += 1;
+            }
+            let text: String = chars[start..position].iter().collect();
+            match text.parse::<f64>() {
+                Ok(number) => Token::Number(number),
+                Err(_) => return Err(ExpressionError::UnexpectedToken { position: start }),
+            }
+        } else if character.is_alphabetic() || character == '_' {
+            while position < chars.len() && (chars[position].is_alphanumeric() || chars[position] == '_') {
+                position += 1;
+            }
+            let name: String = chars[start..position].iter().collect();
+            match name.as_str() {
+                "true" => Token::Boolean(true),
+                "false" => Token::Boolean(false),
+                _ => Token::Identifier(name),
+            }
+        } else {
+            let (token, width): (Token, usize) = match (character, next) {
+                ('=', Some('=')) => (Token::Operator(Operator::Equal), 2),
+                ('!', Some('=')) => (Token::Operator(Operator::NotEqual), 2),
+                ('<', Some('=')) => (Token::Operator(Operator::LessEqual), 2),
+                ('>', Some('=')) => (Token::Operator(Operator::GreaterEqual), 2),
+                ('&', Some('&')) => (Token::Operator(Operator::And), 2),
+                ('|', Some('|')) => (Token::Operator(Operator::Or), 2),
+                ('+', _) => (Token::Operator(Operator::Add), 1),
+                ('-', _) => (Token::Operator(Operator::Subtract), 1),
+                ('*', _) => (Token::Operator(Operator::Multiply), 1),
+                ('/', _) => (Token::Operator(Operator::Divide), 1),
+                ('%', _) => (Token::Operator(Operator::Remainder), 1),
+                ('^', _) => (Token::Operator(Operator::Power), 1),
+                ('<', _) => (Token::Operator(Operator::Less), 1),
+                ('>', _) => (Token::Operator(Operator::Greater), 1),
+                ('!', _) => (Token::Operator(Operator::Not), 1),
+                ('(' | '[' | '{', _) => (Token::Open(character), 1),
+                (')' | ']' | '}', _) => (Token::Close(character), 1),
+                (',', _) => (Token::Comma, 1),
+                _ => return Err(ExpressionError::UnexpectedCharacter { position, character }),
+            };
+            position += width;
+            token
+        };
+
+        tokens.push(Spanned { token, position: start });
+    }
+
+    return Ok(tokens);
+}
+
+/// What waits on the operator stack during shunting-yard.
+#[derive(Clone, PartialEq, Debug)]
+enum Pending {
+    Operator(Operator, usize),
+    /// An open bracket; `function` is set when it starts a call's arguments.
+    Open { bracket: char, position: usize, function: Option<String> },
+}
+
+/// Converts infix tokens to postfix with Dijkstra's shunting-yard
+/// algorithm. Operators wait on a stack until one of lower precedence (or a
+/// closing bracket) forces them out; a second stack counts the arguments of
+/// each open function call.
+pub fn to_postfix(tokens: &[Spanned]) -> Result<Vec<Instruction>, ExpressionError> {
+    let mut output: Vec<Instruction> = Vec::new();
+    let mut pending: ArrayStack<Pending> = ArrayStack::new();
+    let mut arities: ArrayStack<usize> = ArrayStack::new();
+    let mut expect_operand: bool = true;
+
+    for (index, Spanned { token, position }) in tokens.iter().enumerate() {
+        let position: usize = *position;
+        let unexpected: ExpressionError = ExpressionError::UnexpectedToken { position };
+
+        match token {
+            Token::Number(_) | Token::Boolean(_) | Token::Identifier(_) | Token::Open(_) if !expect_operand => {
+                return Err(unexpected);
+            }
+            Token::Number(number) => {
+                output.push(Instruction::Push(Value::Number(*number), position));
+                expect_operand = false;
+            }
+            Token::Boolean(boolean) => {
+                output.push(Instruction::Push(Value::Boolean(*boolean), position));
+                expect_operand = false;
+            }
+            Token::Identifier(name) => {
+                let is_call: bool = matches!(tokens.get(index + 1), Some(Spanned { token: Token::Open('('), .. }));
+                if is_call {
+                    pending.push(Pending::Open { bracket: '(', position, function: Some(name.clone()) });
+                    arities.push(0);
+                } else {
+                    output.push(Instruction::Load(name.clone(), position));
+                    expect_operand = false;
+                }
+            }
+            Token::Open(bracket) => {
+                let is_call_bracket: bool = *bracket == '(' && index > 0 && matches!(tokens[index - 1].token, Token::Identifier(_));
+                if !is_call_bracket {
+                    pending.push(Pending::Open { bracket: *bracket, position, function: None });
+                }
+            }
+            Token::Operator(operator) if expect_operand => match operator {
+                Operator::Subtract => pending.push(Pending::Operator(Operator::Negate, position)),
+                Operator::Not => pending.push(Pending::Operator(Operator::Not, position)),
+                Operator::Add => {}
+                _ => return Err(unexpected),
+            },
+            Token::Operator(Operator::Not) => return Err(unexpected),
+            Token::Operator(operator) => {
+                while let Some(Pending::Operator(top, top_position)) = pending.peek() {
+                    let outranks: bool = top.precedence() > operator.precedence()
+                        || (top.precedence() == operator.precedence() && !operator.is_right_associative());
+                    if !outranks {
+                        break;
+                    }
+                    pending.pop();
+                    output.push(Instruction::Apply(top, top_position));
+                }
+                pending.push(Pending::Operator(*operator, position));
+                expect_operand = true;
+            }
+            Token::Comma | Token::Close(_) => {
+                let empty_call: bool = matches!(token, Token::Close(_))
+                    && expect_operand
+                    && index > 0
+                    && matches!(tokens[index - 1].token, Token::Open('('))
+                    && index > 1
+                    && matches!(tokens[index - 2].token, Token::Identifier(_));
+                if expect_operand && !empty_call {
+                    return Err(unexpected);
+                }
+
+                let (bracket, open_position, function): (char, usize, Option<String>) = loop {
+                    match pending.pop() {
+                        Some(Pending::Operator(operator, operator_position)) => {
+                            output.push(Instruction::Apply(operator, operator_position))
+                        }
+                        Some(Pending::Open { bracket, position, function }) => break (bracket, position, function),
+                        None => match token {
+                            Token::Close(bracket) => {
+                                return Err(ExpressionError::UnopenedBracket { position, bracket: *bracket })
+                            }
+                            _ => return Err(unexpected),
+                        },
+                    }
+                };
+
+                match token {
+                    Token::Comma => {
+                        if function.is_none() {
+                            return Err(unexpected);
+                        }
+                        let arity: usize = arities.pop().unwrap_or(0);
+                        arities.push(arity + 1);
+                        pending.push(Pending::Open { bracket, position: open_position, function });
+                        expect_operand = true;
+                    }
+                    Token::Close(close) => {
+                        if matching_close(bracket) != *close {
+                            return Err(ExpressionError::MismatchedBracket { open: open_position, close: position });
+                        }
+                        if let Some(name) = function {
+                            let arity: usize = arities.pop().unwrap_or(0) + if empty_call { 0 } else { 1 };
+                            output.push(Instruction::Call { name, arity, position: open_position });
+                        }
+                        expect_operand = false;
+                    }
+                    _ => unreachable!("only commas and closing brackets get here"),
+                }
+            }
+        }
+    }
+
+    if expect_operand {
+        let position: usize = tokens.last().map(|spanned| spanned.position + 1).unwrap_or(0);
+        return Err(ExpressionError::UnexpectedToken { position });
+    }
+
+    while let Some(entry) = pending.pop() {
+        match entry {
+            Pending::Operator(operator, position) => output.push(Instruction::Apply(operator, position)),
+            Pending::Open { bracket, position, .. } => return Err(ExpressionError::UnclosedBracket { position, bracket }),
+        }
+    }
+
+    return Ok(output);
+}
+
+pub type FunctionBody = Box<dyn Fn(&[f64]) -> f64>;
+
+/// A function callable from expressions: a fixed number of numeric
+/// arguments in, one number out.
+pub struct Function {
+    pub arity: usize,
+    pub body: FunctionBody,
+}
+
+/// The variables and functions an expression can refer to.
+pub struct Environment {
+    variables: HashMap<String, Value>,
+    functions: HashMap<String, Function>,
+}
+
+impl Environment {
+    pub fn new() -> Self {
+        Environment { variables: HashMap::new(), functions: HashMap::new() }
+    }
+
+    /// `pi`, `e`, and `abs`, `sqrt`, `min`, `max`.
+    pub fn standard() -> Self {
+        let mut environment: Environment = Environment::new();
+        environment.set_variable("pi", Value::Number(std::f64::consts::PI));
+        environment.set_variable("e", Value::Number(std::f64::consts::E));
+        environment.define_function("abs", 1, |arguments| arguments[0].abs());
+        environment.define_function("sqrt", 1, |arguments| arguments[0].sqrt());
+        environment.define_function("min", 2, |arguments| arguments[0].min(arguments[1]));
+        environment.define_function("max", 2, |arguments| arguments[0].max(arguments[1]));
+        return environment;
+    }
+
+    pub fn set_variable(&mut self, name: &str, value: Value) {
+        self.variables.insert(name.to_string(), value);
+    }
+
+    pub fn define_function(&mut self, name: &str, arity: usize, body: impl Fn(&[f64]) -> f64 + 'static) {
+        self.functions.insert(name.to_string(), Function { arity, body: Box::new(body) });
+    }
+}
+
+fn apply(operator: Operator, left: Value, right: Value, position: usize) -> Result<Value, ExpressionError> {
+    let value: Value = match (operator, left, right) {
+        (Operator::Equal, left, right) => match (&left, &right) {
+            (Value::Number(_), Value::Number(_)) | (Value::Boolean(_), Value::Boolean(_)) => Value::Boolean(left == right),
+            _ => return Err(ExpressionError::TypeMismatch { position }),
+        },
+        (Operator::NotEqual, left, right) => match apply(Operator::Equal, left, right, position)? {
+            Value::Boolean(equal) => Value::Boolean(!equal),
+            Value::Number(_) => unreachable!("equality is boolean"),
+        },
+        (Operator::And, Value::Boolean(left), Value::Boolean(right)) => Value::Boolean(left && right),
+        (Operator::Or, Value::Boolean(left), Value::Boolean(right)) => Value::Boolean(left || right),
+        (Operator::Divide | Operator::Remainder, Value::Number(_), Value::Number(0.0)) => {
+            return Err(ExpressionError::DivisionByZero { position })
+        }
+        (operator, Value::Number(left), Value::Number(right)) => match operator {
+            Operator::Add => Value::Number(left + right),
+            Operator::Subtract => Value::Number(left - right),
+            Operator::Multiply => Value::Number(left * right),
+            Operator::Divide => Value::Number(left / right),
+            Operator::Remainder => Value::Number(left % right),
+            Operator::Power => Value::Number(left.powf(right)),
+            Operator::Less => Value::Boolean(left < right),
+            Operator::LessEqual => Value::Boolean(left <= right),
+            Operator::Greater => Value::Boolean(left > right),
+            Operator::GreaterEqual => Value::Boolean(left >= right),
+            _ => return Err(ExpressionError::TypeMismatch { position }),
+        },
+        _ => return Err(ExpressionError::TypeMismatch { position }),
+    };
+
+    return Ok(value);
+}
+
+/// Runs a postfix program on `operands`, which can be any of the crate's
+/// stacks; it should start empty.
+pub fn evaluate_with<S: Stack<Value>>(
+    program: &[Instruction],
+    environment: &Environment,
+    mut operands: S,
+) -> Result<Value, ExpressionError> {
+    for instruction in program {
+        match instruction {
+            Instruction::Push(value, _) => operands.push(value.clone()),
+            Instruction::Load(name, position) => match environment.variables.get(name) {
+                Some(value) => operands.push(value.clone()),
+                None => return Err(ExpressionError::UnknownVariable { position: *position, name: name.clone() }),
+            },
+            Instruction::Apply(operator, position) => {
+                let missing: ExpressionError = ExpressionError::UnexpectedToken { position: *position };
+                let right: Value = operands.pop().ok_or(missing.clone())?;
+                let value: Value = match (operator, right) {
+                    (Operator::Negate, Value::Number(number)) => Value::Number(-number),
+                    (Operator::Not, Value::Boolean(boolean)) => Value::Boolean(!boolean),
+                    (operator, _) if operator.is_unary() => return Err(ExpressionError::TypeMismatch { position: *position }),
+                    (operator, right) => {
+                        let left: Value = operands.pop().ok_or(missing)?;
+                        apply(*operator, left, right, *position)?
+                    }
+                };
+                operands.push(value);
+            }
+            Instruction::Call { name, arity, position } => {
+                let function: &Function = match environment.functions.get(name) {
+                    Some(function) => function,
+                    None => return Err(ExpressionError::UnknownFunction { position: *position, name: name.clone() }),
+                };
+                if function.arity != *arity {
+                    return Err(ExpressionError::WrongArgumentCount {
+                        position: *position,
+                        name: name.clone(),
+                        expected: function.arity,
+                        found: *arity,
+                    });
+                }
+
+                let mut arguments: Vec<f64> = vec![0.0; *arity];
+                for argument in arguments.iter_mut().rev() {
+                    match operands.pop() {
+                        Some(Value::Number(number)) => *argument = number,
+                        _ => return Err(ExpressionError::TypeMismatch { position: *position }),
+                    }
+                }
+                operands.push(Value::Number((function.body)(&arguments)));
+            }
+        }
+    }
+
+    let result: Option<Value> = operands.pop();
+    match (result, operands.is_empty()) {
+        (Some(value), true) => Ok(value),
+        _ => Err(ExpressionError::UnexpectedToken { position: 0 }),
+    }
+}
+
+pub fn evaluate(program: &[Instruction], environment: &Environment) -> Result<Value, ExpressionError> {
+    evaluate_with(program, environment, ArrayStack::new())
+}
+
+/// Brackets, tokens, postfix and evaluation in one go.
+pub fn evaluate_str(input: &str, environment: &Environment) -> Result<Value, ExpressionError> {
+    check_brackets(input)?;
+    let tokens: Vec<Spanned> = tokenize(input)?;
+    let program: Vec<Instruction> = to_postfix(&tokens)?;
+    evaluate(&program, environment)
+}
+
+/// The postfix form as space-separated text, for showing the conversion.
+pub fn postfix_string(program: &[Instruction]) -> String {
+    program.iter().map(|instruction| instruction.to_string()).collect::<Vec<String>>().join(" ")
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stacks::list_stack::ListStack;
+
+    fn postfix(input: &str) -> Result<String, ExpressionError> {
+        Ok(postfix_string(&to_postfix(&tokenize(input)?)?))
+    }
+
+    fn number(input: &str) -> f64 {
+        match evaluate_str(input, &Environment::standard()) {
+            Ok(Value::Number(number)) => number,
+            other => panic!("{} gave {:?}", input, other),
+        }
+    }
+
+    #[test]
+    fn test_tokenize() {
+        let tokens: Vec<Spanned> = tokenize("x1 >= 2.5").unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                Spanned { token: Token::Identifier("x1".to_string()), position: 0 },
+                Spanned { token: Token::Operator(Operator::GreaterEqual), position: 3 },
+                Spanned { token: Token::Number(2.5), position: 6 },
+            ]
+        );
+        assert_eq!(tokenize("1 = 2"), Err(ExpressionError::UnexpectedCharacter { position: 2, character: '=' }));
+        assert_eq!(tokenize("true||false").unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_check_brackets() {
+        assert_eq!(check_brackets("{[()()]}"), Ok(()));
+        assert_eq!(check_brackets("(1 + [2)]"), Err(ExpressionError::MismatchedBracket { open: 5, close: 7 }));
+        assert_eq!(check_brackets("(1))"), Err(ExpressionError::UnopenedBracket { position: 3, bracket: ')' }));
+        assert_eq!(check_brackets("((1)"), Err(ExpressionError::UnclosedBracket { position: 0, bracket: '(' }));
+    }
+
+    #[test]
+    fn test_to_postfix() {
+        assert_eq!(postfix("1 + 2 * 3"), Ok("1 2 3 * +".to_string()));
+        assert_eq!(postfix("(1 + 2) * 3"), Ok("1 2 + 3 *".to_string()));
+        assert_eq!(postfix("2 ^ 3 ^ 2"), Ok("2 3 2 ^ ^".to_string()));
+        assert_eq!(postfix("8 - 3 - 2"), Ok("8 3 - 2 -".to_string()));
+        assert_eq!(postfix("-x ^ 2"), Ok("x 2 ^ neg".to_string()));
+        assert_eq!(postfix("max(1, min(2, 3)) + f()"), Ok("1 2 3 min/2 max/2 f/0 +".to_string()));
+        assert_eq!(postfix("!a && b || c"), Ok("a ! b && c ||".to_string()));
+    }
+
+    #[test]
+    fn test_syntax_errors() {
+        assert_eq!(postfix("1 + * 2"), Err(ExpressionError::UnexpectedToken { position: 4 }));
+        assert_eq!(postfix("1 2"), Err(ExpressionError::UnexpectedToken { position: 2 }));
+        assert_eq!(postfix("1 +"), Err(ExpressionError::UnexpectedToken { position: 3 }));
+        assert_eq!(postfix("(1, 2)"), Err(ExpressionError::UnexpectedToken { position: 2 }));
+        assert_eq!(postfix("max(1,)"), Err(ExpressionError::UnexpectedToken { position: 6 }));
+        assert_eq!(postfix("(1]"), Err(ExpressionError::MismatchedBracket { open: 0, close: 2 }));
+        assert_eq!(postfix("1)"), Err(ExpressionError::UnopenedBracket { position: 1, bracket: ')' }));
+        assert_eq!(postfix("(1"), Err(ExpressionError::UnclosedBracket { position: 0, bracket: '(' }));
+        assert_eq!(postfix(""), Err(ExpressionError::UnexpectedToken { position: 0 }));
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(number("1 + 2 * 3"), 7.0);
+        assert_eq!(number("[1 + 2] * {3}"), 9.0);
+        assert_eq!(number("2 ^ 3 ^ 2"), 512.0);
+        assert_eq!(number("-2 ^ 2"), -4.0);
+        assert_eq!(number("2 ^ -1"), 0.5);
+        assert_eq!(number("--3 + +4"), 7.0);
+        assert_eq!(number("7 % 4 - 10 / 4"), 0.5);
+        assert_eq!(number("max(abs(-3), sqrt(16)) * 2"), 8.0);
+    }
+
+    #[test]
+    fn test_boolean_logic() {
+        let environment: Environment = Environment::standard();
+        assert_eq!(evaluate_str("1 < 2 && 2 <= 2", &environment), Ok(Value::Boolean(true)));
+        assert_eq!(evaluate_str("!(1 == 1) || 3 != 3", &environment), Ok(Value::Boolean(false)));
+        assert_eq!(evaluate_str("true == (2 > 1)", &environment), Ok(Value::Boolean(true)));
+        assert_eq!(evaluate_str("1 + true", &environment), Err(ExpressionError::TypeMismatch { position: 2 }));
+        assert_eq!(evaluate_str("!1", &environment), Err(ExpressionError::TypeMismatch { position: 0 }));
+        assert_eq!(evaluate_str("1 == true", &environment), Err(ExpressionError::TypeMismatch { position: 2 }));
+    }
+
+    #[test]
+    fn test_variables_and_functions() {
+        let mut environment: Environment = Environment::new();
+        environment.set_variable("rate", Value::Number(0.25));
+        environment.set_variable("enabled", Value::Boolean(true));
+        environment.define_function("clamp", 3, |arguments| arguments[0].max(arguments[1]).min(arguments[2]));
+
+        assert_eq!(evaluate_str("clamp(rate * 8, 0, 1)", &environment), Ok(Value::Number(1.0)));
+        assert_eq!(evaluate_str("enabled && rate > 0", &environment), Ok(Value::Boolean(true)));
+        assert_eq!(
+            evaluate_str("1 + missing", &environment),
+            Err(ExpressionError::UnknownVariable { position: 4, name: "missing".to_string() })
+        );
+        assert_eq!(
+            evaluate_str("nope(1)", &environment),
+            Err(ExpressionError::UnknownFunction { position: 0, name: "nope".to_string() })
+        );
+        assert_eq!(
+            evaluate_str("2 * clamp(1, 2)", &environment),
+            Err(ExpressionError::WrongArgumentCount { position: 4, name: "clamp".to_string(), expected: 3, found: 2 })
+        );
+        assert_eq!(evaluate_str("1 / (2 - 2)", &environment), Err(ExpressionError::DivisionByZero { position: 2 }));
+    }
+
+    #[test]
+    fn test_evaluate_on_list_stack() {
+        let program: Vec<Instruction> = to_postfix(&tokenize("(1 + 2) * max(3, 4) - 5").unwrap()).unwrap();
+        let environment: Environment = Environment::standard();
+        assert_eq!(evaluate_with(&program, &environment, ListStack::new()), Ok(Value::Number(7.0)));
+        assert_eq!(evaluate_with(&program, &environment, ArrayStack::new()), Ok(Value::Number(7.0)));
+    }
+
+    #[test]
+    fn test_error_messages() {
+        let error: ExpressionError = evaluate_str("(1 + 2", &Environment::new()).unwrap_err();
+        assert_eq!(error.to_string(), "'(' at 0 is never closed");
+    }
+}
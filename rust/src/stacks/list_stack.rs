@@ -0,0 +1,107 @@
+use std::cell::RefCell;
+use std::fmt::Debug;
+use std::rc::Rc;
+
+use super::stack::Stack;
+use crate::linked_lists::sllwt_rc_refcell::{LinkedList, Node};
+
+/// A stack on top of the crate's singly linked list. The top of the stack is
+/// the head of the list, so it only ever calls `push_to_front` and
+/// `pop_from_front`.
+///
+/// Both `push` and `pop` are O(n), since those list methods renumber every
+/// node through `update_indices`.
+#[derive(PartialEq, Debug)]
+pub struct ListStack<T> {
+    list: LinkedList<T>,
+}
+
+impl<T> ListStack<T>
+where
+    T: Clone + PartialEq + Debug,
+{
+    pub fn new() -> Self {
+        ListStack { list: LinkedList::new() }
+    }
+
+    /// Pushes the elements in order, so the last one ends up on top.
+    pub fn from(data_vec: Vec<T>) -> Self {
+        let mut stack: ListStack<T> = ListStack::new();
+
+        for data in data_vec {
+            stack.push(data);
+        }
+
+        return stack;
+    }
+
+    /// Hands back the underlying list, top of the stack first.
+    pub fn into_list(self) -> LinkedList<T> {
+        self.list
+    }
+}
+
+impl<T> Stack<T> for ListStack<T>
+where
+    T: Clone + PartialEq + Debug,
+{
+    fn push(&mut self, data: T) {
+        self.list.push_to_front(data);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.list.pop_from_front().ok()
+    }
+
+    fn peek(&self) -> Option<T> {
+        self.list.head.as_ref().and_then(|head| head.borrow().data.clone())
+    }
+
+    fn len(&self) -> usize {
+        self.list.length
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = T> + '_> {
+        Box::new(Iter { next: self.list.head.clone() })
+    }
+}
+
+struct Iter<T> {
+    next: Option<Rc<RefCell<Node<T>>>>,
+}
+
+impl<T: Clone> Iterator for Iter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let node: Rc<RefCell<Node<T>>> = self.next.take()?;
+        self.next = node.borrow().next.clone();
+        let data: Option<T> = node.borrow().data.clone();
+        data
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stacks::stack::behaviour;
+
+    #[test]
+    fn test_list_stack_behaviour() {
+        behaviour::starts_empty(ListStack::new());
+        behaviour::pops_in_reverse_order(ListStack::new());
+        behaviour::peek_does_not_remove(ListStack::new());
+        behaviour::iterates_top_to_bottom(ListStack::new());
+        behaviour::interleaves_push_and_pop(ListStack::new());
+        behaviour::survives_empty_pops(ListStack::new());
+    }
+
+    #[test]
+    fn test_list_stack_shares_list_order() {
+        let stack: ListStack<i64> = ListStack::from(vec![1, 2, 3]);
+        assert_eq!(stack.peek(), Some(3));
+        assert_eq!(stack.into_list(), LinkedList::from(vec![3, 2, 1]));
+    }
+}
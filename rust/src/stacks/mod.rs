@@ -0,0 +1,7 @@
+#![allow(clippy::needless_return, clippy::new_without_default)]
+
+pub mod stack;
+pub mod array_stack;
+pub mod list_stack;
+pub mod aggregate_stack;
+pub mod expression;
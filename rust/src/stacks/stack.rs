@@ -0,0 +1,94 @@
+/// A last-in, first-out collection. `peek` and `iter` hand out clones so the
+/// trait also fits stacks whose nodes sit behind `Rc<RefCell<_>>`.
+pub trait Stack<T: Clone> {
+    fn push(&mut self, data: T);
+
+    fn pop(&mut self) -> Option<T>;
+
+    /// The element `pop` would return, without removing it.
+    fn peek(&self) -> Option<T>;
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The elements from the top of the stack to the bottom.
+    fn iter(&self) -> Box<dyn Iterator<Item = T> + '_>;
+}
+
+
+
+/// Behaviour every `Stack` must have. Each implementation's tests run these
+/// against a fresh, empty stack.
+#[cfg(test)]
+pub(crate) mod behaviour {
+    use super::Stack;
+
+    pub fn starts_empty<S: Stack<i64>>(stack: S) {
+        assert!(stack.is_empty());
+        assert_eq!(stack.len(), 0);
+        assert_eq!(stack.peek(), None);
+        assert_eq!(stack.iter().next(), None);
+    }
+
+    pub fn pops_in_reverse_order<S: Stack<i64>>(mut stack: S) {
+        for data in 1..=5 {
+            stack.push(data);
+        }
+
+        assert_eq!(stack.len(), 5);
+        for data in (1..=5).rev() {
+            assert_eq!(stack.pop(), Some(data));
+        }
+        assert_eq!(stack.pop(), None);
+        assert!(stack.is_empty());
+    }
+
+    pub fn peek_does_not_remove<S: Stack<i64>>(mut stack: S) {
+        stack.push(1);
+        stack.push(2);
+
+        assert_eq!(stack.peek(), Some(2));
+        assert_eq!(stack.peek(), Some(2));
+        assert_eq!(stack.len(), 2);
+        assert_eq!(stack.pop(), Some(2));
+        assert_eq!(stack.peek(), Some(1));
+    }
+
+    pub fn iterates_top_to_bottom<S: Stack<i64>>(mut stack: S) {
+        for data in [3, 1, 4, 1, 5] {
+            stack.push(data);
+        }
+
+        assert_eq!(stack.iter().collect::<Vec<i64>>(), vec![5, 1, 4, 1, 3]);
+        assert_eq!(stack.len(), 5);
+    }
+
+    pub fn interleaves_push_and_pop<S: Stack<i64>>(mut stack: S) {
+        let mut model: Vec<i64> = Vec::new();
+
+        for step in 0..1000 {
+            if step % 3 == 2 {
+                assert_eq!(stack.pop(), model.pop());
+            } else {
+                stack.push(step);
+                model.push(step);
+            }
+            assert_eq!(stack.len(), model.len());
+            assert_eq!(stack.peek(), model.last().copied());
+        }
+
+        assert!(stack.iter().eq(model.into_iter().rev()));
+    }
+
+    pub fn survives_empty_pops<S: Stack<i64>>(mut stack: S) {
+        assert_eq!(stack.pop(), None);
+        assert_eq!(stack.pop(), None);
+        stack.push(7);
+        assert_eq!(stack.pop(), Some(7));
+        assert_eq!(stack.pop(), None);
+        assert_eq!(stack.len(), 0);
+    }
+}